@@ -0,0 +1,5 @@
+#!/usr/bin/env bash
+set -e
+
+dir=$(dirname "$(realpath "$0")")
+cbindgen --config "$dir/cbindgen.toml" --crate nucleo-capi --output "$dir/include/nucleo.h" "$dir"
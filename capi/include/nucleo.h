@@ -0,0 +1,343 @@
+/* SPDX-License-Identifier: MPL-2.0 */
+
+#ifndef NUCLEO_H
+#define NUCLEO_H
+
+/* Generated with cbindgen:0.29.4 */
+
+/* This file is generated by generate_header.sh, do not edit it manually. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// How to treat a case mismatch between two characters.
+typedef enum NucleoCaseMatching {
+  // Characters never match their case folded version (`a != A`).
+  NUCLEO_CASE_MATCHING_RESPECT,
+  // Characters always match their case folded version (`a == A`).
+  NUCLEO_CASE_MATCHING_IGNORE,
+  // Acts like `NUCLEO_CASE_MATCHING_IGNORE` if all characters in a pattern atom are
+  // lowercase and like `NUCLEO_CASE_MATCHING_RESPECT` otherwise.
+  NUCLEO_CASE_MATCHING_SMART,
+} NucleoCaseMatching;
+
+// How to handle unicode normalization.
+typedef enum NucleoNormalization {
+  // Characters never match their normalized version (`a != ä`).
+  NUCLEO_NORMALIZATION_NEVER,
+  // Acts like `NUCLEO_NORMALIZATION_NEVER` if any character in a pattern
+  // atom would need to be normalized. Otherwise normalization occurs
+  // (`a == ä` but `ä != a`).
+  NUCLEO_NORMALIZATION_SMART,
+} NucleoNormalization;
+
+// A handle that allows adding new items to a worker. Injectors may be used
+// from any thread.
+typedef struct NucleoInjector NucleoInjector;
+
+// A matcher holds the scratch memory and configuration required for
+// matching. Creating a matcher is relatively expensive so it should be reused
+// where possible. A matcher must not be used from multiple threads at the
+// same time.
+typedef struct NucleoMatcher NucleoMatcher;
+
+// A parsed fzf-style pattern (see the `Pattern::parse` documentation of
+// `nucleo-matcher` for the supported syntax).
+typedef struct NucleoPattern NucleoPattern;
+
+// A high level matcher that matches items on a background threadpool. Each
+// item is an opaque `void *` (owned by the caller) with one or more matcher
+// columns.
+//
+// A worker must not be used from multiple threads at the same time, use
+// injectors to add items from other threads.
+typedef struct NucleoWorker NucleoWorker;
+
+// A callback invoked (from a worker thread) whenever new results are
+// available and `nucleo_worker_tick` should be called.
+typedef void (*NucleoNotify)(void *data);
+
+// The status of a worker after a call to `nucleo_worker_tick`.
+typedef struct NucleoStatus {
+  // Whether the current snapshot has changed.
+  bool changed;
+  // Whether the matcher is still processing in the background.
+  bool running;
+} NucleoStatus;
+
+// A matched item in the current snapshot.
+typedef struct NucleoMatch {
+  uint32_t score;
+  // The index of the item, as returned by `nucleo_injector_push`.
+  uint32_t idx;
+} NucleoMatch;
+
+// A borrowed UTF-8 string.
+typedef struct NucleoStr {
+  const char *ptr;
+  size_t len;
+} NucleoStr;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a new matcher. If `match_paths` is `true` the matcher is
+// configured with bonuses appropriate for matching file paths.
+struct NucleoMatcher *nucleo_matcher_new(bool match_paths);
+
+// Frees a matcher created with `nucleo_matcher_new`.
+//
+// # Safety
+//
+// `matcher` must be `NULL` or a pointer returned by `nucleo_matcher_new`
+// that was not freed yet.
+void nucleo_matcher_free(struct NucleoMatcher *matcher);
+
+// Parses `text` into a new pattern.
+//
+// # Safety
+//
+// `text` must point to `len` readable bytes.
+struct NucleoPattern *nucleo_pattern_new(const char *text,
+                                         size_t len,
+                                         enum NucleoCaseMatching case_matching,
+                                         enum NucleoNormalization normalization);
+
+// Reparses `pattern` from `text`, reusing its allocations.
+//
+// # Safety
+//
+// `pattern` must be a valid pattern handle and `text` must point to `len`
+// readable bytes.
+void nucleo_pattern_reparse(struct NucleoPattern *pattern,
+                            const char *text,
+                            size_t len,
+                            enum NucleoCaseMatching case_matching,
+                            enum NucleoNormalization normalization);
+
+// Frees a pattern created with `nucleo_pattern_new`.
+//
+// # Safety
+//
+// `pattern` must be `NULL` or a pointer returned by `nucleo_pattern_new`
+// that was not freed yet.
+void nucleo_pattern_free(struct NucleoPattern *pattern);
+
+// Matches `pattern` against `haystack`. Returns `false` if the haystack does
+// not match, otherwise the score is written to `score` (which may be `NULL`).
+//
+// # Safety
+//
+// `pattern` and `matcher` must be valid handles, `haystack` must point to
+// `len` readable bytes and `score` must be `NULL` or valid for writes.
+bool nucleo_pattern_score(const struct NucleoPattern *pattern,
+                          struct NucleoMatcher *matcher,
+                          const char *haystack,
+                          size_t len,
+                          uint32_t *score);
+
+// Matches `pattern` against `haystack` and computes the (sorted and
+// deduplicated) character indices of the matched characters. Returns `false`
+// if the haystack does not match.
+//
+// At most `capacity` indices are written to `indices`, the total number of
+// indices is written to `indices_len`. If `indices_len` is larger than
+// `capacity` the output was truncated. `score` and `indices_len` may be
+// `NULL`.
+//
+// # Safety
+//
+// `pattern` and `matcher` must be valid handles, `haystack` must point to
+// `len` readable bytes, `indices` must be valid for writing `capacity`
+// elements and `score`/`indices_len` must be `NULL` or valid for writes.
+bool nucleo_pattern_indices(const struct NucleoPattern *pattern,
+                            struct NucleoMatcher *matcher,
+                            const char *haystack,
+                            size_t len,
+                            uint32_t *score,
+                            uint32_t *indices,
+                            size_t capacity,
+                            size_t *indices_len);
+
+// Creates a new worker with `columns` matcher columns.
+//
+// `notify` (which may be `NULL`) is called with `notify_data` everytime new
+// results are available. If `num_threads` is 0 one thread per hardware
+// thread is used. If `match_paths` is `true` the matcher is configured with
+// bonuses appropriate for matching file paths. Returns `NULL` if `columns`
+// is 0.
+struct NucleoWorker *nucleo_worker_new(NucleoNotify notify,
+                                       void *notify_data,
+                                       uint32_t num_threads,
+                                       uint32_t columns,
+                                       bool match_paths);
+
+// Frees a worker created with `nucleo_worker_new`. The item pointers are
+// owned by the caller and are not freed.
+//
+// # Safety
+//
+// `worker` must be `NULL` or a pointer returned by `nucleo_worker_new`
+// that was not freed yet.
+void nucleo_worker_free(struct NucleoWorker *worker);
+
+// Returns a new injector for adding items to `worker`. The injector must be
+// freed with `nucleo_injector_free`.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+struct NucleoInjector *nucleo_worker_injector(const struct NucleoWorker *worker);
+
+// Returns the number of injectors that can still add items to `worker`.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+size_t nucleo_worker_active_injectors(const struct NucleoWorker *worker);
+
+// Reparses the pattern of `column`. By passing `append` the caller promises
+// that the text passed to the previous call is a prefix of `text`. Does
+// nothing if `column` is out of bounds.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle and `text` must point to `len`
+// readable bytes.
+void nucleo_worker_reparse(struct NucleoWorker *worker,
+                           uint32_t column,
+                           const char *text,
+                           size_t len,
+                           enum NucleoCaseMatching case_matching,
+                           enum NucleoNormalization normalization,
+                           bool append);
+
+// Processes new results, waiting at most `timeout_ms` milliseconds for the
+// worker threads. This should be called regularly (for example each time a
+// frame is rendered) and after `notify` was invoked.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+struct NucleoStatus nucleo_worker_tick(struct NucleoWorker *worker, uint64_t timeout_ms);
+
+// Removes all items and disconnects all existing injectors. If
+// `clear_snapshot` is `true` the snapshot is cleared immediately, otherwise
+// it keeps the current matches until the matcher has run again.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+void nucleo_worker_restart(struct NucleoWorker *worker, bool clear_snapshot);
+
+// Sets whether matches are sorted by score (enabled by default).
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+void nucleo_worker_sort_results(struct NucleoWorker *worker, bool sort_results);
+
+// Sets whether the order of the input is reversed (disabled by default).
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+void nucleo_worker_reverse_items(struct NucleoWorker *worker, bool reverse_items);
+
+// Returns the total number of items in the current snapshot.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+uint32_t nucleo_worker_item_count(const struct NucleoWorker *worker);
+
+// Returns the number of matched items in the current snapshot.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+uint32_t nucleo_worker_matched_item_count(const struct NucleoWorker *worker);
+
+// Writes the `n`th match of the current snapshot to `out`. Returns `false`
+// if `n` is out of bounds.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle and `out` must be valid for writes.
+bool nucleo_worker_get_match(const struct NucleoWorker *worker,
+                             uint32_t n,
+                             struct NucleoMatch *out);
+
+// Returns the user pointer of the item at index `idx`. Returns `NULL` if the
+// item is not part of the current snapshot.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+void *nucleo_worker_get_item(const struct NucleoWorker *worker, uint32_t idx);
+
+// Returns the user pointer of the `n`th match of the current snapshot.
+// Returns `NULL` if `n` is out of bounds.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle.
+void *nucleo_worker_get_matched_item(const struct NucleoWorker *worker, uint32_t n);
+
+// Computes the (sorted and deduplicated) character indices of `column` of
+// the `n`th match of the current snapshot that were matched by the pattern.
+// Returns `false` if `n` or `column` are out of bounds.
+//
+// At most `capacity` indices are written to `indices`, the total number of
+// indices is written to `indices_len` (which may be `NULL`). If
+// `indices_len` is larger than `capacity` the output was truncated.
+//
+// # Safety
+//
+// `worker` must be a valid worker handle, `indices` must be valid for writing
+// `capacity` elements and `indices_len` must be `NULL` or valid for writes.
+bool nucleo_worker_match_indices(struct NucleoWorker *worker,
+                                 uint32_t n,
+                                 uint32_t column,
+                                 uint32_t *indices,
+                                 size_t capacity,
+                                 size_t *indices_len);
+
+// Adds an item to the worker and returns its index. `columns` points to
+// `num_columns` strings which are used as the matcher columns of the item.
+// Missing columns are left empty and excess columns are ignored.
+//
+// The item pointer `data` is never dereferenced by nucleo and must stay
+// valid for as long as the item can be retrieved from the worker.
+//
+// # Safety
+//
+// `injector` must be a valid injector handle and `columns` must point to
+// `num_columns` valid strings.
+uint32_t nucleo_injector_push(const struct NucleoInjector *injector,
+                              void *data,
+                              const struct NucleoStr *columns,
+                              size_t num_columns);
+
+// Returns the total number of items added to the worker of `injector`.
+//
+// # Safety
+//
+// `injector` must be a valid injector handle.
+uint32_t nucleo_injector_injected_items(const struct NucleoInjector *injector);
+
+// Frees an injector created with `nucleo_worker_injector`.
+//
+// # Safety
+//
+// `injector` must be `NULL` or a pointer returned by `nucleo_worker_injector`
+// that was not freed yet.
+void nucleo_injector_free(struct NucleoInjector *injector);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NUCLEO_H */
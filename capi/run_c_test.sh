@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+set -e
+
+# builds the static library and runs the C test program against it
+dir=$(dirname "$(realpath "$0")")
+target_dir=$(cargo metadata --format-version 1 --no-deps --manifest-path "$dir/Cargo.toml" | sed -n 's/.*"target_directory":"\([^"]*\)".*/\1/p')
+cargo build --manifest-path "$dir/Cargo.toml"
+cc -std=c11 -Wall -Wextra -Werror -I "$dir/include" "$dir/tests/test.c" \
+    "$target_dir/debug/libnucleo_capi.a" -lpthread -ldl -lm -o "$target_dir/debug/nucleo-capi-test"
+"$target_dir/debug/nucleo-capi-test"
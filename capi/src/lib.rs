@@ -0,0 +1,116 @@
+/*!
+C bindings for `nucleo`.
+
+This crate exposes both the low level matching algorithm (`nucleo-matcher`)
+and the high level [`Nucleo`](nucleo::Nucleo) worker through a C ABI so that
+nucleo can be used from other languages (for example nvim plugins). The
+corresponding header can be found in `include/nucleo.h`.
+
+All objects are exposed as opaque handles that are created with a `*_new`
+function and must be released with the corresponding `*_free` function.
+Passing `NULL` to a `*_free` function is a no-op.
+
+Strings are passed as a pointer/length pair and must be valid UTF-8. Invalid
+UTF-8 sequences are replaced with `U+FFFD`. Match indices are returned as
+character (grapheme) indices, the same indices the rust API returns for a
+[`Utf32Str`](nucleo::Utf32Str).
+*/
+
+use std::borrow::Cow;
+use std::os::raw::c_char;
+use std::slice;
+
+use nucleo::pattern::{CaseMatching, Normalization};
+
+pub use crate::matcher::*;
+pub use crate::pattern::*;
+pub use crate::worker::*;
+
+mod matcher;
+mod pattern;
+mod worker;
+
+#[cfg(test)]
+mod tests;
+
+/// How to treat a case mismatch between two characters.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NucleoCaseMatching {
+    /// Characters never match their case folded version (`a != A`).
+    Respect,
+    /// Characters always match their case folded version (`a == A`).
+    Ignore,
+    /// Acts like `NUCLEO_CASE_MATCHING_IGNORE` if all characters in a pattern atom are
+    /// lowercase and like `NUCLEO_CASE_MATCHING_RESPECT` otherwise.
+    Smart,
+}
+
+impl From<NucleoCaseMatching> for CaseMatching {
+    fn from(case_matching: NucleoCaseMatching) -> CaseMatching {
+        match case_matching {
+            NucleoCaseMatching::Respect => CaseMatching::Respect,
+            NucleoCaseMatching::Ignore => CaseMatching::Ignore,
+            NucleoCaseMatching::Smart => CaseMatching::Smart,
+        }
+    }
+}
+
+/// How to handle unicode normalization.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NucleoNormalization {
+    /// Characters never match their normalized version (`a != ä`).
+    Never,
+    /// Acts like `NUCLEO_NORMALIZATION_NEVER` if any character in a pattern
+    /// atom would need to be normalized. Otherwise normalization occurs
+    /// (`a == ä` but `ä != a`).
+    Smart,
+}
+
+impl From<NucleoNormalization> for Normalization {
+    fn from(normalization: NucleoNormalization) -> Normalization {
+        match normalization {
+            NucleoNormalization::Never => Normalization::Never,
+            NucleoNormalization::Smart => Normalization::Smart,
+        }
+    }
+}
+
+/// A borrowed UTF-8 string.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct NucleoStr {
+    pub ptr: *const c_char,
+    pub len: usize,
+}
+
+/// Converts a pointer/length pair to a string.
+///
+/// # Safety
+///
+/// `ptr` must point to `len` readable bytes (or be `NULL` if `len` is 0).
+unsafe fn str_from_raw<'a>(ptr: *const c_char, len: usize) -> Cow<'a, str> {
+    if len == 0 {
+        return Cow::Borrowed("");
+    }
+    String::from_utf8_lossy(slice::from_raw_parts(ptr.cast(), len))
+}
+
+/// Copies `indices` into the caller provided buffer `out` with `capacity`
+/// elements. Returns the total number of indices so that callers can detect
+/// truncation.
+///
+/// # Safety
+///
+/// `out` must be valid for writing `capacity` elements (or be `NULL` if
+/// `capacity` is 0).
+unsafe fn copy_indices(indices: &mut Vec<u32>, out: *mut u32, capacity: usize) -> usize {
+    indices.sort_unstable();
+    indices.dedup();
+    let len = indices.len().min(capacity);
+    if len != 0 {
+        out.copy_from_nonoverlapping(indices.as_ptr(), len);
+    }
+    indices.len()
+}
@@ -0,0 +1,40 @@
+use nucleo::{Config, Matcher};
+
+/// A matcher holds the scratch memory and configuration required for
+/// matching. Creating a matcher is relatively expensive so it should be reused
+/// where possible. A matcher must not be used from multiple threads at the
+/// same time.
+pub struct NucleoMatcher {
+    pub(crate) matcher: Matcher,
+    pub(crate) buf: Vec<char>,
+    pub(crate) indices: Vec<u32>,
+}
+
+/// Creates a new matcher. If `match_paths` is `true` the matcher is
+/// configured with bonuses appropriate for matching file paths.
+#[no_mangle]
+pub extern "C" fn nucleo_matcher_new(match_paths: bool) -> *mut NucleoMatcher {
+    let config = if match_paths {
+        Config::DEFAULT.match_paths()
+    } else {
+        Config::DEFAULT
+    };
+    Box::into_raw(Box::new(NucleoMatcher {
+        matcher: Matcher::new(config),
+        buf: Vec::new(),
+        indices: Vec::new(),
+    }))
+}
+
+/// Frees a matcher created with `nucleo_matcher_new`.
+///
+/// # Safety
+///
+/// `matcher` must be `NULL` or a pointer returned by `nucleo_matcher_new`
+/// that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_matcher_free(matcher: *mut NucleoMatcher) {
+    if !matcher.is_null() {
+        drop(Box::from_raw(matcher))
+    }
+}
@@ -0,0 +1,132 @@
+use std::os::raw::c_char;
+
+use nucleo::pattern::Pattern;
+use nucleo::Utf32Str;
+
+use crate::{copy_indices, str_from_raw, NucleoCaseMatching, NucleoMatcher, NucleoNormalization};
+
+/// A parsed fzf-style pattern (see the `Pattern::parse` documentation of
+/// `nucleo-matcher` for the supported syntax).
+pub struct NucleoPattern(pub(crate) Pattern);
+
+/// Parses `text` into a new pattern.
+///
+/// # Safety
+///
+/// `text` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_pattern_new(
+    text: *const c_char,
+    len: usize,
+    case_matching: NucleoCaseMatching,
+    normalization: NucleoNormalization,
+) -> *mut NucleoPattern {
+    let text = str_from_raw(text, len);
+    let pattern = Pattern::parse(&text, case_matching.into(), normalization.into());
+    Box::into_raw(Box::new(NucleoPattern(pattern)))
+}
+
+/// Reparses `pattern` from `text`, reusing its allocations.
+///
+/// # Safety
+///
+/// `pattern` must be a valid pattern handle and `text` must point to `len`
+/// readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_pattern_reparse(
+    pattern: *mut NucleoPattern,
+    text: *const c_char,
+    len: usize,
+    case_matching: NucleoCaseMatching,
+    normalization: NucleoNormalization,
+) {
+    let text = str_from_raw(text, len);
+    (*pattern)
+        .0
+        .reparse(&text, case_matching.into(), normalization.into());
+}
+
+/// Frees a pattern created with `nucleo_pattern_new`.
+///
+/// # Safety
+///
+/// `pattern` must be `NULL` or a pointer returned by `nucleo_pattern_new`
+/// that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_pattern_free(pattern: *mut NucleoPattern) {
+    if !pattern.is_null() {
+        drop(Box::from_raw(pattern))
+    }
+}
+
+/// Matches `pattern` against `haystack`. Returns `false` if the haystack does
+/// not match, otherwise the score is written to `score` (which may be `NULL`).
+///
+/// # Safety
+///
+/// `pattern` and `matcher` must be valid handles, `haystack` must point to
+/// `len` readable bytes and `score` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_pattern_score(
+    pattern: *const NucleoPattern,
+    matcher: *mut NucleoMatcher,
+    haystack: *const c_char,
+    len: usize,
+    score: *mut u32,
+) -> bool {
+    let matcher = &mut *matcher;
+    let haystack = str_from_raw(haystack, len);
+    let haystack = Utf32Str::new(&haystack, &mut matcher.buf);
+    let Some(res) = (*pattern).0.score(haystack, &mut matcher.matcher) else {
+        return false;
+    };
+    if !score.is_null() {
+        *score = res;
+    }
+    true
+}
+
+/// Matches `pattern` against `haystack` and computes the (sorted and
+/// deduplicated) character indices of the matched characters. Returns `false`
+/// if the haystack does not match.
+///
+/// At most `capacity` indices are written to `indices`, the total number of
+/// indices is written to `indices_len`. If `indices_len` is larger than
+/// `capacity` the output was truncated. `score` and `indices_len` may be
+/// `NULL`.
+///
+/// # Safety
+///
+/// `pattern` and `matcher` must be valid handles, `haystack` must point to
+/// `len` readable bytes, `indices` must be valid for writing `capacity`
+/// elements and `score`/`indices_len` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_pattern_indices(
+    pattern: *const NucleoPattern,
+    matcher: *mut NucleoMatcher,
+    haystack: *const c_char,
+    len: usize,
+    score: *mut u32,
+    indices: *mut u32,
+    capacity: usize,
+    indices_len: *mut usize,
+) -> bool {
+    let matcher = &mut *matcher;
+    let haystack = str_from_raw(haystack, len);
+    let haystack = Utf32Str::new(&haystack, &mut matcher.buf);
+    matcher.indices.clear();
+    let Some(res) = (*pattern)
+        .0
+        .indices(haystack, &mut matcher.matcher, &mut matcher.indices)
+    else {
+        return false;
+    };
+    let total = copy_indices(&mut matcher.indices, indices, capacity);
+    if !score.is_null() {
+        *score = res;
+    }
+    if !indices_len.is_null() {
+        *indices_len = total;
+    }
+    true
+}
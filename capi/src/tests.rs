@@ -0,0 +1,158 @@
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::*;
+
+fn str(s: &str) -> NucleoStr {
+    NucleoStr {
+        ptr: s.as_ptr().cast(),
+        len: s.len(),
+    }
+}
+
+#[test]
+fn pattern() {
+    unsafe {
+        let matcher = nucleo_matcher_new(false);
+        let text = "foo$";
+        let pattern = nucleo_pattern_new(
+            text.as_ptr().cast(),
+            text.len(),
+            NucleoCaseMatching::Smart,
+            NucleoNormalization::Smart,
+        );
+        let haystack = "barfoo";
+        let mut score = 0;
+        assert!(nucleo_pattern_score(
+            pattern,
+            matcher,
+            haystack.as_ptr().cast(),
+            haystack.len(),
+            &mut score
+        ));
+        assert_ne!(score, 0);
+
+        let mut indices = [0; 2];
+        let mut indices_len = 0;
+        let mut score2 = 0;
+        assert!(nucleo_pattern_indices(
+            pattern,
+            matcher,
+            haystack.as_ptr().cast(),
+            haystack.len(),
+            &mut score2,
+            indices.as_mut_ptr(),
+            indices.len(),
+            &mut indices_len,
+        ));
+        assert_eq!(score, score2);
+        assert_eq!(indices_len, 3);
+        assert_eq!(indices, [3, 4]);
+
+        let text = "baz";
+        nucleo_pattern_reparse(
+            pattern,
+            text.as_ptr().cast(),
+            text.len(),
+            NucleoCaseMatching::Smart,
+            NucleoNormalization::Smart,
+        );
+        assert!(!nucleo_pattern_score(
+            pattern,
+            matcher,
+            haystack.as_ptr().cast(),
+            haystack.len(),
+            ptr::null_mut()
+        ));
+        nucleo_pattern_free(pattern);
+        nucleo_matcher_free(matcher);
+        nucleo_pattern_free(ptr::null_mut());
+        nucleo_matcher_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn worker() {
+    let mut items = [1u32, 2, 3];
+    unsafe {
+        assert!(nucleo_worker_new(None, ptr::null_mut(), 1, 0, false).is_null());
+        let worker = nucleo_worker_new(None, ptr::null_mut(), 1, 2, false);
+        let injector = nucleo_worker_injector(worker);
+        assert_eq!(nucleo_worker_active_injectors(worker), 1);
+        let columns = [
+            [str("foo"), str("bar")],
+            [str("foobar"), str("baz")],
+            [str("qux"), str("bar")],
+        ];
+        for (item, columns) in items.iter_mut().zip(&columns) {
+            let data: *mut u32 = item;
+            nucleo_injector_push(injector, data.cast(), columns.as_ptr(), 2);
+        }
+        // missing columns are left empty
+        nucleo_injector_push(injector, ptr::null_mut(), ptr::null(), 0);
+        assert_eq!(nucleo_injector_injected_items(injector), 4);
+        nucleo_injector_free(injector);
+        assert_eq!(nucleo_worker_active_injectors(worker), 0);
+
+        let text = "foo";
+        nucleo_worker_reparse(
+            worker,
+            0,
+            text.as_ptr().cast(),
+            text.len(),
+            NucleoCaseMatching::Smart,
+            NucleoNormalization::Smart,
+            false,
+        );
+        let text = "ba";
+        nucleo_worker_reparse(
+            worker,
+            1,
+            text.as_ptr().cast(),
+            text.len(),
+            NucleoCaseMatching::Smart,
+            NucleoNormalization::Smart,
+            false,
+        );
+        while nucleo_worker_tick(worker, 10).running {}
+
+        assert_eq!(nucleo_worker_item_count(worker), 4);
+        assert_eq!(nucleo_worker_matched_item_count(worker), 2);
+        let mut m = NucleoMatch { score: 0, idx: 0 };
+        assert!(nucleo_worker_get_match(worker, 0, &mut m));
+        assert_eq!(m.idx, 0);
+        assert!(!nucleo_worker_get_match(worker, 2, &mut m));
+        let data = nucleo_worker_get_matched_item(worker, 1);
+        assert_eq!(*data.cast::<u32>(), 2);
+        assert_eq!(
+            nucleo_worker_get_item(worker, 2),
+            (&mut items[2] as *mut u32).cast::<c_void>()
+        );
+        assert!(nucleo_worker_get_item(worker, 4).is_null());
+        assert!(nucleo_worker_get_matched_item(worker, 2).is_null());
+
+        let mut indices = [0; 4];
+        let mut indices_len = 0;
+        assert!(nucleo_worker_match_indices(
+            worker,
+            1,
+            1,
+            indices.as_mut_ptr(),
+            indices.len(),
+            &mut indices_len
+        ));
+        assert_eq!(indices[..indices_len], [0, 1]);
+        assert!(!nucleo_worker_match_indices(
+            worker,
+            1,
+            2,
+            indices.as_mut_ptr(),
+            indices.len(),
+            &mut indices_len
+        ));
+
+        nucleo_worker_restart(worker, true);
+        assert_eq!(nucleo_worker_item_count(worker), 0);
+        nucleo_worker_free(worker);
+    }
+}
@@ -0,0 +1,400 @@
+use std::os::raw::{c_char, c_void};
+use std::sync::Arc;
+use std::{ptr, slice};
+
+use nucleo::{Config, Injector, Matcher, Nucleo, Status};
+
+use crate::{copy_indices, str_from_raw, NucleoCaseMatching, NucleoNormalization, NucleoStr};
+
+/// An opaque user pointer. Nucleo never dereferences these pointers so it's
+/// up to the caller to ensure they can be used from the worker threads.
+#[derive(Clone, Copy)]
+struct UserData(*mut c_void);
+
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+/// A callback invoked (from a worker thread) whenever new results are
+/// available and `nucleo_worker_tick` should be called.
+pub type NucleoNotify = Option<unsafe extern "C" fn(data: *mut c_void)>;
+
+/// A high level matcher that matches items on a background threadpool. Each
+/// item is an opaque `void *` (owned by the caller) with one or more matcher
+/// columns.
+///
+/// A worker must not be used from multiple threads at the same time, use
+/// injectors to add items from other threads.
+pub struct NucleoWorker {
+    nucleo: Nucleo<UserData>,
+    columns: u32,
+    matcher: Matcher,
+    indices: Vec<u32>,
+}
+
+/// A handle that allows adding new items to a worker. Injectors may be used
+/// from any thread.
+pub struct NucleoInjector(Injector<UserData>);
+
+/// The status of a worker after a call to `nucleo_worker_tick`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NucleoStatus {
+    /// Whether the current snapshot has changed.
+    pub changed: bool,
+    /// Whether the matcher is still processing in the background.
+    pub running: bool,
+}
+
+impl From<Status> for NucleoStatus {
+    fn from(status: Status) -> NucleoStatus {
+        NucleoStatus {
+            changed: status.changed,
+            running: status.running,
+        }
+    }
+}
+
+/// A matched item in the current snapshot.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NucleoMatch {
+    pub score: u32,
+    /// The index of the item, as returned by `nucleo_injector_push`.
+    pub idx: u32,
+}
+
+/// Creates a new worker with `columns` matcher columns.
+///
+/// `notify` (which may be `NULL`) is called with `notify_data` everytime new
+/// results are available. If `num_threads` is 0 one thread per hardware
+/// thread is used. If `match_paths` is `true` the matcher is configured with
+/// bonuses appropriate for matching file paths. Returns `NULL` if `columns`
+/// is 0.
+#[no_mangle]
+pub extern "C" fn nucleo_worker_new(
+    notify: NucleoNotify,
+    notify_data: *mut c_void,
+    num_threads: u32,
+    columns: u32,
+    match_paths: bool,
+) -> *mut NucleoWorker {
+    if columns == 0 {
+        return ptr::null_mut();
+    }
+    let config = if match_paths {
+        Config::DEFAULT.match_paths()
+    } else {
+        Config::DEFAULT
+    };
+    let notify_data = UserData(notify_data);
+    let notify: Arc<dyn Fn() + Sync + Send> = match notify {
+        Some(notify) => Arc::new(move || {
+            // capture the whole `UserData` (and not just the raw pointer)
+            let data = notify_data;
+            unsafe { notify(data.0) }
+        }),
+        None => Arc::new(|| ()),
+    };
+    let num_threads = (num_threads != 0).then_some(num_threads as usize);
+    let nucleo = Nucleo::new(config.clone(), notify, num_threads, columns);
+    Box::into_raw(Box::new(NucleoWorker {
+        nucleo,
+        columns,
+        matcher: Matcher::new(config),
+        indices: Vec::new(),
+    }))
+}
+
+/// Frees a worker created with `nucleo_worker_new`. The item pointers are
+/// owned by the caller and are not freed.
+///
+/// # Safety
+///
+/// `worker` must be `NULL` or a pointer returned by `nucleo_worker_new`
+/// that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_free(worker: *mut NucleoWorker) {
+    if !worker.is_null() {
+        drop(Box::from_raw(worker))
+    }
+}
+
+/// Returns a new injector for adding items to `worker`. The injector must be
+/// freed with `nucleo_injector_free`.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_injector(
+    worker: *const NucleoWorker,
+) -> *mut NucleoInjector {
+    Box::into_raw(Box::new(NucleoInjector((*worker).nucleo.injector())))
+}
+
+/// Returns the number of injectors that can still add items to `worker`.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_active_injectors(worker: *const NucleoWorker) -> usize {
+    (*worker).nucleo.active_injectors()
+}
+
+/// Reparses the pattern of `column`. By passing `append` the caller promises
+/// that the text passed to the previous call is a prefix of `text`. Does
+/// nothing if `column` is out of bounds.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle and `text` must point to `len`
+/// readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_reparse(
+    worker: *mut NucleoWorker,
+    column: u32,
+    text: *const c_char,
+    len: usize,
+    case_matching: NucleoCaseMatching,
+    normalization: NucleoNormalization,
+    append: bool,
+) {
+    let worker = &mut *worker;
+    if column >= worker.columns {
+        return;
+    }
+    let text = str_from_raw(text, len);
+    worker.nucleo.pattern.reparse(
+        column as usize,
+        &text,
+        case_matching.into(),
+        normalization.into(),
+        append,
+    );
+}
+
+/// Processes new results, waiting at most `timeout_ms` milliseconds for the
+/// worker threads. This should be called regularly (for example each time a
+/// frame is rendered) and after `notify` was invoked.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_tick(
+    worker: *mut NucleoWorker,
+    timeout_ms: u64,
+) -> NucleoStatus {
+    (*worker).nucleo.tick(timeout_ms).into()
+}
+
+/// Removes all items and disconnects all existing injectors. If
+/// `clear_snapshot` is `true` the snapshot is cleared immediately, otherwise
+/// it keeps the current matches until the matcher has run again.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_restart(worker: *mut NucleoWorker, clear_snapshot: bool) {
+    (*worker).nucleo.restart(clear_snapshot)
+}
+
+/// Sets whether matches are sorted by score (enabled by default).
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_sort_results(worker: *mut NucleoWorker, sort_results: bool) {
+    (*worker).nucleo.sort_results(sort_results)
+}
+
+/// Sets whether the order of the input is reversed (disabled by default).
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_reverse_items(
+    worker: *mut NucleoWorker,
+    reverse_items: bool,
+) {
+    (*worker).nucleo.reverse_items(reverse_items)
+}
+
+/// Returns the total number of items in the current snapshot.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_item_count(worker: *const NucleoWorker) -> u32 {
+    (*worker).nucleo.snapshot().item_count()
+}
+
+/// Returns the number of matched items in the current snapshot.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_matched_item_count(worker: *const NucleoWorker) -> u32 {
+    (*worker).nucleo.snapshot().matched_item_count()
+}
+
+/// Writes the `n`th match of the current snapshot to `out`. Returns `false`
+/// if `n` is out of bounds.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_get_match(
+    worker: *const NucleoWorker,
+    n: u32,
+    out: *mut NucleoMatch,
+) -> bool {
+    let Some(m) = (*worker).nucleo.snapshot().matches().get(n as usize) else {
+        return false;
+    };
+    *out = NucleoMatch {
+        score: m.score,
+        idx: m.idx,
+    };
+    true
+}
+
+/// Returns the user pointer of the item at index `idx`. Returns `NULL` if the
+/// item is not part of the current snapshot.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_get_item(
+    worker: *const NucleoWorker,
+    idx: u32,
+) -> *mut c_void {
+    let snapshot = (*worker).nucleo.snapshot();
+    if idx >= snapshot.item_count() {
+        return ptr::null_mut();
+    }
+    snapshot
+        .get_item(idx)
+        .map_or(ptr::null_mut(), |item| item.data.0)
+}
+
+/// Returns the user pointer of the `n`th match of the current snapshot.
+/// Returns `NULL` if `n` is out of bounds.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_get_matched_item(
+    worker: *const NucleoWorker,
+    n: u32,
+) -> *mut c_void {
+    (*worker)
+        .nucleo
+        .snapshot()
+        .get_matched_item(n)
+        .map_or(ptr::null_mut(), |item| item.data.0)
+}
+
+/// Computes the (sorted and deduplicated) character indices of `column` of
+/// the `n`th match of the current snapshot that were matched by the pattern.
+/// Returns `false` if `n` or `column` are out of bounds.
+///
+/// At most `capacity` indices are written to `indices`, the total number of
+/// indices is written to `indices_len` (which may be `NULL`). If
+/// `indices_len` is larger than `capacity` the output was truncated.
+///
+/// # Safety
+///
+/// `worker` must be a valid worker handle, `indices` must be valid for writing
+/// `capacity` elements and `indices_len` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_worker_match_indices(
+    worker: *mut NucleoWorker,
+    n: u32,
+    column: u32,
+    indices: *mut u32,
+    capacity: usize,
+    indices_len: *mut usize,
+) -> bool {
+    let worker = &mut *worker;
+    let snapshot = worker.nucleo.snapshot();
+    let Some(item) = snapshot.get_matched_item(n) else {
+        return false;
+    };
+    let Some(haystack) = item.matcher_columns.get(column as usize) else {
+        return false;
+    };
+    worker.indices.clear();
+    snapshot.pattern().column_pattern(column as usize).indices(
+        haystack.slice(..),
+        &mut worker.matcher,
+        &mut worker.indices,
+    );
+    let total = copy_indices(&mut worker.indices, indices, capacity);
+    if !indices_len.is_null() {
+        *indices_len = total;
+    }
+    true
+}
+
+/// Adds an item to the worker and returns its index. `columns` points to
+/// `num_columns` strings which are used as the matcher columns of the item.
+/// Missing columns are left empty and excess columns are ignored.
+///
+/// The item pointer `data` is never dereferenced by nucleo and must stay
+/// valid for as long as the item can be retrieved from the worker.
+///
+/// # Safety
+///
+/// `injector` must be a valid injector handle and `columns` must point to
+/// `num_columns` valid strings.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_injector_push(
+    injector: *const NucleoInjector,
+    data: *mut c_void,
+    columns: *const NucleoStr,
+    num_columns: usize,
+) -> u32 {
+    let columns = if num_columns == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(columns, num_columns)
+    };
+    (*injector).0.push(UserData(data), |_, dst| {
+        for (dst, src) in dst.iter_mut().zip(columns) {
+            *dst = str_from_raw(src.ptr, src.len).as_ref().into();
+        }
+    })
+}
+
+/// Returns the total number of items added to the worker of `injector`.
+///
+/// # Safety
+///
+/// `injector` must be a valid injector handle.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_injector_injected_items(injector: *const NucleoInjector) -> u32 {
+    (*injector).0.injected_items()
+}
+
+/// Frees an injector created with `nucleo_worker_injector`.
+///
+/// # Safety
+///
+/// `injector` must be `NULL` or a pointer returned by `nucleo_worker_injector`
+/// that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nucleo_injector_free(injector: *mut NucleoInjector) {
+    if !injector.is_null() {
+        drop(Box::from_raw(injector))
+    }
+}
@@ -0,0 +1,97 @@
+/* Exercises the nucleo C API, run with `run_c_test.sh`. */
+
+#include <assert.h>
+#include <stdatomic.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "nucleo.h"
+
+static NucleoStr str(const char *s) {
+  NucleoStr res = {s, strlen(s)};
+  return res;
+}
+
+static void notify(void *data) { atomic_fetch_add((atomic_int *)data, 1); }
+
+static void test_pattern(void) {
+  NucleoMatcher *matcher = nucleo_matcher_new(false);
+  const char *text = "^foo bar";
+  NucleoPattern *pattern =
+      nucleo_pattern_new(text, strlen(text), NUCLEO_CASE_MATCHING_SMART,
+                         NUCLEO_NORMALIZATION_SMART);
+
+  const char *haystack = "foo/baz/bar";
+  uint32_t score = 0;
+  assert(nucleo_pattern_score(pattern, matcher, haystack, strlen(haystack),
+                              &score));
+  assert(score > 0);
+
+  uint32_t indices[16];
+  size_t indices_len = 0;
+  uint32_t score2 = 0;
+  assert(nucleo_pattern_indices(pattern, matcher, haystack, strlen(haystack),
+                                &score2, indices, 16, &indices_len));
+  assert(score == score2);
+  assert(indices_len == 6);
+  uint32_t expected[] = {0, 1, 2, 8, 9, 10};
+  assert(memcmp(indices, expected, sizeof(expected)) == 0);
+
+  haystack = "bar/foo";
+  assert(!nucleo_pattern_score(pattern, matcher, haystack, strlen(haystack),
+                               NULL));
+
+  nucleo_pattern_free(pattern);
+  nucleo_matcher_free(matcher);
+}
+
+static void test_worker(void) {
+  atomic_int notified = 0;
+  NucleoWorker *worker = nucleo_worker_new(notify, &notified, 0, 1, true);
+  NucleoInjector *injector = nucleo_worker_injector(worker);
+
+  const char *items[] = {"src/lib.rs", "src/main.rs", "README.md",
+                         "src/worker.rs"};
+  for (size_t i = 0; i < 4; i++) {
+    NucleoStr column = str(items[i]);
+    assert(nucleo_injector_push(injector, (void *)items[i], &column, 1) == i);
+  }
+  nucleo_injector_free(injector);
+  assert(nucleo_worker_active_injectors(worker) == 0);
+
+  const char *text = "src rs";
+  nucleo_worker_reparse(worker, 0, text, strlen(text),
+                        NUCLEO_CASE_MATCHING_SMART, NUCLEO_NORMALIZATION_SMART,
+                        false);
+  while (nucleo_worker_tick(worker, 10).running) {
+  }
+
+  assert(nucleo_worker_item_count(worker) == 4);
+  assert(nucleo_worker_matched_item_count(worker) == 3);
+  for (uint32_t n = 0; n < 3; n++) {
+    NucleoMatch match;
+    assert(nucleo_worker_get_match(worker, n, &match));
+    const char *item = nucleo_worker_get_matched_item(worker, n);
+    assert(item == items[match.idx]);
+    assert(nucleo_worker_get_item(worker, match.idx) == item);
+    assert(strncmp(item, "src/", 4) == 0);
+
+    uint32_t indices[16];
+    size_t indices_len = 0;
+    assert(nucleo_worker_match_indices(worker, n, 0, indices, 16,
+                                       &indices_len));
+    assert(indices_len == 5);
+    assert(indices[0] == 0);
+  }
+  assert(nucleo_worker_get_matched_item(worker, 3) == NULL);
+  assert(atomic_load(&notified) > 0);
+
+  nucleo_worker_free(worker);
+}
+
+int main(void) {
+  test_pattern();
+  test_worker();
+  printf("all tests passed\n");
+  return 0;
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+pub const USAGE: &str = "\
+usage: nucleo [options]
+
+Reads items from stdin (one per line) and lets you interactively pick
+among them. The selected items are written to stdout.
+
+options:
+  -q, --query <STR>     start the finder with the given query
+  -f, --filter <STR>    filter mode, print all matches for <STR> and exit
+  -m, --multi           enable multi-select with tab/shift-tab
+      --no-sort         do not sort the results by score
+      --tac             reverse the order of the input
+      --print-query     print the query as the first line of the output
+      --print-score     in filter mode, prefix each match with its score
+      --print-indices   in filter mode, prefix each match with the (character)
+                        indices of the matched characters
+  -0, --exit-0          exit immediately when there is no match
+  -1, --select-1        automatically select the only match
+  -h, --help            print this help message
+  -V, --version         print version information
+";
+
+/// The command line options supported by the `nucleo` binary. The flags
+/// mirror their `fzf` counterparts.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Args {
+    pub query: String,
+    pub filter: Option<String>,
+    pub no_sort: bool,
+    pub tac: bool,
+    pub multi: bool,
+    pub print_query: bool,
+    pub print_score: bool,
+    pub print_indices: bool,
+    pub exit_0: bool,
+    pub select_1: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Help,
+    Version,
+    UnknownOption(String),
+    MissingValue(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Help => f.write_str(USAGE),
+            Error::Version => write!(f, "nucleo {}", env!("CARGO_PKG_VERSION")),
+            Error::UnknownOption(option) => write!(f, "unknown option: {option}"),
+            Error::MissingValue(option) => write!(f, "option {option} requires a value"),
+        }
+    }
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, Error> {
+        let mut res = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // support both `--query foo` and `--query=foo`
+            let (flag, mut value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_owned())),
+                _ => (&*arg, None),
+            };
+            let mut value = |name| {
+                value
+                    .take()
+                    .or_else(|| args.next())
+                    .ok_or(Error::MissingValue(name))
+            };
+            match flag {
+                "-q" | "--query" => res.query = value("--query")?,
+                "-f" | "--filter" => res.filter = Some(value("--filter")?),
+                "-m" | "--multi" => res.multi = true,
+                "+m" | "--no-multi" => res.multi = false,
+                "--no-sort" => res.no_sort = true,
+                "--tac" => res.tac = true,
+                "--print-query" => res.print_query = true,
+                "--print-score" => res.print_score = true,
+                "--print-indices" => res.print_indices = true,
+                "-0" | "--exit-0" => res.exit_0 = true,
+                "-1" | "--select-1" => res.select_1 = true,
+                "-h" | "--help" => return Err(Error::Help),
+                "-V" | "--version" => return Err(Error::Version),
+                _ => return Err(Error::UnknownOption(arg)),
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,51 @@
+use std::io::{self, Write};
+
+use nucleo::{Config, Matcher, Snapshot};
+
+/// Controls which information is printed for each match in filter mode.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OutputFormat {
+    /// Print the score of each match, followed by a tab.
+    pub score: bool,
+    /// Print the comma separated (character) indices of the matched
+    /// characters, followed by a tab.
+    pub indices: bool,
+}
+
+/// Writes all matches of `snapshot` to `out` in rank order, one per line.
+///
+/// Matching itself already happened in parallel on the nucleo threadpool,
+/// only the (optional) match indices are computed here.
+pub fn print_matches(
+    snapshot: &Snapshot<String>,
+    format: OutputFormat,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let pattern = snapshot.pattern().column_pattern(0);
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut indices = Vec::new();
+    for (m, item) in snapshot.matches().iter().zip(snapshot.matched_items(..)) {
+        if format.score {
+            write!(out, "{}\t", m.score)?;
+        }
+        if format.indices {
+            indices.clear();
+            pattern.indices(
+                item.matcher_columns[0].slice(..),
+                &mut matcher,
+                &mut indices,
+            );
+            indices.sort_unstable();
+            indices.dedup();
+            for (i, idx) in indices.iter().enumerate() {
+                if i != 0 {
+                    out.write_all(b",")?;
+                }
+                write!(out, "{idx}")?;
+            }
+            out.write_all(b"\t")?;
+        }
+        writeln!(out, "{}", item.data)?;
+    }
+    Ok(())
+}
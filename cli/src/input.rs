@@ -0,0 +1,55 @@
+use std::io::{self, BufRead, BufReader, Read};
+
+use nucleo::{Injector, Utf32String};
+
+/// Fills the matcher column of an item, items only have a single column.
+// the signature is dictated by `Injector::push`/`Injector::extend`
+#[allow(clippy::ptr_arg)]
+pub fn fill_columns(line: &String, columns: &mut [Utf32String]) {
+    columns[0] = line.as_str().into();
+}
+
+/// Reads newline separated items from `reader` and streams them into the
+/// matcher until EOF is reached.
+///
+/// Lines are injected in batches: every time the underlying reader returns
+/// a chunk of data all complete lines in that chunk are passed to
+/// [`Injector::extend`] at once. This keeps the overhead low for large inputs
+/// while still showing items from slow producers without delay.
+pub fn read_items(reader: impl Read, injector: &Injector<String>) -> io::Result<()> {
+    let mut reader = BufReader::with_capacity(64 * 1024, reader);
+    let mut partial = Vec::new();
+    let mut batch = Vec::with_capacity(1024);
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf.is_empty() {
+            break;
+        }
+        let len = buf.len();
+        let mut rest = buf;
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            partial.extend_from_slice(&rest[..pos]);
+            batch.push(take_line(&mut partial));
+            rest = &rest[pos + 1..];
+        }
+        partial.extend_from_slice(rest);
+        reader.consume(len);
+        if !batch.is_empty() {
+            injector.extend(batch.drain(..), fill_columns);
+        }
+    }
+    if !partial.is_empty() {
+        injector.push(take_line(&mut partial), fill_columns);
+    }
+    Ok(())
+}
+
+fn take_line(buf: &mut Vec<u8>) -> String {
+    let line = String::from_utf8_lossy(buf).into_owned();
+    buf.clear();
+    line
+}
@@ -0,0 +1,145 @@
+//! `nucleo` is a small fzf-like fuzzy finder built on top of the `nucleo`
+//! crate. Items are read from stdin (one per line) and the selected items are
+//! written to stdout. The UI is drawn on stderr.
+//!
+//! With `--filter` no UI is shown, instead all matches are printed to stdout
+//! in rank order (optionally with their score and match indices). Matching
+//! runs on the same parallel worker as the interactive mode so this scales to
+//! millions of lines.
+//!
+//! The exit status follows fzf: `0` if an item was selected, `1` if there was
+//! no match, `2` on error and `130` if the user aborted.
+
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::thread;
+
+use nucleo::pattern::{CaseMatching, Normalization};
+use nucleo::{Config, Nucleo};
+
+use crate::args::Args;
+use crate::filter::OutputFormat;
+use crate::picker::{Outcome, Picker};
+
+mod args;
+mod filter;
+mod input;
+mod picker;
+
+#[cfg(test)]
+mod tests;
+
+const EXIT_NO_MATCH: u8 = 1;
+const EXIT_ERROR: u8 = 2;
+const EXIT_INTERRUPTED: u8 = 130;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err @ (args::Error::Help | args::Error::Version)) => {
+            println!("{err}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("nucleo: {err}");
+            eprintln!("try 'nucleo --help' for more information");
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+    match run(args) {
+        Ok(code) => code,
+        // a closed stdout (for example `nucleo | head -1`) is not an error
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("nucleo: {err}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(args: Args) -> io::Result<ExitCode> {
+    if io::stdin().is_terminal() {
+        eprintln!("nucleo: no input, pipe the items to pick from into stdin");
+        return Ok(ExitCode::from(EXIT_ERROR));
+    }
+    let mut nucleo = Nucleo::new(Config::DEFAULT, Arc::new(|| {}), None, 1);
+    nucleo.sort_results(!args.no_sort);
+    nucleo.reverse_items(args.tac);
+
+    let injector = nucleo.injector();
+    // the reader thread holds the only injector, once it exits
+    // `active_injectors` drops to zero and all input has been received
+    let reader = thread::spawn(move || input::read_items(io::stdin(), &injector));
+
+    let query = args.filter.as_deref().unwrap_or(&args.query);
+    nucleo
+        .pattern
+        .reparse(0, query, CaseMatching::Smart, Normalization::Smart, false);
+
+    if args.filter.is_some() || args.exit_0 || args.select_1 {
+        nucleo.tick_until_done(None);
+        reader.join().expect("stdin reader panicked")?;
+    }
+
+    if let Some(filter) = &args.filter {
+        let snapshot = nucleo.snapshot();
+        // stdout is line buffered by default which is way too slow for
+        // printing millions of matches
+        let mut stdout = BufWriter::with_capacity(64 * 1024, io::stdout().lock());
+        if args.print_query {
+            writeln!(stdout, "{filter}")?;
+        }
+        let format = OutputFormat {
+            score: args.print_score,
+            indices: args.print_indices,
+        };
+        filter::print_matches(snapshot, format, &mut stdout)?;
+        stdout.flush()?;
+        return Ok(exit_status(snapshot.matched_item_count() != 0));
+    }
+
+    let snapshot = nucleo.snapshot();
+    if args.exit_0 && snapshot.matched_item_count() == 0 {
+        if args.print_query {
+            println!("{}", args.query);
+        }
+        return Ok(ExitCode::from(EXIT_NO_MATCH));
+    }
+    if args.select_1 && snapshot.matched_item_count() == 1 {
+        let mut stdout = io::stdout().lock();
+        if args.print_query {
+            writeln!(stdout, "{}", args.query)?;
+        }
+        writeln!(stdout, "{}", snapshot.get_matched_item(0).unwrap().data)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut picker = Picker::new(&mut nucleo, args.query, args.multi);
+    let outcome = picker.run()?;
+    let query = picker.query().to_owned();
+    let selection = match outcome {
+        Outcome::Accept(selection) => selection,
+        Outcome::Abort => return Ok(ExitCode::from(EXIT_INTERRUPTED)),
+    };
+    let snapshot = nucleo.snapshot();
+    let mut stdout = io::stdout().lock();
+    if args.print_query {
+        writeln!(stdout, "{query}")?;
+    }
+    for &idx in &selection {
+        if let Some(item) = snapshot.get_item(idx) {
+            writeln!(stdout, "{}", item.data)?;
+        }
+    }
+    stdout.flush()?;
+    Ok(exit_status(!selection.is_empty()))
+}
+
+fn exit_status(matched: bool) -> ExitCode {
+    if matched {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_NO_MATCH)
+    }
+}
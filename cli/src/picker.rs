@@ -0,0 +1,314 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use nucleo::pattern::{CaseMatching, Normalization};
+use nucleo::{Config, Matcher, Nucleo};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Number of lines above the item list (prompt and info line).
+const HEADER_LINES: u16 = 2;
+
+/// What to do after a key press has been processed.
+enum Action {
+    Continue,
+    Accept,
+    Abort,
+}
+
+/// The result of an interactive session.
+pub enum Outcome {
+    /// The indices of the accepted items, in the order they were selected.
+    Accept(Vec<u32>),
+    Abort,
+}
+
+/// Puts the terminal into raw mode and the alternate screen for as long as
+/// it's alive. The UI is drawn on stderr so that stdout can be piped.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        if let Err(err) = execute!(io::stderr(), EnterAlternateScreen) {
+            let _ = terminal::disable_raw_mode();
+            return Err(err);
+        }
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stderr(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// An fzf-like interactive picker. The prompt is drawn in the first line of
+/// the screen followed by an info line and the list of matched items.
+pub struct Picker<'a> {
+    nucleo: &'a mut Nucleo<String>,
+    query: String,
+    multi: bool,
+    /// Position of the cursor in the list of matches.
+    cursor: u32,
+    /// Number of matches scrolled out of view above the list.
+    offset: u32,
+    matcher: Matcher,
+}
+
+impl<'a> Picker<'a> {
+    pub fn new(nucleo: &'a mut Nucleo<String>, query: String, multi: bool) -> Picker<'a> {
+        nucleo.track_match_ranks(true);
+        Picker {
+            nucleo,
+            query,
+            multi,
+            cursor: 0,
+            offset: 0,
+            matcher: Matcher::new(Config::DEFAULT),
+        }
+    }
+
+    /// The current query text.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Runs the picker until the user accepts or aborts.
+    pub fn run(&mut self) -> io::Result<Outcome> {
+        let _guard = TerminalGuard::enter()?;
+        let mut stderr = io::stderr().lock();
+        let mut redraw = true;
+        loop {
+            let cursor_item = self.cursor_item();
+            let status = self.nucleo.tick(10);
+            if status.changed {
+                self.follow_cursor(cursor_item);
+                redraw = true;
+            }
+            if redraw {
+                self.draw(&mut stderr, status.running)?;
+                redraw = false;
+            }
+            if !event::poll(Duration::from_millis(16))? {
+                continue;
+            }
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    match self.handle_key(key) {
+                        Action::Continue => (),
+                        Action::Accept => return Ok(Outcome::Accept(self.accepted())),
+                        Action::Abort => return Ok(Outcome::Abort),
+                    }
+                    redraw = true;
+                }
+                Event::Resize(..) => redraw = true,
+                _ => (),
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Action::Abort,
+            KeyCode::Char('c' | 'g' | 'q') if ctrl => return Action::Abort,
+            KeyCode::Enter => return Action::Accept,
+            KeyCode::Char('m') if ctrl => return Action::Accept,
+            KeyCode::Up => self.move_cursor(1),
+            KeyCode::Down => self.move_cursor(-1),
+            KeyCode::Char('p' | 'k') if ctrl => self.move_cursor(1),
+            KeyCode::Char('n' | 'j') if ctrl => self.move_cursor(-1),
+            KeyCode::Tab if self.multi => {
+                self.toggle_selection();
+                self.move_cursor(-1);
+            }
+            KeyCode::BackTab if self.multi => {
+                self.toggle_selection();
+                self.move_cursor(1);
+            }
+            KeyCode::Backspace | KeyCode::Char('h') if ctrl || key.code == KeyCode::Backspace => {
+                if self.query.pop().is_none() {
+                    return Action::Continue;
+                }
+                self.update_pattern(false);
+            }
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.update_pattern(false);
+            }
+            KeyCode::Char('w') if ctrl => {
+                let trimmed = self.query.trim_end_matches(' ').len();
+                let word_start = self.query[..trimmed].rfind(' ').map_or(0, |i| i + 1);
+                self.query.truncate(word_start);
+                self.update_pattern(false);
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.update_pattern(true);
+            }
+            _ => (),
+        }
+        Action::Continue
+    }
+
+    fn update_pattern(&mut self, append: bool) {
+        self.nucleo.pattern.reparse(
+            0,
+            &self.query,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            append,
+        );
+        self.cursor = 0;
+        self.offset = 0;
+    }
+
+    /// Moves the cursor `delta` matches towards the end of the list (the
+    /// best matches are at the top).
+    fn move_cursor(&mut self, delta: i32) {
+        let matched = self.nucleo.snapshot().matched_item_count();
+        if matched == 0 {
+            return;
+        }
+        self.cursor = (self.cursor as i64 - delta as i64).clamp(0, matched as i64 - 1) as u32;
+    }
+
+    /// The index of the item under the cursor, `None` if the cursor is at
+    /// the top of the list (where it stays when new matches arrive).
+    fn cursor_item(&self) -> Option<u32> {
+        if self.cursor == 0 {
+            return None;
+        }
+        let m = self.nucleo.snapshot().matches().get(self.cursor as usize)?;
+        Some(m.idx)
+    }
+
+    /// Keeps the cursor on `cursor_item` after the matches changed.
+    fn follow_cursor(&mut self, cursor_item: Option<u32>) {
+        let snapshot = self.nucleo.snapshot();
+        if let Some(rank) = cursor_item.and_then(|idx| snapshot.match_rank(idx)) {
+            self.cursor = rank;
+        }
+        let matched = snapshot.matched_item_count();
+        self.cursor = self.cursor.min(matched.saturating_sub(1));
+    }
+
+    fn toggle_selection(&mut self) {
+        let Some(&m) = self.nucleo.snapshot().matches().get(self.cursor as usize) else {
+            return;
+        };
+        self.nucleo.selection_mut().toggle(m.idx);
+    }
+
+    fn accepted(&self) -> Vec<u32> {
+        let snapshot = self.nucleo.snapshot();
+        if !snapshot.selection().is_empty() {
+            return snapshot.selection().iter().collect();
+        }
+        snapshot
+            .matches()
+            .get(self.cursor as usize)
+            .map(|m| vec![m.idx])
+            .unwrap_or_default()
+    }
+
+    fn draw(&mut self, out: &mut impl Write, running: bool) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let list_height = height.saturating_sub(HEADER_LINES) as u32;
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if list_height != 0 && self.cursor >= self.offset + list_height {
+            self.offset = self.cursor + 1 - list_height;
+        }
+
+        queue!(out, Hide, MoveTo(0, 0), Clear(ClearType::All))?;
+        let snapshot = self.nucleo.snapshot();
+        let mut info = format!(
+            "  {}/{}",
+            snapshot.matched_item_count(),
+            snapshot.item_count()
+        );
+        if self.multi && !snapshot.selection().is_empty() {
+            info.push_str(&format!(" ({})", snapshot.selection().len()));
+        }
+        if running {
+            info.push_str(" ...");
+        }
+        queue!(
+            out,
+            MoveTo(0, 1),
+            SetForegroundColor(Color::DarkGrey),
+            Print(truncate(&info, width as usize)),
+            ResetColor
+        )?;
+
+        let end = snapshot.matched_item_count().min(self.offset + list_height);
+        for (row, n) in (self.offset..end).enumerate() {
+            let m = snapshot.matches()[n as usize];
+            let Some(item) = snapshot.get_item(m.idx) else {
+                continue;
+            };
+            let highlights = snapshot
+                .match_highlights(n, &mut self.matcher)
+                .unwrap_or_default();
+
+            let is_cursor = n == self.cursor;
+            let is_selected = snapshot.selection().is_selected(m.idx);
+            queue!(
+                out,
+                MoveTo(0, row as u16 + HEADER_LINES),
+                SetForegroundColor(Color::Red),
+                Print(if is_cursor { '>' } else { ' ' }),
+                Print(if is_selected { '*' } else { ' ' }),
+                ResetColor
+            )?;
+            if is_cursor {
+                queue!(out, SetAttribute(Attribute::Bold))?;
+            }
+            let mut highlights = highlights.first().into_iter().flatten().peekable();
+            let text_width = (width as usize).saturating_sub(2);
+            for (i, grapheme) in item.data.graphemes(true).take(text_width).enumerate() {
+                let i = i as u32;
+                while highlights.next_if(|range| range.end <= i).is_some() {}
+                let highlight = matches!(highlights.peek(), Some(range) if range.contains(&i));
+                if highlight {
+                    queue!(out, SetForegroundColor(Color::Green))?;
+                }
+                if grapheme.chars().any(char::is_control) {
+                    queue!(out, Print(' '))?;
+                } else {
+                    queue!(out, Print(grapheme))?;
+                }
+                if highlight {
+                    queue!(out, ResetColor)?;
+                }
+            }
+            queue!(out, SetAttribute(Attribute::Reset))?;
+        }
+
+        let prompt = format!("> {}", self.query);
+        let prompt_width = prompt.graphemes(true).count().min(width as usize);
+        queue!(
+            out,
+            MoveTo(0, 0),
+            Print(truncate(&prompt, width as usize)),
+            MoveTo(prompt_width as u16, 0),
+            Show
+        )?;
+        out.flush()
+    }
+}
+
+fn truncate(text: &str, width: usize) -> &str {
+    match text.grapheme_indices(true).nth(width) {
+        Some((i, _)) => &text[..i],
+        None => text,
+    }
+}
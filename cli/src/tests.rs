@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use nucleo::pattern::{CaseMatching, Normalization};
+use nucleo::{Config, Nucleo};
+
+use crate::args::{Args, Error};
+use crate::filter::{print_matches, OutputFormat};
+use crate::input::read_items;
+
+fn parse(args: &[&str]) -> Result<Args, Error> {
+    Args::parse(args.iter().map(|&arg| arg.to_owned()))
+}
+
+#[test]
+fn parse_flags() {
+    assert_eq!(parse(&[]), Ok(Args::default()));
+    assert_eq!(
+        parse(&[
+            "--no-sort",
+            "--tac",
+            "-m",
+            "--print-query",
+            "--print-score",
+            "--print-indices",
+            "-0",
+            "-1"
+        ]),
+        Ok(Args {
+            no_sort: true,
+            tac: true,
+            multi: true,
+            print_query: true,
+            print_score: true,
+            print_indices: true,
+            exit_0: true,
+            select_1: true,
+            ..Args::default()
+        })
+    );
+    assert_eq!(parse(&["-m", "+m"]), Ok(Args::default()));
+}
+
+#[test]
+fn parse_values() {
+    let args = parse(&["-q", "foo bar", "--filter=baz"]).unwrap();
+    assert_eq!(args.query, "foo bar");
+    assert_eq!(args.filter.as_deref(), Some("baz"));
+    let args = parse(&["--query=a=b", "-f", ""]).unwrap();
+    assert_eq!(args.query, "a=b");
+    assert_eq!(args.filter.as_deref(), Some(""));
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse(&["--help"]), Err(Error::Help));
+    assert_eq!(parse(&["-V"]), Err(Error::Version));
+    assert_eq!(parse(&["--query"]), Err(Error::MissingValue("--query")));
+    assert_eq!(
+        parse(&["--bogus"]),
+        Err(Error::UnknownOption("--bogus".to_owned()))
+    );
+    assert_eq!(
+        parse(&["-m=1"]),
+        Err(Error::UnknownOption("-m=1".to_owned()))
+    );
+}
+
+#[test]
+fn read_lines() {
+    let nucleo: Nucleo<String> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    read_items(&b"foo\nbar\n\nb\xffz\nlast"[..], &injector).unwrap();
+    let items: Vec<_> = (0..injector.injected_items())
+        .map(|i| injector.get(i).unwrap().data.clone())
+        .collect();
+    assert_eq!(items, ["foo", "bar", "", "b\u{FFFD}z", "last"]);
+    assert_eq!(
+        injector.get(0).unwrap().matcher_columns[0].to_string(),
+        "foo"
+    );
+}
+
+#[test]
+fn filter_output() {
+    let mut nucleo: Nucleo<String> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(2), 1);
+    let injector = nucleo.injector();
+    read_items(&b"src/lib.rs\nREADME.md\nsrc/tests.rs\n"[..], &injector).unwrap();
+    drop(injector);
+    nucleo
+        .pattern
+        .reparse(0, "rs", CaseMatching::Smart, Normalization::Smart, false);
+    nucleo.tick_until_done(None);
+
+    let mut out = Vec::new();
+    print_matches(nucleo.snapshot(), OutputFormat::default(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "src/lib.rs\nsrc/tests.rs\n"
+    );
+
+    let format = OutputFormat {
+        score: true,
+        indices: true,
+    };
+    let mut out = Vec::new();
+    print_matches(nucleo.snapshot(), format, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<_> = out
+        .lines()
+        .map(|line| line.split('\t').collect::<Vec<_>>())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0][1..], ["8,9", "src/lib.rs"]);
+    assert_eq!(lines[1][1..], ["10,11", "src/tests.rs"]);
+    assert_eq!(
+        lines[0][0].parse::<u32>().unwrap(),
+        nucleo.snapshot().matches()[0].score
+    );
+}
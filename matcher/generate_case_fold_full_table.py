@@ -0,0 +1,35 @@
+#!/usr/bin/env python3
+"""Generates the table of full (multi char) case folds from CaseFolding.txt.
+
+Combining marks are removed from the folded text because haystacks only
+contain the first char of each grapheme.
+"""
+
+import sys
+
+
+def is_combining(c):
+    return 0x300 <= c <= 0x36F
+
+
+folds = []
+with open(sys.argv[1]) as f:
+    for line in f:
+        line = line.split("#", 1)[0].strip()
+        if not line:
+            continue
+        code, status, mapping = [field.strip() for field in line.split(";")[:3]]
+        if status != "F":
+            continue
+        folded = [int(c, 16) for c in mapping.split()]
+        folded = "".join(chr(c) for c in folded if not is_combining(c))
+        folds.append((int(code, 16), folded))
+
+print("// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:")
+print("//")
+print("//   generate_case_fold_full_table.py CaseFolding.txt")
+print("")
+print("pub const CASE_FOLDING_FULL: &'static [(char, &'static str)] = &[")
+for code, folded in sorted(folds):
+    print("  ('%s', \"%s\")," % (chr(code), folded))
+print("];")
@@ -10,4 +10,5 @@ unzip UCD.zip
 cd "${dir}"
 cargo install ucd-generate
 ucd-generate case-folding-simple /tmp/ucd-15.0.0 --chars > src/chars/case_fold.rs
+./generate_case_fold_full_table.py /tmp/ucd-15.0.0/CaseFolding.txt > src/chars/case_fold_full.rs
 rm -rf /tmp/ucd-15.0.0
@@ -0,0 +1,59 @@
+#!/usr/bin/env python3
+"""Generates the table that transliterates Cyrillic and Greek chars to Latin.
+
+Letters with diacritics that are not listed explicitly are transliterated
+like their base letter. Uppercase letters are transliterated like their
+lowercase version with the first char in uppercase (`Ж` to `Zh`).
+"""
+
+import unicodedata
+
+CYRILLIC = {
+    "а": "a", "б": "b", "в": "v", "г": "g", "д": "d", "е": "e", "ж": "zh",
+    "з": "z", "и": "i", "й": "y", "к": "k", "л": "l", "м": "m", "н": "n",
+    "о": "o", "п": "p", "р": "r", "с": "s", "т": "t", "у": "u", "ф": "f",
+    "х": "kh", "ц": "ts", "ч": "ch", "ш": "sh", "щ": "shch", "ъ": "",
+    "ы": "y", "ь": "", "э": "e", "ю": "yu", "я": "ya",
+    # Ukrainian and Belarusian
+    "є": "ye", "і": "i", "ї": "yi", "ґ": "g", "ў": "u",
+    # Serbian and Macedonian
+    "ђ": "dj", "ј": "j", "љ": "lj", "њ": "nj", "ћ": "c", "џ": "dz",
+    "ѓ": "gj", "ќ": "kj", "ѕ": "dz",
+}
+
+GREEK = {
+    "α": "a", "β": "v", "γ": "g", "δ": "d", "ε": "e", "ζ": "z", "η": "i",
+    "θ": "th", "ι": "i", "κ": "k", "λ": "l", "μ": "m", "ν": "n", "ξ": "x",
+    "ο": "o", "π": "p", "ρ": "r", "σ": "s", "ς": "s", "τ": "t", "υ": "y",
+    "φ": "f", "χ": "ch", "ψ": "ps", "ω": "o",
+}
+
+RANGES = [(0x0370, 0x03FF), (0x0400, 0x052F), (0x1F00, 0x1FFF)]
+
+lower = {**CYRILLIC, **GREEK}
+explicit = dict(lower)
+for c, latin in lower.items():
+    upper = c.upper()
+    if len(upper) == 1 and upper != c:
+        explicit.setdefault(upper, latin[:1].upper() + latin[1:])
+
+table = {}
+for start, end in RANGES:
+    for code in range(start, end + 1):
+        c = chr(code)
+        if c in explicit:
+            table[c] = explicit[c]
+            continue
+        decomposed = unicodedata.normalize("NFD", c)
+        base, marks = decomposed[0], decomposed[1:]
+        if base in explicit and all(unicodedata.combining(m) for m in marks):
+            table[c] = explicit[base]
+
+print("// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:")
+print("//")
+print("//   generate_transliteration_table.py")
+print("")
+print("pub const TRANSLITERATION: &'static [(char, &'static str)] = &[")
+for c, latin in sorted(table.items()):
+    print("  ('%s', \"%s\")," % (c, latin))
+print("];")
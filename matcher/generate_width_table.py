@@ -0,0 +1,64 @@
+#!/usr/bin/env python3
+"""Generates the table that normalizes the Halfwidth and Fullwidth Forms block
+from the `<wide>` and `<narrow>` decompositions in UnicodeData.txt.
+
+The output replaces `HALFWIDTH_AND_FULLWIDTH_FORMS` in
+`src/chars/normalize.rs`. Chars without such a decomposition (including
+unassigned chars) map to themselves.
+"""
+
+import sys
+import unicodedata
+
+START = 0xFF00
+END = 0xFFEF
+
+# chars that can't be printed as is (the hangul fillers are invisible)
+INVISIBLE = {0x3164, 0xFFA0}
+
+names = {}
+decompositions = {}
+with open(sys.argv[1]) as f:
+    for line in f:
+        fields = line.split(";")
+        code = int(fields[0], 16)
+        names[code] = fields[1]
+        decomposition = fields[5].split()
+        if len(decomposition) == 2 and decomposition[0] in ("<wide>", "<narrow>"):
+            decompositions[code] = int(decomposition[1], 16)
+
+
+def escape(c):
+    if c in INVISIBLE or unicodedata.category(chr(c)) in ("Mn", "Cn"):
+        return "'\\u{%x}'" % c
+    if chr(c) in "'\\":
+        return "'\\%s'" % chr(c)
+    return "'%s'" % chr(c)
+
+
+def width(text):
+    return sum(2 if unicodedata.east_asian_width(c) in "WF" else 1 for c in text)
+
+
+print("/// A char array corresponding to the following contiguous Unicode block:")
+print("///")
+print(
+    "/// - [Halfwidth and Fullwidth Forms]"
+    "(https://en.wikipedia.org/wiki/Halfwidth_and_Fullwidth_Forms_(Unicode_block))"
+)
+print("///")
+print("/// This covers the range `'\\u{ff00}'..='\\u{ffef}'`. The entries were generated")
+print("/// from the `<wide>` and `<narrow>` decompositions in `UnicodeData.txt` by")
+print("/// `generate_width_table.py`.")
+print('#[cfg(feature = "unicode-width-normalization")]')
+print("static HALFWIDTH_AND_FULLWIDTH_FORMS: [char; %d] = [" % (END - START + 1))
+for code in range(START, END + 1):
+    entry = escape(decompositions.get(code, code)) + ","
+    if code not in names:
+        comment = "unassigned '\\u{%x}'" % code
+    elif code in INVISIBLE:
+        comment = "invisible " + names[code]
+    else:
+        comment = "'%s'; '\\u{%x}'" % (chr(code), code)
+    print("    %s%s // %s" % (entry, " " * (11 - width(entry)), comment))
+print("];")
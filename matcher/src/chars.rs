@@ -4,6 +4,10 @@ use std::fmt::{self, Debug, Display};
 
 #[cfg(feature = "unicode-casefold")]
 use crate::chars::case_fold::CASE_FOLDING_SIMPLE;
+#[cfg(feature = "unicode-casefold")]
+use crate::chars::case_fold_full::CASE_FOLDING_FULL;
+#[cfg(feature = "unicode-transliteration")]
+use crate::chars::transliterate::TRANSLITERATION;
 use crate::Config;
 
 //autogenerated by generate-ucd
@@ -11,8 +15,18 @@ use crate::Config;
 #[rustfmt::skip]
 #[cfg(feature = "unicode-casefold")]
 mod case_fold;
+//autogenerated by generate_case_fold_full_table.py
+#[allow(warnings)]
+#[rustfmt::skip]
+#[cfg(feature = "unicode-casefold")]
+mod case_fold_full;
 #[cfg(feature = "unicode-normalization")]
 mod normalize;
+//autogenerated by generate_transliteration_table.py
+#[allow(warnings)]
+#[rustfmt::skip]
+#[cfg(feature = "unicode-transliteration")]
+mod transliterate;
 
 pub(crate) trait Char: Copy + Eq + Ord + fmt::Display {
     const ASCII: bool;
@@ -44,6 +58,12 @@ impl PartialEq<AsciiChar> for char {
     }
 }
 
+impl PartialEq<char> for AsciiChar {
+    fn eq(&self, other: &char) -> bool {
+        self.0 as char == *other
+    }
+}
+
 impl Char for AsciiChar {
     const ASCII: bool = true;
     #[inline]
@@ -58,7 +78,7 @@ impl Char for AsciiChar {
             CharClass::Number
         } else if c.is_ascii_whitespace() {
             CharClass::Whitespace
-        } else if config.delimiter_chars.contains(&c) {
+        } else if config.delimiters.contains_ascii(c) {
             CharClass::Delimiter
         } else {
             CharClass::NonWord
@@ -82,7 +102,7 @@ impl Char for AsciiChar {
         self
     }
 }
-fn char_class_non_ascii(c: char) -> CharClass {
+fn char_class_non_ascii(c: char, config: &Config) -> CharClass {
     if c.is_lowercase() {
         CharClass::Lower
     } else if is_upper_case(c) {
@@ -93,6 +113,8 @@ fn char_class_non_ascii(c: char) -> CharClass {
         CharClass::Letter
     } else if c.is_whitespace() {
         CharClass::Whitespace
+    } else if config.delimiters.contains_non_ascii(c) {
+        CharClass::Delimiter
     } else {
         CharClass::NonWord
     }
@@ -104,7 +126,7 @@ impl Char for char {
         if self.is_ascii() {
             return AsciiChar(self as u8).char_class(config);
         }
-        char_class_non_ascii(self)
+        char_class_non_ascii(self, config)
     }
 
     #[inline(always)]
@@ -113,9 +135,17 @@ impl Char for char {
             let (c, class) = AsciiChar(self as u8).char_class_and_normalize(config);
             return (c.0 as char, class);
         }
-        let char_class = char_class_non_ascii(self);
+        let char_class = char_class_non_ascii(self, config);
         #[cfg(feature = "unicode-casefold")]
         let mut case_fold = char_class == CharClass::Upper;
+        #[cfg(feature = "unicode-width-normalization")]
+        if config.normalize_width {
+            self = normalize::normalize_width(self);
+        }
+        #[cfg(feature = "unicode-kana-normalization")]
+        if config.normalize_kana {
+            self = normalize::normalize_kana(self);
+        }
         #[cfg(feature = "unicode-normalization")]
         if config.normalize {
             self = normalize::normalize(self);
@@ -132,6 +162,14 @@ impl Char for char {
 
     #[inline(always)]
     fn normalize(mut self, config: &Config) -> Self {
+        #[cfg(feature = "unicode-width-normalization")]
+        if config.normalize_width {
+            self = normalize::normalize_width(self);
+        }
+        #[cfg(feature = "unicode-kana-normalization")]
+        if config.normalize_kana {
+            self = normalize::normalize_kana(self);
+        }
         #[cfg(feature = "unicode-normalization")]
         if config.normalize {
             self = normalize::normalize(self);
@@ -146,6 +184,10 @@ impl Char for char {
 
 #[cfg(feature = "unicode-normalization")]
 pub use normalize::normalize;
+#[cfg(feature = "unicode-kana-normalization")]
+pub use normalize::normalize_kana;
+#[cfg(feature = "unicode-width-normalization")]
+pub use normalize::normalize_width;
 #[cfg(feature = "unicode-segmentation")]
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -158,6 +200,95 @@ pub fn to_lower_case(c: char) -> char {
         .map_or(c, |idx| CASE_FOLDING_SIMPLE[idx].1)
 }
 
+/// Returns the full unicode case folding of a character if it folds to
+/// multiple characters (`ß` to `ss`) or differs from its
+/// [simple case folding](to_lower_case) (`İ` to `i`). Combining marks are
+/// removed from the folded text and the turkish dotless `ı` is folded to `i`.
+#[cfg(feature = "unicode-casefold")]
+#[inline]
+pub fn case_fold_full(c: char) -> Option<&'static str> {
+    if c == 'ı' {
+        return Some("i");
+    }
+    CASE_FOLDING_FULL
+        .binary_search_by_key(&c, |(c, _)| *c)
+        .ok()
+        .map(|idx| CASE_FOLDING_FULL[idx].1)
+}
+
+/// Transliterates a Cyrillic or Greek character to Latin (`ж` to `zh`,
+/// `Ж` to `Zh`, `ά` to `a`). Returns `None` for all other characters. The
+/// hard and soft signs `ъ` and `ь` are transliterated to the empty string.
+#[cfg(feature = "unicode-transliteration")]
+#[inline]
+pub fn transliterate(c: char) -> Option<&'static str> {
+    TRANSLITERATION
+        .binary_search_by_key(&c, |(c, _)| *c)
+        .ok()
+        .map(|idx| TRANSLITERATION[idx].1)
+}
+
+/// A haystack where chars were replaced with multiple chars (their full
+/// case folding (`case_fold_full`) or transliteration (`transliterate`)).
+/// Remembers which char of the original haystack each folded char originates
+/// from so that match indices can be mapped back.
+#[cfg(any(feature = "unicode-casefold", feature = "unicode-transliteration"))]
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FoldedHaystack {
+    pub(crate) chars: Vec<char>,
+    origins: Vec<u32>,
+}
+
+#[cfg(any(feature = "unicode-casefold", feature = "unicode-transliteration"))]
+impl FoldedHaystack {
+    /// Replaces the chars of `haystack` with the text returned by `fold`,
+    /// returns `false` (without folding) if `fold` returns `None` for all
+    /// chars of `haystack`.
+    pub(crate) fn fold(
+        &mut self,
+        haystack: &[char],
+        fold: impl Fn(char) -> Option<&'static str>,
+    ) -> bool {
+        let Some(first) = haystack.iter().position(|&c| fold(c).is_some()) else {
+            return false;
+        };
+        self.chars.clear();
+        self.origins.clear();
+        self.chars.extend_from_slice(&haystack[..first]);
+        self.origins.extend(0..first as u32);
+        for (i, &c) in haystack.iter().enumerate().skip(first) {
+            match fold(c) {
+                Some(folded) => {
+                    for c in folded.chars() {
+                        self.chars.push(c);
+                        self.origins.push(i as u32);
+                    }
+                }
+                None => {
+                    self.chars.push(c);
+                    self.origins.push(i as u32);
+                }
+            }
+        }
+        true
+    }
+
+    /// Maps the indices (of the folded chars) that were pushed to `indices`
+    /// after `start` back to indices of the original haystack. Chars that
+    /// were folded to multiple chars are only reported once.
+    pub(crate) fn map_indices(&self, indices: &mut Vec<u32>, start: usize) {
+        let mut len = start;
+        for i in start..indices.len() {
+            let idx = self.origins[indices[i] as usize];
+            if len == start || indices[len - 1] != idx {
+                indices[len] = idx;
+                len += 1;
+            }
+        }
+        indices.truncate(len);
+    }
+}
+
 /// Checks if a character is upper case according to simple unicode case folding.
 /// if the `unicode-casefold` feature is disable the equivalent std function is used
 #[inline(always)]
@@ -205,3 +336,11 @@ pub fn graphemes(text: &str) -> impl Iterator<Item = char> + '_ {
     let res = text.chars();
     res
 }
+
+/// Returns the unicode graphemes of a string. Like for [`graphemes`] `\r\n`
+/// is represented by `\n`.
+#[cfg(feature = "unicode-segmentation")]
+pub(crate) fn grapheme_clusters(text: &str) -> impl Iterator<Item = &str> + '_ {
+    text.graphemes(true)
+        .map(|grapheme| if grapheme == "\r\n" { "\n" } else { grapheme })
+}
@@ -0,0 +1,110 @@
+// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
+//
+//   generate_case_fold_full_table.py CaseFolding.txt
+
+pub const CASE_FOLDING_FULL: &'static [(char, &'static str)] = &[
+  ('ß', "ss"),
+  ('İ', "i"),
+  ('ŉ', "ʼn"),
+  ('ǰ', "j"),
+  ('ΐ', "ι"),
+  ('ΰ', "υ"),
+  ('և', "եւ"),
+  ('ẖ', "h"),
+  ('ẗ', "t"),
+  ('ẘ', "w"),
+  ('ẙ', "y"),
+  ('ẚ', "aʾ"),
+  ('ẞ', "ss"),
+  ('ὐ', "υ"),
+  ('ὒ', "υ"),
+  ('ὔ', "υ"),
+  ('ὖ', "υ"),
+  ('ᾀ', "ἀι"),
+  ('ᾁ', "ἁι"),
+  ('ᾂ', "ἂι"),
+  ('ᾃ', "ἃι"),
+  ('ᾄ', "ἄι"),
+  ('ᾅ', "ἅι"),
+  ('ᾆ', "ἆι"),
+  ('ᾇ', "ἇι"),
+  ('ᾈ', "ἀι"),
+  ('ᾉ', "ἁι"),
+  ('ᾊ', "ἂι"),
+  ('ᾋ', "ἃι"),
+  ('ᾌ', "ἄι"),
+  ('ᾍ', "ἅι"),
+  ('ᾎ', "ἆι"),
+  ('ᾏ', "ἇι"),
+  ('ᾐ', "ἠι"),
+  ('ᾑ', "ἡι"),
+  ('ᾒ', "ἢι"),
+  ('ᾓ', "ἣι"),
+  ('ᾔ', "ἤι"),
+  ('ᾕ', "ἥι"),
+  ('ᾖ', "ἦι"),
+  ('ᾗ', "ἧι"),
+  ('ᾘ', "ἠι"),
+  ('ᾙ', "ἡι"),
+  ('ᾚ', "ἢι"),
+  ('ᾛ', "ἣι"),
+  ('ᾜ', "ἤι"),
+  ('ᾝ', "ἥι"),
+  ('ᾞ', "ἦι"),
+  ('ᾟ', "ἧι"),
+  ('ᾠ', "ὠι"),
+  ('ᾡ', "ὡι"),
+  ('ᾢ', "ὢι"),
+  ('ᾣ', "ὣι"),
+  ('ᾤ', "ὤι"),
+  ('ᾥ', "ὥι"),
+  ('ᾦ', "ὦι"),
+  ('ᾧ', "ὧι"),
+  ('ᾨ', "ὠι"),
+  ('ᾩ', "ὡι"),
+  ('ᾪ', "ὢι"),
+  ('ᾫ', "ὣι"),
+  ('ᾬ', "ὤι"),
+  ('ᾭ', "ὥι"),
+  ('ᾮ', "ὦι"),
+  ('ᾯ', "ὧι"),
+  ('ᾲ', "ὰι"),
+  ('ᾳ', "αι"),
+  ('ᾴ', "άι"),
+  ('ᾶ', "α"),
+  ('ᾷ', "αι"),
+  ('ᾼ', "αι"),
+  ('ῂ', "ὴι"),
+  ('ῃ', "ηι"),
+  ('ῄ', "ήι"),
+  ('ῆ', "η"),
+  ('ῇ', "ηι"),
+  ('ῌ', "ηι"),
+  ('ῒ', "ι"),
+  ('ΐ', "ι"),
+  ('ῖ', "ι"),
+  ('ῗ', "ι"),
+  ('ῢ', "υ"),
+  ('ΰ', "υ"),
+  ('ῤ', "ρ"),
+  ('ῦ', "υ"),
+  ('ῧ', "υ"),
+  ('ῲ', "ὼι"),
+  ('ῳ', "ωι"),
+  ('ῴ', "ώι"),
+  ('ῶ', "ω"),
+  ('ῷ', "ωι"),
+  ('ῼ', "ωι"),
+  ('ﬀ', "ff"),
+  ('ﬁ', "fi"),
+  ('ﬂ', "fl"),
+  ('ﬃ', "ffi"),
+  ('ﬄ', "ffl"),
+  ('ﬅ', "st"),
+  ('ﬆ', "st"),
+  ('ﬓ', "մն"),
+  ('ﬔ', "մե"),
+  ('ﬕ', "մի"),
+  ('ﬖ', "վն"),
+  ('ﬗ', "մխ"),
+];
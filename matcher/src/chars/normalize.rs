@@ -891,6 +891,305 @@ static SUPERSCRIPTS_AND_SUBSCRIPTS: [char; 48] = [
     '₟', // '₟'; '\u{209f}'
 ];
 
+/// Normalize a full-width or half-width character to its regular width form
+/// (`Ａ` to `A`, `ｶ` to `カ`, `　` to ` `).
+///
+/// This covers the [Halfwidth and Fullwidth Forms](https://en.wikipedia.org/wiki/Halfwidth_and_Fullwidth_Forms_(Unicode_block))
+/// block and the ideographic space. Half-width voiced sound marks (`ﾞ`) are
+/// separate graphemes so `ｶﾞ` is normalized to `カ` (and not to `ガ`) when
+/// only the first char of each grapheme is matched.
+///
+/// # Example
+/// ```
+/// # use nucleo_matcher::chars::normalize_width;
+/// assert_eq!(normalize_width('Ａ'), 'A');
+/// assert_eq!(normalize_width('ｶ'), 'カ');
+/// assert_eq!(normalize_width('カ'), 'カ');
+/// ```
+#[cfg(feature = "unicode-width-normalization")]
+pub fn normalize_width(c: char) -> char {
+    if c == '\u{3000}' {
+        return ' ';
+    }
+    if c < '\u{ff00}' || c > '\u{ffef}' {
+        return c;
+    }
+    HALFWIDTH_AND_FULLWIDTH_FORMS[c as usize - '\u{ff00}' as usize]
+}
+
+/// Normalize a katakana character to the equivalent hiragana character
+/// (`カ` to `か`). Katakana without a hiragana equivalent (like `ヷ`) and all
+/// other characters are not normalized.
+///
+/// # Example
+/// ```
+/// # use nucleo_matcher::chars::normalize_kana;
+/// assert_eq!(normalize_kana('カ'), 'か');
+/// assert_eq!(normalize_kana('か'), 'か');
+/// assert_eq!(normalize_kana('ヷ'), 'ヷ');
+/// ```
+#[cfg(feature = "unicode-kana-normalization")]
+pub fn normalize_kana(c: char) -> char {
+    // the katakana block mirrors the hiragana block with an offset of 0x60
+    match c {
+        '\u{30a1}'..='\u{30f6}' | '\u{30fd}'..='\u{30fe}' => {
+            char::from_u32(c as u32 - 0x60).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// A char array corresponding to the following contiguous Unicode block:
+///
+/// - [Halfwidth and Fullwidth Forms](https://en.wikipedia.org/wiki/Halfwidth_and_Fullwidth_Forms_(Unicode_block))
+///
+/// This covers the range `'\u{ff00}'..='\u{ffef}'`. The entries were generated
+/// from the `<wide>` and `<narrow>` decompositions in `UnicodeData.txt` by
+/// `generate_width_table.py`.
+#[cfg(feature = "unicode-width-normalization")]
+static HALFWIDTH_AND_FULLWIDTH_FORMS: [char; 240] = [
+    '\u{ff00}', // unassigned '\u{ff00}'
+    '!',        // '！'; '\u{ff01}'
+    '"',        // '＂'; '\u{ff02}'
+    '#',        // '＃'; '\u{ff03}'
+    '$',        // '＄'; '\u{ff04}'
+    '%',        // '％'; '\u{ff05}'
+    '&',        // '＆'; '\u{ff06}'
+    '\'',       // '＇'; '\u{ff07}'
+    '(',        // '（'; '\u{ff08}'
+    ')',        // '）'; '\u{ff09}'
+    '*',        // '＊'; '\u{ff0a}'
+    '+',        // '＋'; '\u{ff0b}'
+    ',',        // '，'; '\u{ff0c}'
+    '-',        // '－'; '\u{ff0d}'
+    '.',        // '．'; '\u{ff0e}'
+    '/',        // '／'; '\u{ff0f}'
+    '0',        // '０'; '\u{ff10}'
+    '1',        // '１'; '\u{ff11}'
+    '2',        // '２'; '\u{ff12}'
+    '3',        // '３'; '\u{ff13}'
+    '4',        // '４'; '\u{ff14}'
+    '5',        // '５'; '\u{ff15}'
+    '6',        // '６'; '\u{ff16}'
+    '7',        // '７'; '\u{ff17}'
+    '8',        // '８'; '\u{ff18}'
+    '9',        // '９'; '\u{ff19}'
+    ':',        // '：'; '\u{ff1a}'
+    ';',        // '；'; '\u{ff1b}'
+    '<',        // '＜'; '\u{ff1c}'
+    '=',        // '＝'; '\u{ff1d}'
+    '>',        // '＞'; '\u{ff1e}'
+    '?',        // '？'; '\u{ff1f}'
+    '@',        // '＠'; '\u{ff20}'
+    'A',        // 'Ａ'; '\u{ff21}'
+    'B',        // 'Ｂ'; '\u{ff22}'
+    'C',        // 'Ｃ'; '\u{ff23}'
+    'D',        // 'Ｄ'; '\u{ff24}'
+    'E',        // 'Ｅ'; '\u{ff25}'
+    'F',        // 'Ｆ'; '\u{ff26}'
+    'G',        // 'Ｇ'; '\u{ff27}'
+    'H',        // 'Ｈ'; '\u{ff28}'
+    'I',        // 'Ｉ'; '\u{ff29}'
+    'J',        // 'Ｊ'; '\u{ff2a}'
+    'K',        // 'Ｋ'; '\u{ff2b}'
+    'L',        // 'Ｌ'; '\u{ff2c}'
+    'M',        // 'Ｍ'; '\u{ff2d}'
+    'N',        // 'Ｎ'; '\u{ff2e}'
+    'O',        // 'Ｏ'; '\u{ff2f}'
+    'P',        // 'Ｐ'; '\u{ff30}'
+    'Q',        // 'Ｑ'; '\u{ff31}'
+    'R',        // 'Ｒ'; '\u{ff32}'
+    'S',        // 'Ｓ'; '\u{ff33}'
+    'T',        // 'Ｔ'; '\u{ff34}'
+    'U',        // 'Ｕ'; '\u{ff35}'
+    'V',        // 'Ｖ'; '\u{ff36}'
+    'W',        // 'Ｗ'; '\u{ff37}'
+    'X',        // 'Ｘ'; '\u{ff38}'
+    'Y',        // 'Ｙ'; '\u{ff39}'
+    'Z',        // 'Ｚ'; '\u{ff3a}'
+    '[',        // '［'; '\u{ff3b}'
+    '\\',       // '＼'; '\u{ff3c}'
+    ']',        // '］'; '\u{ff3d}'
+    '^',        // '＾'; '\u{ff3e}'
+    '_',        // '＿'; '\u{ff3f}'
+    '`',        // '｀'; '\u{ff40}'
+    'a',        // 'ａ'; '\u{ff41}'
+    'b',        // 'ｂ'; '\u{ff42}'
+    'c',        // 'ｃ'; '\u{ff43}'
+    'd',        // 'ｄ'; '\u{ff44}'
+    'e',        // 'ｅ'; '\u{ff45}'
+    'f',        // 'ｆ'; '\u{ff46}'
+    'g',        // 'ｇ'; '\u{ff47}'
+    'h',        // 'ｈ'; '\u{ff48}'
+    'i',        // 'ｉ'; '\u{ff49}'
+    'j',        // 'ｊ'; '\u{ff4a}'
+    'k',        // 'ｋ'; '\u{ff4b}'
+    'l',        // 'ｌ'; '\u{ff4c}'
+    'm',        // 'ｍ'; '\u{ff4d}'
+    'n',        // 'ｎ'; '\u{ff4e}'
+    'o',        // 'ｏ'; '\u{ff4f}'
+    'p',        // 'ｐ'; '\u{ff50}'
+    'q',        // 'ｑ'; '\u{ff51}'
+    'r',        // 'ｒ'; '\u{ff52}'
+    's',        // 'ｓ'; '\u{ff53}'
+    't',        // 'ｔ'; '\u{ff54}'
+    'u',        // 'ｕ'; '\u{ff55}'
+    'v',        // 'ｖ'; '\u{ff56}'
+    'w',        // 'ｗ'; '\u{ff57}'
+    'x',        // 'ｘ'; '\u{ff58}'
+    'y',        // 'ｙ'; '\u{ff59}'
+    'z',        // 'ｚ'; '\u{ff5a}'
+    '{',        // '｛'; '\u{ff5b}'
+    '|',        // '｜'; '\u{ff5c}'
+    '}',        // '｝'; '\u{ff5d}'
+    '~',        // '～'; '\u{ff5e}'
+    '⦅',        // '｟'; '\u{ff5f}'
+    '⦆',        // '｠'; '\u{ff60}'
+    '。',       // '｡'; '\u{ff61}'
+    '「',       // '｢'; '\u{ff62}'
+    '」',       // '｣'; '\u{ff63}'
+    '、',       // '､'; '\u{ff64}'
+    '・',       // '･'; '\u{ff65}'
+    'ヲ',       // 'ｦ'; '\u{ff66}'
+    'ァ',       // 'ｧ'; '\u{ff67}'
+    'ィ',       // 'ｨ'; '\u{ff68}'
+    'ゥ',       // 'ｩ'; '\u{ff69}'
+    'ェ',       // 'ｪ'; '\u{ff6a}'
+    'ォ',       // 'ｫ'; '\u{ff6b}'
+    'ャ',       // 'ｬ'; '\u{ff6c}'
+    'ュ',       // 'ｭ'; '\u{ff6d}'
+    'ョ',       // 'ｮ'; '\u{ff6e}'
+    'ッ',       // 'ｯ'; '\u{ff6f}'
+    'ー',       // 'ｰ'; '\u{ff70}'
+    'ア',       // 'ｱ'; '\u{ff71}'
+    'イ',       // 'ｲ'; '\u{ff72}'
+    'ウ',       // 'ｳ'; '\u{ff73}'
+    'エ',       // 'ｴ'; '\u{ff74}'
+    'オ',       // 'ｵ'; '\u{ff75}'
+    'カ',       // 'ｶ'; '\u{ff76}'
+    'キ',       // 'ｷ'; '\u{ff77}'
+    'ク',       // 'ｸ'; '\u{ff78}'
+    'ケ',       // 'ｹ'; '\u{ff79}'
+    'コ',       // 'ｺ'; '\u{ff7a}'
+    'サ',       // 'ｻ'; '\u{ff7b}'
+    'シ',       // 'ｼ'; '\u{ff7c}'
+    'ス',       // 'ｽ'; '\u{ff7d}'
+    'セ',       // 'ｾ'; '\u{ff7e}'
+    'ソ',       // 'ｿ'; '\u{ff7f}'
+    'タ',       // 'ﾀ'; '\u{ff80}'
+    'チ',       // 'ﾁ'; '\u{ff81}'
+    'ツ',       // 'ﾂ'; '\u{ff82}'
+    'テ',       // 'ﾃ'; '\u{ff83}'
+    'ト',       // 'ﾄ'; '\u{ff84}'
+    'ナ',       // 'ﾅ'; '\u{ff85}'
+    'ニ',       // 'ﾆ'; '\u{ff86}'
+    'ヌ',       // 'ﾇ'; '\u{ff87}'
+    'ネ',       // 'ﾈ'; '\u{ff88}'
+    'ノ',       // 'ﾉ'; '\u{ff89}'
+    'ハ',       // 'ﾊ'; '\u{ff8a}'
+    'ヒ',       // 'ﾋ'; '\u{ff8b}'
+    'フ',       // 'ﾌ'; '\u{ff8c}'
+    'ヘ',       // 'ﾍ'; '\u{ff8d}'
+    'ホ',       // 'ﾎ'; '\u{ff8e}'
+    'マ',       // 'ﾏ'; '\u{ff8f}'
+    'ミ',       // 'ﾐ'; '\u{ff90}'
+    'ム',       // 'ﾑ'; '\u{ff91}'
+    'メ',       // 'ﾒ'; '\u{ff92}'
+    'モ',       // 'ﾓ'; '\u{ff93}'
+    'ヤ',       // 'ﾔ'; '\u{ff94}'
+    'ユ',       // 'ﾕ'; '\u{ff95}'
+    'ヨ',       // 'ﾖ'; '\u{ff96}'
+    'ラ',       // 'ﾗ'; '\u{ff97}'
+    'リ',       // 'ﾘ'; '\u{ff98}'
+    'ル',       // 'ﾙ'; '\u{ff99}'
+    'レ',       // 'ﾚ'; '\u{ff9a}'
+    'ロ',       // 'ﾛ'; '\u{ff9b}'
+    'ワ',       // 'ﾜ'; '\u{ff9c}'
+    'ン',       // 'ﾝ'; '\u{ff9d}'
+    '\u{3099}', // 'ﾞ'; '\u{ff9e}'
+    '\u{309a}', // 'ﾟ'; '\u{ff9f}'
+    '\u{3164}', // invisible HALFWIDTH HANGUL FILLER
+    'ㄱ',       // 'ﾡ'; '\u{ffa1}'
+    'ㄲ',       // 'ﾢ'; '\u{ffa2}'
+    'ㄳ',       // 'ﾣ'; '\u{ffa3}'
+    'ㄴ',       // 'ﾤ'; '\u{ffa4}'
+    'ㄵ',       // 'ﾥ'; '\u{ffa5}'
+    'ㄶ',       // 'ﾦ'; '\u{ffa6}'
+    'ㄷ',       // 'ﾧ'; '\u{ffa7}'
+    'ㄸ',       // 'ﾨ'; '\u{ffa8}'
+    'ㄹ',       // 'ﾩ'; '\u{ffa9}'
+    'ㄺ',       // 'ﾪ'; '\u{ffaa}'
+    'ㄻ',       // 'ﾫ'; '\u{ffab}'
+    'ㄼ',       // 'ﾬ'; '\u{ffac}'
+    'ㄽ',       // 'ﾭ'; '\u{ffad}'
+    'ㄾ',       // 'ﾮ'; '\u{ffae}'
+    'ㄿ',       // 'ﾯ'; '\u{ffaf}'
+    'ㅀ',       // 'ﾰ'; '\u{ffb0}'
+    'ㅁ',       // 'ﾱ'; '\u{ffb1}'
+    'ㅂ',       // 'ﾲ'; '\u{ffb2}'
+    'ㅃ',       // 'ﾳ'; '\u{ffb3}'
+    'ㅄ',       // 'ﾴ'; '\u{ffb4}'
+    'ㅅ',       // 'ﾵ'; '\u{ffb5}'
+    'ㅆ',       // 'ﾶ'; '\u{ffb6}'
+    'ㅇ',       // 'ﾷ'; '\u{ffb7}'
+    'ㅈ',       // 'ﾸ'; '\u{ffb8}'
+    'ㅉ',       // 'ﾹ'; '\u{ffb9}'
+    'ㅊ',       // 'ﾺ'; '\u{ffba}'
+    'ㅋ',       // 'ﾻ'; '\u{ffbb}'
+    'ㅌ',       // 'ﾼ'; '\u{ffbc}'
+    'ㅍ',       // 'ﾽ'; '\u{ffbd}'
+    'ㅎ',       // 'ﾾ'; '\u{ffbe}'
+    '\u{ffbf}', // unassigned '\u{ffbf}'
+    '\u{ffc0}', // unassigned '\u{ffc0}'
+    '\u{ffc1}', // unassigned '\u{ffc1}'
+    'ㅏ',       // 'ￂ'; '\u{ffc2}'
+    'ㅐ',       // 'ￃ'; '\u{ffc3}'
+    'ㅑ',       // 'ￄ'; '\u{ffc4}'
+    'ㅒ',       // 'ￅ'; '\u{ffc5}'
+    'ㅓ',       // 'ￆ'; '\u{ffc6}'
+    'ㅔ',       // 'ￇ'; '\u{ffc7}'
+    '\u{ffc8}', // unassigned '\u{ffc8}'
+    '\u{ffc9}', // unassigned '\u{ffc9}'
+    'ㅕ',       // 'ￊ'; '\u{ffca}'
+    'ㅖ',       // 'ￋ'; '\u{ffcb}'
+    'ㅗ',       // 'ￌ'; '\u{ffcc}'
+    'ㅘ',       // 'ￍ'; '\u{ffcd}'
+    'ㅙ',       // 'ￎ'; '\u{ffce}'
+    'ㅚ',       // 'ￏ'; '\u{ffcf}'
+    '\u{ffd0}', // unassigned '\u{ffd0}'
+    '\u{ffd1}', // unassigned '\u{ffd1}'
+    'ㅛ',       // 'ￒ'; '\u{ffd2}'
+    'ㅜ',       // 'ￓ'; '\u{ffd3}'
+    'ㅝ',       // 'ￔ'; '\u{ffd4}'
+    'ㅞ',       // 'ￕ'; '\u{ffd5}'
+    'ㅟ',       // 'ￖ'; '\u{ffd6}'
+    'ㅠ',       // 'ￗ'; '\u{ffd7}'
+    '\u{ffd8}', // unassigned '\u{ffd8}'
+    '\u{ffd9}', // unassigned '\u{ffd9}'
+    'ㅡ',       // 'ￚ'; '\u{ffda}'
+    'ㅢ',       // 'ￛ'; '\u{ffdb}'
+    'ㅣ',       // 'ￜ'; '\u{ffdc}'
+    '\u{ffdd}', // unassigned '\u{ffdd}'
+    '\u{ffde}', // unassigned '\u{ffde}'
+    '\u{ffdf}', // unassigned '\u{ffdf}'
+    '¢',        // '￠'; '\u{ffe0}'
+    '£',        // '￡'; '\u{ffe1}'
+    '¬',        // '￢'; '\u{ffe2}'
+    '¯',        // '￣'; '\u{ffe3}'
+    '¦',        // '￤'; '\u{ffe4}'
+    '¥',        // '￥'; '\u{ffe5}'
+    '₩',        // '￦'; '\u{ffe6}'
+    '\u{ffe7}', // unassigned '\u{ffe7}'
+    '│',        // '￨'; '\u{ffe8}'
+    '←',        // '￩'; '\u{ffe9}'
+    '↑',        // '￪'; '\u{ffea}'
+    '→',        // '￫'; '\u{ffeb}'
+    '↓',        // '￬'; '\u{ffec}'
+    '■',        // '￭'; '\u{ffed}'
+    '○',        // '￮'; '\u{ffee}'
+    '\u{ffef}', // unassigned '\u{ffef}'
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -957,6 +1256,49 @@ mod tests {
         ]);
     }
 
+    #[cfg(feature = "unicode-width-normalization")]
+    #[test]
+    fn width() {
+        for (original, normalized) in [
+            ('Ａ', 'A'),
+            ('ｚ', 'z'),
+            ('０', '0'),
+            ('！', '!'),
+            ('～', '~'),
+            ('\u{3000}', ' '),
+            ('ｦ', 'ヲ'),
+            ('ﾝ', 'ン'),
+            ('｡', '。'),
+            ('ﾡ', 'ㄱ'),
+            ('￥', '¥'),
+            ('ｶ', 'カ'),
+            ('カ', 'カ'),
+            ('\u{ff00}', '\u{ff00}'),
+            ('\u{fff0}', '\u{fff0}'),
+            ('a', 'a'),
+        ] {
+            assert_eq!(normalize_width(original), normalized);
+        }
+    }
+
+    #[cfg(feature = "unicode-kana-normalization")]
+    #[test]
+    fn kana() {
+        for (original, normalized) in [
+            ('ァ', 'ぁ'),
+            ('ヶ', 'ゖ'),
+            ('ヽ', 'ゝ'),
+            ('ヾ', 'ゞ'),
+            ('ヷ', 'ヷ'),
+            ('ー', 'ー'),
+            ('゠', '゠'),
+            ('か', 'か'),
+            ('A', 'A'),
+        ] {
+            assert_eq!(normalize_kana(original), normalized);
+        }
+    }
+
     /// Check that conversions outside the blocks are unchanged.
     #[test]
     fn unchanged_outside_blocks() {
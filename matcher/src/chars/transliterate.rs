@@ -0,0 +1,421 @@
+// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
+//
+//   generate_transliteration_table.py
+
+pub const TRANSLITERATION: &'static [(char, &'static str)] = &[
+  ('Ά', "A"),
+  ('Έ', "E"),
+  ('Ή', "I"),
+  ('Ί', "I"),
+  ('Ό', "O"),
+  ('Ύ', "Y"),
+  ('Ώ', "O"),
+  ('ΐ', "i"),
+  ('Α', "A"),
+  ('Β', "V"),
+  ('Γ', "G"),
+  ('Δ', "D"),
+  ('Ε', "E"),
+  ('Ζ', "Z"),
+  ('Η', "I"),
+  ('Θ', "Th"),
+  ('Ι', "I"),
+  ('Κ', "K"),
+  ('Λ', "L"),
+  ('Μ', "M"),
+  ('Ν', "N"),
+  ('Ξ', "X"),
+  ('Ο', "O"),
+  ('Π', "P"),
+  ('Ρ', "R"),
+  ('Σ', "S"),
+  ('Τ', "T"),
+  ('Υ', "Y"),
+  ('Φ', "F"),
+  ('Χ', "Ch"),
+  ('Ψ', "Ps"),
+  ('Ω', "O"),
+  ('Ϊ', "I"),
+  ('Ϋ', "Y"),
+  ('ά', "a"),
+  ('έ', "e"),
+  ('ή', "i"),
+  ('ί', "i"),
+  ('ΰ', "y"),
+  ('α', "a"),
+  ('β', "v"),
+  ('γ', "g"),
+  ('δ', "d"),
+  ('ε', "e"),
+  ('ζ', "z"),
+  ('η', "i"),
+  ('θ', "th"),
+  ('ι', "i"),
+  ('κ', "k"),
+  ('λ', "l"),
+  ('μ', "m"),
+  ('ν', "n"),
+  ('ξ', "x"),
+  ('ο', "o"),
+  ('π', "p"),
+  ('ρ', "r"),
+  ('ς', "s"),
+  ('σ', "s"),
+  ('τ', "t"),
+  ('υ', "y"),
+  ('φ', "f"),
+  ('χ', "ch"),
+  ('ψ', "ps"),
+  ('ω', "o"),
+  ('ϊ', "i"),
+  ('ϋ', "y"),
+  ('ό', "o"),
+  ('ύ', "y"),
+  ('ώ', "o"),
+  ('Ѐ', "E"),
+  ('Ё', "E"),
+  ('Ђ', "Dj"),
+  ('Ѓ', "Gj"),
+  ('Є', "Ye"),
+  ('Ѕ', "Dz"),
+  ('І', "I"),
+  ('Ї', "Yi"),
+  ('Ј', "J"),
+  ('Љ', "Lj"),
+  ('Њ', "Nj"),
+  ('Ћ', "C"),
+  ('Ќ', "Kj"),
+  ('Ѝ', "I"),
+  ('Ў', "U"),
+  ('Џ', "Dz"),
+  ('А', "A"),
+  ('Б', "B"),
+  ('В', "V"),
+  ('Г', "G"),
+  ('Д', "D"),
+  ('Е', "E"),
+  ('Ж', "Zh"),
+  ('З', "Z"),
+  ('И', "I"),
+  ('Й', "Y"),
+  ('К', "K"),
+  ('Л', "L"),
+  ('М', "M"),
+  ('Н', "N"),
+  ('О', "O"),
+  ('П', "P"),
+  ('Р', "R"),
+  ('С', "S"),
+  ('Т', "T"),
+  ('У', "U"),
+  ('Ф', "F"),
+  ('Х', "Kh"),
+  ('Ц', "Ts"),
+  ('Ч', "Ch"),
+  ('Ш', "Sh"),
+  ('Щ', "Shch"),
+  ('Ъ', ""),
+  ('Ы', "Y"),
+  ('Ь', ""),
+  ('Э', "E"),
+  ('Ю', "Yu"),
+  ('Я', "Ya"),
+  ('а', "a"),
+  ('б', "b"),
+  ('в', "v"),
+  ('г', "g"),
+  ('д', "d"),
+  ('е', "e"),
+  ('ж', "zh"),
+  ('з', "z"),
+  ('и', "i"),
+  ('й', "y"),
+  ('к', "k"),
+  ('л', "l"),
+  ('м', "m"),
+  ('н', "n"),
+  ('о', "o"),
+  ('п', "p"),
+  ('р', "r"),
+  ('с', "s"),
+  ('т', "t"),
+  ('у', "u"),
+  ('ф', "f"),
+  ('х', "kh"),
+  ('ц', "ts"),
+  ('ч', "ch"),
+  ('ш', "sh"),
+  ('щ', "shch"),
+  ('ъ', ""),
+  ('ы', "y"),
+  ('ь', ""),
+  ('э', "e"),
+  ('ю', "yu"),
+  ('я', "ya"),
+  ('ѐ', "e"),
+  ('ё', "e"),
+  ('ђ', "dj"),
+  ('ѓ', "gj"),
+  ('є', "ye"),
+  ('ѕ', "dz"),
+  ('і', "i"),
+  ('ї', "yi"),
+  ('ј', "j"),
+  ('љ', "lj"),
+  ('њ', "nj"),
+  ('ћ', "c"),
+  ('ќ', "kj"),
+  ('ѝ', "i"),
+  ('ў', "u"),
+  ('џ', "dz"),
+  ('Ґ', "G"),
+  ('ґ', "g"),
+  ('Ӂ', "Zh"),
+  ('ӂ', "zh"),
+  ('Ӑ', "A"),
+  ('ӑ', "a"),
+  ('Ӓ', "A"),
+  ('ӓ', "a"),
+  ('Ӗ', "E"),
+  ('ӗ', "e"),
+  ('Ӝ', "Zh"),
+  ('ӝ', "zh"),
+  ('Ӟ', "Z"),
+  ('ӟ', "z"),
+  ('Ӣ', "I"),
+  ('ӣ', "i"),
+  ('Ӥ', "I"),
+  ('ӥ', "i"),
+  ('Ӧ', "O"),
+  ('ӧ', "o"),
+  ('Ӭ', "E"),
+  ('ӭ', "e"),
+  ('Ӯ', "U"),
+  ('ӯ', "u"),
+  ('Ӱ', "U"),
+  ('ӱ', "u"),
+  ('Ӳ', "U"),
+  ('ӳ', "u"),
+  ('Ӵ', "Ch"),
+  ('ӵ', "ch"),
+  ('Ӹ', "Y"),
+  ('ӹ', "y"),
+  ('ἀ', "a"),
+  ('ἁ', "a"),
+  ('ἂ', "a"),
+  ('ἃ', "a"),
+  ('ἄ', "a"),
+  ('ἅ', "a"),
+  ('ἆ', "a"),
+  ('ἇ', "a"),
+  ('Ἀ', "A"),
+  ('Ἁ', "A"),
+  ('Ἂ', "A"),
+  ('Ἃ', "A"),
+  ('Ἄ', "A"),
+  ('Ἅ', "A"),
+  ('Ἆ', "A"),
+  ('Ἇ', "A"),
+  ('ἐ', "e"),
+  ('ἑ', "e"),
+  ('ἒ', "e"),
+  ('ἓ', "e"),
+  ('ἔ', "e"),
+  ('ἕ', "e"),
+  ('Ἐ', "E"),
+  ('Ἑ', "E"),
+  ('Ἒ', "E"),
+  ('Ἓ', "E"),
+  ('Ἔ', "E"),
+  ('Ἕ', "E"),
+  ('ἠ', "i"),
+  ('ἡ', "i"),
+  ('ἢ', "i"),
+  ('ἣ', "i"),
+  ('ἤ', "i"),
+  ('ἥ', "i"),
+  ('ἦ', "i"),
+  ('ἧ', "i"),
+  ('Ἠ', "I"),
+  ('Ἡ', "I"),
+  ('Ἢ', "I"),
+  ('Ἣ', "I"),
+  ('Ἤ', "I"),
+  ('Ἥ', "I"),
+  ('Ἦ', "I"),
+  ('Ἧ', "I"),
+  ('ἰ', "i"),
+  ('ἱ', "i"),
+  ('ἲ', "i"),
+  ('ἳ', "i"),
+  ('ἴ', "i"),
+  ('ἵ', "i"),
+  ('ἶ', "i"),
+  ('ἷ', "i"),
+  ('Ἰ', "I"),
+  ('Ἱ', "I"),
+  ('Ἲ', "I"),
+  ('Ἳ', "I"),
+  ('Ἴ', "I"),
+  ('Ἵ', "I"),
+  ('Ἶ', "I"),
+  ('Ἷ', "I"),
+  ('ὀ', "o"),
+  ('ὁ', "o"),
+  ('ὂ', "o"),
+  ('ὃ', "o"),
+  ('ὄ', "o"),
+  ('ὅ', "o"),
+  ('Ὀ', "O"),
+  ('Ὁ', "O"),
+  ('Ὂ', "O"),
+  ('Ὃ', "O"),
+  ('Ὄ', "O"),
+  ('Ὅ', "O"),
+  ('ὐ', "y"),
+  ('ὑ', "y"),
+  ('ὒ', "y"),
+  ('ὓ', "y"),
+  ('ὔ', "y"),
+  ('ὕ', "y"),
+  ('ὖ', "y"),
+  ('ὗ', "y"),
+  ('Ὑ', "Y"),
+  ('Ὓ', "Y"),
+  ('Ὕ', "Y"),
+  ('Ὗ', "Y"),
+  ('ὠ', "o"),
+  ('ὡ', "o"),
+  ('ὢ', "o"),
+  ('ὣ', "o"),
+  ('ὤ', "o"),
+  ('ὥ', "o"),
+  ('ὦ', "o"),
+  ('ὧ', "o"),
+  ('Ὠ', "O"),
+  ('Ὡ', "O"),
+  ('Ὢ', "O"),
+  ('Ὣ', "O"),
+  ('Ὤ', "O"),
+  ('Ὥ', "O"),
+  ('Ὦ', "O"),
+  ('Ὧ', "O"),
+  ('ὰ', "a"),
+  ('ά', "a"),
+  ('ὲ', "e"),
+  ('έ', "e"),
+  ('ὴ', "i"),
+  ('ή', "i"),
+  ('ὶ', "i"),
+  ('ί', "i"),
+  ('ὸ', "o"),
+  ('ό', "o"),
+  ('ὺ', "y"),
+  ('ύ', "y"),
+  ('ὼ', "o"),
+  ('ώ', "o"),
+  ('ᾀ', "a"),
+  ('ᾁ', "a"),
+  ('ᾂ', "a"),
+  ('ᾃ', "a"),
+  ('ᾄ', "a"),
+  ('ᾅ', "a"),
+  ('ᾆ', "a"),
+  ('ᾇ', "a"),
+  ('ᾈ', "A"),
+  ('ᾉ', "A"),
+  ('ᾊ', "A"),
+  ('ᾋ', "A"),
+  ('ᾌ', "A"),
+  ('ᾍ', "A"),
+  ('ᾎ', "A"),
+  ('ᾏ', "A"),
+  ('ᾐ', "i"),
+  ('ᾑ', "i"),
+  ('ᾒ', "i"),
+  ('ᾓ', "i"),
+  ('ᾔ', "i"),
+  ('ᾕ', "i"),
+  ('ᾖ', "i"),
+  ('ᾗ', "i"),
+  ('ᾘ', "I"),
+  ('ᾙ', "I"),
+  ('ᾚ', "I"),
+  ('ᾛ', "I"),
+  ('ᾜ', "I"),
+  ('ᾝ', "I"),
+  ('ᾞ', "I"),
+  ('ᾟ', "I"),
+  ('ᾠ', "o"),
+  ('ᾡ', "o"),
+  ('ᾢ', "o"),
+  ('ᾣ', "o"),
+  ('ᾤ', "o"),
+  ('ᾥ', "o"),
+  ('ᾦ', "o"),
+  ('ᾧ', "o"),
+  ('ᾨ', "O"),
+  ('ᾩ', "O"),
+  ('ᾪ', "O"),
+  ('ᾫ', "O"),
+  ('ᾬ', "O"),
+  ('ᾭ', "O"),
+  ('ᾮ', "O"),
+  ('ᾯ', "O"),
+  ('ᾰ', "a"),
+  ('ᾱ', "a"),
+  ('ᾲ', "a"),
+  ('ᾳ', "a"),
+  ('ᾴ', "a"),
+  ('ᾶ', "a"),
+  ('ᾷ', "a"),
+  ('Ᾰ', "A"),
+  ('Ᾱ', "A"),
+  ('Ὰ', "A"),
+  ('Ά', "A"),
+  ('ᾼ', "A"),
+  ('ι', "i"),
+  ('ῂ', "i"),
+  ('ῃ', "i"),
+  ('ῄ', "i"),
+  ('ῆ', "i"),
+  ('ῇ', "i"),
+  ('Ὲ', "E"),
+  ('Έ', "E"),
+  ('Ὴ', "I"),
+  ('Ή', "I"),
+  ('ῌ', "I"),
+  ('ῐ', "i"),
+  ('ῑ', "i"),
+  ('ῒ', "i"),
+  ('ΐ', "i"),
+  ('ῖ', "i"),
+  ('ῗ', "i"),
+  ('Ῐ', "I"),
+  ('Ῑ', "I"),
+  ('Ὶ', "I"),
+  ('Ί', "I"),
+  ('ῠ', "y"),
+  ('ῡ', "y"),
+  ('ῢ', "y"),
+  ('ΰ', "y"),
+  ('ῤ', "r"),
+  ('ῥ', "r"),
+  ('ῦ', "y"),
+  ('ῧ', "y"),
+  ('Ῠ', "Y"),
+  ('Ῡ', "Y"),
+  ('Ὺ', "Y"),
+  ('Ύ', "Y"),
+  ('Ῥ', "R"),
+  ('ῲ', "o"),
+  ('ῳ', "o"),
+  ('ῴ', "o"),
+  ('ῶ', "o"),
+  ('ῷ', "o"),
+  ('Ὸ', "O"),
+  ('Ό', "O"),
+  ('Ὼ', "O"),
+  ('Ώ', "O"),
+  ('ῼ', "O"),
+];
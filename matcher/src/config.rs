@@ -1,5 +1,10 @@
+use std::fmt;
+
 use crate::chars::CharClass;
-use crate::score::BONUS_BOUNDARY;
+use crate::score::{
+    BONUS_BOUNDARY, BONUS_CAMEL123, BONUS_CONSECUTIVE, BONUS_FIRST_CHAR_MULTIPLIER, BONUS_NON_WORD,
+    MAX_CHAR_SCORE, PENALTY_GAP_EXTENSION, PENALTY_GAP_START, PENALTY_TYPO, SCORE_MATCH,
+};
 
 /// Configuration data that controls how a matcher behaves
 #[non_exhaustive]
@@ -7,15 +12,30 @@ use crate::score::BONUS_BOUNDARY;
 pub struct Config {
     /// Characters that act as delimiters and provide bonus
     /// for matching the following char
-    pub(crate) delimiter_chars: &'static [u8],
-    /// Extra bonus for word boundary after whitespace character or beginning of the string
-    pub(crate) bonus_boundary_white: u16,
-    /// Extra bonus for word boundary after slash, colon, semi-colon, and comma
-    pub(crate) bonus_boundary_delimiter: u16,
+    pub(crate) delimiters: Delimiters,
+    /// The scores and penalties used for ranking matches
+    pub(crate) scoring: Scoring,
     pub(crate) initial_char_class: CharClass,
 
     /// Whether to normalize latin script characters to ASCII (enabled by default)
     pub normalize: bool,
+    /// Whether to normalize full-width and half-width forms to their regular
+    /// width (disabled by default), see
+    /// [`normalize_width`](crate::chars::normalize_width)
+    #[cfg(feature = "unicode-width-normalization")]
+    pub normalize_width: bool,
+    /// Whether to normalize katakana to hiragana (disabled by default), see
+    /// [`normalize_kana`](crate::chars::normalize_kana)
+    #[cfg(feature = "unicode-kana-normalization")]
+    pub normalize_kana: bool,
+    /// Whether to transliterate Cyrillic and Greek haystack characters to
+    /// Latin (disabled by default), see
+    /// [`transliterate`](crate::chars::transliterate). Match indices still
+    /// refer to the characters of the original haystack. The needle should
+    /// not contain characters that are transliterated since they can never
+    /// match. Regex matching ignores this setting.
+    #[cfg(feature = "unicode-transliteration")]
+    pub transliterate: bool,
     /// whether to ignore casing
     pub ignore_case: bool,
     /// Whether to provide a bonus to matches by their distance from the start
@@ -26,6 +46,18 @@ pub struct Config {
     /// match. For a full fzf-like fuzzy matcher/picker word segmentation and
     /// explicit prefix literals should be used instead.
     pub prefer_prefix: bool,
+    /// The maximum number of typos tolerated by fuzzy matching
+    /// ([`Matcher::fuzzy_match`](crate::Matcher::fuzzy_match) and
+    /// [`AtomKind::Fuzzy`](crate::pattern::AtomKind::Fuzzy)). A typo is a
+    /// substituted needle char, two transposed needle chars or an extra needle
+    /// char. At most one typo is allowed per 4 needle chars so short needles
+    /// are always matched exactly. Each typo receives a large penalty.
+    ///
+    /// Typo tolerant matching only runs for haystacks that don't match
+    /// without typos. It is considerably slower (`O(mnk)`) than normal
+    /// fuzzy matching and may allocate. No indices are computed for typo
+    /// tolerant matches in very long haystacks. Disabled (0) by default.
+    pub max_typos: u8,
 }
 
 impl Config {
@@ -33,38 +65,439 @@ impl Config {
     /// Default::default can not be called in a const context
     pub const DEFAULT: Self = {
         Config {
-            delimiter_chars: b"/,:;|",
-            bonus_boundary_white: BONUS_BOUNDARY + 2,
-            bonus_boundary_delimiter: BONUS_BOUNDARY + 1,
+            delimiters: Delimiters::from_ascii(b"/,:;|"),
+            scoring: Scoring::DEFAULT,
             initial_char_class: CharClass::Whitespace,
             normalize: true,
+            #[cfg(feature = "unicode-width-normalization")]
+            normalize_width: false,
+            #[cfg(feature = "unicode-kana-normalization")]
+            normalize_kana: false,
+            #[cfg(feature = "unicode-transliteration")]
+            transliterate: false,
             ignore_case: true,
             prefer_prefix: false,
+            max_typos: 0,
         }
     };
 }
 
 impl Config {
     /// Configures the matcher with bonuses appropriate for matching file paths.
+    /// The ASCII delimiters are replaced with the path separators (and `:`),
+    /// non-ASCII [delimiters](Config::set_delimiters) are kept.
     pub fn set_match_paths(&mut self) {
         if cfg!(windows) {
-            self.delimiter_chars = b"/:\\";
+            self.delimiters.ascii = ascii_delimiters(b"/:\\");
         } else {
-            self.delimiter_chars = b"/:";
+            self.delimiters.ascii = ascii_delimiters(b"/:");
         }
-        self.bonus_boundary_white = BONUS_BOUNDARY;
+        self.scoring.bonus_boundary_white = self.scoring.bonus_boundary;
         self.initial_char_class = CharClass::Delimiter;
     }
 
     /// Configures the matcher with bonuses appropriate for matching file paths.
+    /// The ASCII delimiters are replaced with the path separators, non-ASCII
+    /// [delimiters](Config::set_delimiters) are kept.
     pub const fn match_paths(mut self) -> Self {
         if cfg!(windows) {
-            self.delimiter_chars = b"/\\";
+            self.delimiters.ascii = ascii_delimiters(b"/\\");
         } else {
-            self.delimiter_chars = b"/";
+            self.delimiters.ascii = ascii_delimiters(b"/");
         }
-        self.bonus_boundary_white = BONUS_BOUNDARY;
+        self.scoring.bonus_boundary_white = self.scoring.bonus_boundary;
         self.initial_char_class = CharClass::Delimiter;
         self
     }
 }
+
+impl Config {
+    /// Returns the characters that act as delimiters.
+    pub fn delimiters(&self) -> &Delimiters {
+        &self.delimiters
+    }
+
+    /// Replaces the characters that act as delimiters. Matching the first
+    /// char after a delimiter receives the
+    /// [delimiter bonus](Scoring::with_bonus_boundary_delimiter). Defaults to
+    /// `/,:;|`.
+    ///
+    /// Note that [`set_match_paths`](Config::set_match_paths) and
+    /// [`match_paths`](Config::match_paths) override the ASCII delimiters, so
+    /// they should be called before this function.
+    pub fn set_delimiters(&mut self, delimiters: Delimiters) {
+        self.delimiters = delimiters;
+    }
+
+    /// Builder style version of [`set_delimiters`](Config::set_delimiters).
+    pub fn with_delimiters(mut self, delimiters: Delimiters) -> Self {
+        self.set_delimiters(delimiters);
+        self
+    }
+
+    /// Returns the scoring profile used for ranking matches.
+    pub const fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    /// Replaces the scoring profile used for ranking matches. Returns an error
+    /// (and leaves the config unchanged) if `scoring` is invalid, see
+    /// [`Scoring::validate`].
+    ///
+    /// Note that [`set_match_paths`](Config::set_match_paths) and
+    /// [`match_paths`](Config::match_paths) override the whitespace boundary
+    /// bonus, so they should be called before this function.
+    pub fn set_scoring(&mut self, scoring: Scoring) -> Result<(), ScoringError> {
+        scoring.validate()?;
+        self.scoring = scoring;
+        Ok(())
+    }
+
+    /// Builder style version of [`set_scoring`](Config::set_scoring).
+    pub fn with_scoring(mut self, scoring: Scoring) -> Result<Self, ScoringError> {
+        self.set_scoring(scoring)?;
+        Ok(self)
+    }
+}
+
+/// A set of characters that act as delimiters (see
+/// [`Config::set_delimiters`]). Both ASCII and non-ASCII characters are
+/// supported:
+///
+/// ```
+/// # use nucleo_matcher::{Config, Delimiters};
+/// let delimiters = Delimiters::new("/:→、".chars());
+/// assert!(delimiters.contains('→'));
+/// let config = Config::DEFAULT.with_delimiters(delimiters);
+/// ```
+///
+/// Letters, numbers and whitespace are never treated as delimiters, even if
+/// they are part of the set.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Delimiters {
+    /// bitset of the ASCII delimiters
+    ascii: u128,
+    /// the sorted and deduplicated non-ASCII delimiters
+    non_ascii: Vec<char>,
+}
+
+impl Delimiters {
+    /// Creates a set of delimiters from an arbitrary sequence of `chars`.
+    pub fn new(chars: impl IntoIterator<Item = char>) -> Self {
+        let mut delimiters = Delimiters {
+            ascii: 0,
+            non_ascii: Vec::new(),
+        };
+        for c in chars {
+            if c.is_ascii() {
+                delimiters.ascii |= 1 << c as u32;
+            } else {
+                delimiters.non_ascii.push(c);
+            }
+        }
+        delimiters.non_ascii.sort_unstable();
+        delimiters.non_ascii.dedup();
+        delimiters
+    }
+
+    /// Creates a set of ASCII delimiters, this function can be used in a
+    /// const context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` contains non-ASCII bytes.
+    pub const fn from_ascii(chars: &[u8]) -> Self {
+        Delimiters {
+            ascii: ascii_delimiters(chars),
+            non_ascii: Vec::new(),
+        }
+    }
+
+    /// Returns whether `c` is part of this set.
+    #[inline]
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii() {
+            self.contains_ascii(c as u8)
+        } else {
+            self.contains_non_ascii(c)
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn contains_ascii(&self, c: u8) -> bool {
+        self.ascii & (1 << c) != 0
+    }
+
+    #[inline(always)]
+    pub(crate) fn contains_non_ascii(&self, c: char) -> bool {
+        !self.non_ascii.is_empty() && self.non_ascii.binary_search(&c).is_ok()
+    }
+}
+
+/// Returns the bitset of the ASCII delimiters in `chars`.
+const fn ascii_delimiters(chars: &[u8]) -> u128 {
+    let mut ascii = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        assert!(chars[i].is_ascii(), "non-ASCII delimiter byte");
+        ascii |= 1 << chars[i];
+        i += 1;
+    }
+    ascii
+}
+
+/// The scores and penalties the matcher uses to rank matches.
+///
+/// Each matched char receives [`score_match`](Scoring::with_score_match) and
+/// a bonus depending on the class of the matched char and the char before it.
+/// The bonus of the first matched char is multiplied with
+/// [`bonus_first_char_multiplier`](Scoring::with_bonus_first_char_multiplier).
+/// Gaps between matched chars are penalized. The defaults (modeled after fzf)
+/// work well for most usecases, a custom profile can be used to tune the
+/// ranking for different kinds of haystacks:
+///
+/// ```
+/// # use nucleo_matcher::{Config, Scoring};
+/// let scoring = Scoring::DEFAULT
+///     .with_bonus_camel123(7)
+///     .with_penalty_gap_start(5);
+/// let config = Config::DEFAULT.with_scoring(scoring).unwrap();
+/// assert_eq!(config.scoring().bonus_camel123(), 7);
+/// ```
+///
+/// A scoring profile must be [valid](Scoring::validate) before it can be
+/// used by a [`Config`].
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Scoring {
+    pub(crate) score_match: u16,
+    pub(crate) penalty_gap_start: u16,
+    pub(crate) penalty_gap_extension: u16,
+    pub(crate) bonus_boundary: u16,
+    pub(crate) bonus_boundary_white: u16,
+    pub(crate) bonus_boundary_delimiter: u16,
+    pub(crate) bonus_camel123: u16,
+    pub(crate) bonus_non_word: u16,
+    pub(crate) bonus_consecutive: u16,
+    pub(crate) bonus_first_char_multiplier: u16,
+    pub(crate) penalty_typo: u16,
+}
+
+impl Scoring {
+    /// The default scoring profile, implemented as a constant since
+    /// Default::default can not be called in a const context
+    pub const DEFAULT: Self = Scoring {
+        score_match: SCORE_MATCH,
+        penalty_gap_start: PENALTY_GAP_START,
+        penalty_gap_extension: PENALTY_GAP_EXTENSION,
+        bonus_boundary: BONUS_BOUNDARY,
+        bonus_boundary_white: BONUS_BOUNDARY + 2,
+        bonus_boundary_delimiter: BONUS_BOUNDARY + 1,
+        bonus_camel123: BONUS_CAMEL123,
+        bonus_non_word: BONUS_NON_WORD,
+        bonus_consecutive: BONUS_CONSECUTIVE,
+        bonus_first_char_multiplier: BONUS_FIRST_CHAR_MULTIPLIER,
+        penalty_typo: PENALTY_TYPO,
+    };
+
+    /// The score of each matched char (16 by default). Must be positive.
+    pub const fn with_score_match(mut self, score: u16) -> Self {
+        self.score_match = score;
+        self
+    }
+
+    /// The penalty for the first char of a gap between matched chars (3 by
+    /// default).
+    pub const fn with_penalty_gap_start(mut self, penalty: u16) -> Self {
+        self.penalty_gap_start = penalty;
+        self
+    }
+
+    /// The penalty for each additional char of a gap between matched chars
+    /// (1 by default).
+    pub const fn with_penalty_gap_extension(mut self, penalty: u16) -> Self {
+        self.penalty_gap_extension = penalty;
+        self
+    }
+
+    /// The bonus for matching the start of a word after a non-word char (8 by
+    /// default). This is also the largest prefix bonus awarded when
+    /// [`Config::prefer_prefix`] is enabled.
+    pub const fn with_bonus_boundary(mut self, bonus: u16) -> Self {
+        self.bonus_boundary = bonus;
+        self
+    }
+
+    /// The bonus for matching the start of a word after whitespace or at the
+    /// start of the haystack (10 by default).
+    pub const fn with_bonus_boundary_white(mut self, bonus: u16) -> Self {
+        self.bonus_boundary_white = bonus;
+        self
+    }
+
+    /// The bonus for matching the start of a word after a delimiter char like
+    /// `/` (9 by default).
+    pub const fn with_bonus_boundary_delimiter(mut self, bonus: u16) -> Self {
+        self.bonus_boundary_delimiter = bonus;
+        self
+    }
+
+    /// The bonus for matching an upper case char after a lower case char
+    /// (`camelCase`) or a number after a non-number (`letter123`), 5 by
+    /// default.
+    pub const fn with_bonus_camel123(mut self, bonus: u16) -> Self {
+        self.bonus_camel123 = bonus;
+        self
+    }
+
+    /// The bonus for matching a non-word char (8 by default).
+    pub const fn with_bonus_non_word(mut self, bonus: u16) -> Self {
+        self.bonus_non_word = bonus;
+        self
+    }
+
+    /// The minimum bonus for chars that directly follow the previous matched
+    /// char (4 by default). Must be positive.
+    pub const fn with_bonus_consecutive(mut self, bonus: u16) -> Self {
+        self.bonus_consecutive = bonus;
+        self
+    }
+
+    /// The bonus of the first matched char is multiplied with this value (2
+    /// by default).
+    pub const fn with_bonus_first_char_multiplier(mut self, multiplier: u16) -> Self {
+        self.bonus_first_char_multiplier = multiplier;
+        self
+    }
+
+    /// The penalty for each typo (see [`Config::max_typos`]), 16 by default.
+    pub const fn with_penalty_typo(mut self, penalty: u16) -> Self {
+        self.penalty_typo = penalty;
+        self
+    }
+
+    /// See [`with_score_match`](Scoring::with_score_match).
+    pub const fn score_match(&self) -> u16 {
+        self.score_match
+    }
+
+    /// See [`with_penalty_gap_start`](Scoring::with_penalty_gap_start).
+    pub const fn penalty_gap_start(&self) -> u16 {
+        self.penalty_gap_start
+    }
+
+    /// See [`with_penalty_gap_extension`](Scoring::with_penalty_gap_extension).
+    pub const fn penalty_gap_extension(&self) -> u16 {
+        self.penalty_gap_extension
+    }
+
+    /// See [`with_bonus_boundary`](Scoring::with_bonus_boundary).
+    pub const fn bonus_boundary(&self) -> u16 {
+        self.bonus_boundary
+    }
+
+    /// See [`with_bonus_boundary_white`](Scoring::with_bonus_boundary_white).
+    pub const fn bonus_boundary_white(&self) -> u16 {
+        self.bonus_boundary_white
+    }
+
+    /// See [`with_bonus_boundary_delimiter`](Scoring::with_bonus_boundary_delimiter).
+    pub const fn bonus_boundary_delimiter(&self) -> u16 {
+        self.bonus_boundary_delimiter
+    }
+
+    /// See [`with_bonus_camel123`](Scoring::with_bonus_camel123).
+    pub const fn bonus_camel123(&self) -> u16 {
+        self.bonus_camel123
+    }
+
+    /// See [`with_bonus_non_word`](Scoring::with_bonus_non_word).
+    pub const fn bonus_non_word(&self) -> u16 {
+        self.bonus_non_word
+    }
+
+    /// See [`with_bonus_consecutive`](Scoring::with_bonus_consecutive).
+    pub const fn bonus_consecutive(&self) -> u16 {
+        self.bonus_consecutive
+    }
+
+    /// See [`with_bonus_first_char_multiplier`](Scoring::with_bonus_first_char_multiplier).
+    pub const fn bonus_first_char_multiplier(&self) -> u16 {
+        self.bonus_first_char_multiplier
+    }
+
+    /// See [`with_penalty_typo`](Scoring::with_penalty_typo).
+    pub const fn penalty_typo(&self) -> u16 {
+        self.penalty_typo
+    }
+
+    /// Checks that this profile can be used for matching. Scores are `u16`
+    /// so the largest score a single matched char can receive (the match
+    /// score plus the largest bonus multiplied with the first char
+    /// multiplier plus the largest prefix bonus) must not exceed 204. This
+    /// guarantees that the score of a needle never overflows.
+    pub const fn validate(&self) -> Result<(), ScoringError> {
+        if self.score_match == 0 {
+            return Err(ScoringError::ZeroScoreMatch);
+        }
+        if self.bonus_consecutive == 0 {
+            return Err(ScoringError::ZeroBonusConsecutive);
+        }
+        let mut max_bonus = self.bonus_boundary;
+        let bonuses = [
+            self.bonus_boundary_white,
+            self.bonus_boundary_delimiter,
+            self.bonus_camel123,
+            self.bonus_non_word,
+            self.bonus_consecutive,
+        ];
+        let mut i = 0;
+        while i < bonuses.len() {
+            if bonuses[i] > max_bonus {
+                max_bonus = bonuses[i];
+            }
+            i += 1;
+        }
+        let multiplier = if self.bonus_first_char_multiplier > 1 {
+            self.bonus_first_char_multiplier
+        } else {
+            1
+        };
+        let max_char_score = self.score_match as u32
+            + max_bonus as u32 * multiplier as u32
+            + self.bonus_boundary as u32;
+        if max_char_score > MAX_CHAR_SCORE as u32 {
+            return Err(ScoringError::CharScoreTooLarge);
+        }
+        Ok(())
+    }
+}
+
+/// The reason a [`Scoring`] profile is invalid, see [`Scoring::validate`].
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ScoringError {
+    /// The match score is zero.
+    ZeroScoreMatch,
+    /// The consecutive bonus is zero.
+    ZeroBonusConsecutive,
+    /// The largest score a single char can receive is too large and the
+    /// score of long needles might overflow.
+    CharScoreTooLarge,
+}
+
+impl fmt::Display for ScoringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoringError::ZeroScoreMatch => f.write_str("the match score must be positive"),
+            ScoringError::ZeroBonusConsecutive => {
+                f.write_str("the consecutive bonus must be positive")
+            }
+            ScoringError::CharScoreTooLarge => write!(
+                f,
+                "the largest score of a single char must not exceed {MAX_CHAR_SCORE}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScoringError {}
@@ -2,7 +2,6 @@ use memchr::memmem;
 use memchr::{Memchr, Memchr2};
 
 use crate::chars::{AsciiChar, Char};
-use crate::score::{BONUS_FIRST_CHAR_MULTIPLIER, SCORE_MATCH};
 use crate::Matcher;
 
 impl Matcher {
@@ -22,12 +21,13 @@ impl Matcher {
                     .unwrap_or(self.config.initial_char_class);
                 let char_class = AsciiChar(haystack[i]).char_class(&self.config);
                 let bonus = self.config.bonus_for(prev_char_class, char_class);
-                let score = bonus * BONUS_FIRST_CHAR_MULTIPLIER + SCORE_MATCH;
+                let score = bonus * self.config.scoring.bonus_first_char_multiplier
+                    + self.config.scoring.score_match;
                 if score > max_score {
                     max_pos = i as u32;
                     max_score = score;
                     // can't get better than this
-                    if bonus >= self.config.bonus_boundary_white {
+                    if bonus >= self.config.scoring.bonus_boundary_white {
                         break;
                     }
                 }
@@ -40,12 +40,13 @@ impl Matcher {
                     .map(|i| AsciiChar(haystack[i]).char_class(&self.config))
                     .unwrap_or(self.config.initial_char_class);
                 let bonus = self.config.bonus_for(prev_char_class, char_class);
-                let score = bonus * BONUS_FIRST_CHAR_MULTIPLIER + SCORE_MATCH;
+                let score = bonus * self.config.scoring.bonus_first_char_multiplier
+                    + self.config.scoring.score_match;
                 if score > max_score {
                     max_pos = i as u32;
                     max_score = score;
                     // can't get better than this
-                    if bonus >= self.config.bonus_boundary_white {
+                    if bonus >= self.config.scoring.bonus_boundary_white {
                         break;
                     }
                 }
@@ -78,7 +79,8 @@ impl Matcher {
                 .unwrap_or(self.config.initial_char_class);
             let char_class = AsciiChar(haystack[i]).char_class(&self.config);
             let bonus = self.config.bonus_for(prev_char_class, char_class);
-            let score = bonus * BONUS_FIRST_CHAR_MULTIPLIER + SCORE_MATCH;
+            let score = bonus * self.config.scoring.bonus_first_char_multiplier
+                + self.config.scoring.score_match;
             if score > max_score
                 && haystack[i + prefilter_len..(i + needle.len()).min(haystack.len())]
                     .iter()
@@ -88,7 +90,7 @@ impl Matcher {
                 max_pos = i;
                 max_score = score;
                 // can't get better than this
-                if bonus >= self.config.bonus_boundary_white {
+                if bonus >= self.config.scoring.bonus_boundary_white {
                     break;
                 }
             }
@@ -158,12 +160,13 @@ impl Matcher {
                     .map(|i| AsciiChar(haystack[i]).char_class(&self.config))
                     .unwrap_or(self.config.initial_char_class);
                 let bonus = self.config.bonus_for(prev_char_class, char_class);
-                let score = bonus * BONUS_FIRST_CHAR_MULTIPLIER + SCORE_MATCH;
+                let score = bonus * self.config.scoring.bonus_first_char_multiplier
+                    + self.config.scoring.score_match;
                 if score > max_score {
                     max_pos = i;
                     max_score = score;
                     // can't get better than this
-                    if bonus >= self.config.bonus_boundary_white {
+                    if bonus >= self.config.scoring.bonus_boundary_white {
                         break;
                     }
                 }
@@ -202,12 +205,13 @@ impl Matcher {
             }
             let bonus = self.config.bonus_for(prev_class, char_class);
             prev_class = char_class;
-            let score = bonus * BONUS_FIRST_CHAR_MULTIPLIER + SCORE_MATCH;
+            let score = bonus * self.config.scoring.bonus_first_char_multiplier
+                + self.config.scoring.score_match;
             if score > max_score {
                 max_pos = i as u32;
                 max_score = score;
                 // can't get better than this
-                if bonus >= self.config.bonus_boundary_white {
+                if bonus >= self.config.scoring.bonus_boundary_white {
                     break;
                 }
             }
@@ -237,14 +241,15 @@ impl Matcher {
             .map(|i| haystack[i].char_class(&self.config))
             .unwrap_or(self.config.initial_char_class);
         let end = haystack.len() - needle.len();
-        for (i, &c) in haystack[start..end].iter().enumerate() {
+        for (i, &c) in haystack[start..=end].iter().enumerate() {
             let (c, char_class) = c.char_class_and_normalize(&self.config);
             if c != needle[0] {
                 continue;
             }
             let bonus = self.config.bonus_for(prev_class, char_class);
             prev_class = char_class;
-            let score = bonus * BONUS_FIRST_CHAR_MULTIPLIER + SCORE_MATCH;
+            let score = bonus * self.config.scoring.bonus_first_char_multiplier
+                + self.config.scoring.score_match;
             if score > max_score
                 && haystack[start + i + 1..start + i + needle.len()]
                     .iter()
@@ -254,7 +259,7 @@ impl Matcher {
                 max_pos = i;
                 max_score = score;
                 // can't get better than this
-                if bonus >= self.config.bonus_boundary_white {
+                if bonus >= self.config.scoring.bonus_boundary_white {
                     break;
                 }
             }
@@ -2,11 +2,8 @@ use std::cmp::max;
 
 use crate::chars::{Char, CharClass};
 use crate::matrix::{MatcherDataView, MatrixCell, ScoreCell};
-use crate::score::{
-    BONUS_BOUNDARY, BONUS_CONSECUTIVE, BONUS_FIRST_CHAR_MULTIPLIER, MAX_PREFIX_BONUS,
-    PENALTY_GAP_EXTENSION, PENALTY_GAP_START, PREFIX_BONUS_SCALE, SCORE_MATCH,
-};
-use crate::{Config, Matcher};
+use crate::score::PREFIX_BONUS_SCALE;
+use crate::{Config, Matcher, Scoring};
 
 impl Matcher {
     pub(crate) fn fuzzy_match_optimal<const INDICES: bool, H: Char + PartialEq<N>, N: Char>(
@@ -42,7 +39,7 @@ impl Matcher {
         }
 
         // populate the matrix and find the best score
-        let matrix_len = matrix.populate_matrix::<INDICES, _>(needle);
+        let matrix_len = matrix.populate_matrix::<INDICES, _>(needle, &self.config.scoring);
         let last_row_off = matrix.row_offs[needle.len() - 1];
         let relative_last_row_off = last_row_off as usize + 1 - needle.len();
         let (match_end, match_score_cell) = matrix.current_row[relative_last_row_off..]
@@ -60,23 +57,23 @@ impl Matcher {
 const UNMATCHED: ScoreCell = ScoreCell {
     score: 0,
     // if matched is true then the consecutive bonus
-    // is always atleast BONUS_CONSECUTIVE so
+    // is always atleast bonus_consecutive (which is positive) so
     // this constant can never occur naturally
     consecutive_bonus: 0,
     matched: true,
 };
 
-fn next_m_cell(p_score: u16, bonus: u16, m_cell: ScoreCell) -> ScoreCell {
+fn next_m_cell(scoring: &Scoring, p_score: u16, bonus: u16, m_cell: ScoreCell) -> ScoreCell {
     if m_cell == UNMATCHED {
         return ScoreCell {
-            score: p_score + bonus + SCORE_MATCH,
+            score: p_score + bonus + scoring.score_match,
             matched: false,
             consecutive_bonus: bonus as u8,
         };
     }
 
-    let mut consecutive_bonus = max(m_cell.consecutive_bonus as u16, BONUS_CONSECUTIVE);
-    if bonus >= BONUS_BOUNDARY && bonus > consecutive_bonus {
+    let mut consecutive_bonus = max(m_cell.consecutive_bonus as u16, scoring.bonus_consecutive);
+    if bonus >= scoring.bonus_boundary && bonus > consecutive_bonus {
         consecutive_bonus = bonus
     }
 
@@ -84,22 +81,22 @@ fn next_m_cell(p_score: u16, bonus: u16, m_cell: ScoreCell) -> ScoreCell {
     let score_skip = p_score + bonus;
     if score_match > score_skip {
         ScoreCell {
-            score: score_match + SCORE_MATCH,
+            score: score_match + scoring.score_match,
             matched: true,
             consecutive_bonus: consecutive_bonus as u8,
         }
     } else {
         ScoreCell {
-            score: score_skip + SCORE_MATCH,
+            score: score_skip + scoring.score_match,
             matched: false,
             consecutive_bonus: bonus as u8,
         }
     }
 }
 
-fn p_score(prev_p_score: u16, prev_m_score: u16) -> (u16, bool) {
-    let score_match = prev_m_score.saturating_sub(PENALTY_GAP_START);
-    let score_skip = prev_p_score.saturating_sub(PENALTY_GAP_EXTENSION);
+fn p_score(scoring: &Scoring, prev_p_score: u16, prev_m_score: u16) -> (u16, bool) {
+    let score_match = prev_m_score.saturating_sub(scoring.penalty_gap_start);
+    let score_skip = prev_p_score.saturating_sub(scoring.penalty_gap_extension);
     if score_match > score_skip {
         (score_match, true)
     } else {
@@ -154,7 +151,10 @@ impl<H: Char> MatcherDataView<'_, H> {
             return false;
         }
         debug_assert_eq!(self.row_offs[0], 0);
+        let scoring = &config.scoring;
+        let max_prefix_bonus = scoring.bonus_boundary * PREFIX_BONUS_SCALE;
         Self::score_row::<true, INDICES, _>(
+            scoring,
             self.current_row,
             self.matrix_cells,
             self.haystack,
@@ -166,11 +166,14 @@ impl<H: Char> MatcherDataView<'_, H> {
             needle[1],
             if config.prefer_prefix {
                 if start == 0 {
-                    MAX_PREFIX_BONUS * PREFIX_BONUS_SCALE
+                    max_prefix_bonus
                 } else {
-                    (MAX_PREFIX_BONUS * PREFIX_BONUS_SCALE - PENALTY_GAP_START).saturating_sub(
-                        (start - 1).min(u16::MAX as u32) as u16 * PENALTY_GAP_EXTENSION,
-                    )
+                    max_prefix_bonus
+                        .saturating_sub(scoring.penalty_gap_start)
+                        .saturating_sub(
+                            ((start - 1).min(u16::MAX as u32) as u16)
+                                .saturating_mul(scoring.penalty_gap_extension),
+                        )
                 }
             } else {
                 0
@@ -181,6 +184,7 @@ impl<H: Char> MatcherDataView<'_, H> {
 
     #[allow(clippy::too_many_arguments)]
     fn score_row<const FIRST_ROW: bool, const INDICES: bool, N: Char>(
+        scoring: &Scoring,
         current_row: &mut [ScoreCell],
         matrix_cells: &mut [MatrixCell],
         haystack: &[H],
@@ -205,12 +209,12 @@ impl<H: Char> MatcherDataView<'_, H> {
         let mut prev_p_score = 0;
         let mut prev_m_score = 0;
         for (((&c, bonus), score_cell), matrix_cell) in skipped_col_iter {
-            let (p_score, p_matched) = p_score(prev_p_score, prev_m_score);
+            let (p_score, p_matched) = p_score(scoring, prev_p_score, prev_m_score);
             let m_cell = if FIRST_ROW {
                 let cell = if c == needle_char {
                     ScoreCell {
-                        score: *bonus as u16 * BONUS_FIRST_CHAR_MULTIPLIER
-                            + SCORE_MATCH
+                        score: *bonus as u16 * scoring.bonus_first_char_multiplier
+                            + scoring.score_match
                             + prefix_bonus / PREFIX_BONUS_SCALE,
                         matched: false,
                         consecutive_bonus: *bonus,
@@ -218,7 +222,7 @@ impl<H: Char> MatcherDataView<'_, H> {
                 } else {
                     UNMATCHED
                 };
-                prefix_bonus = prefix_bonus.saturating_sub(PENALTY_GAP_EXTENSION);
+                prefix_bonus = prefix_bonus.saturating_sub(scoring.penalty_gap_extension);
                 cell
             } else {
                 *score_cell
@@ -235,12 +239,12 @@ impl<H: Char> MatcherDataView<'_, H> {
             .zip(current_row[next_relative_row_off as usize..].iter_mut())
             .zip(matrix_cells[(next_relative_row_off - relative_row_off) as usize..].iter_mut());
         for (((c, bonus), score_cell), matrix_cell) in col_iter {
-            let (p_score, p_matched) = p_score(prev_p_score, prev_m_score);
+            let (p_score, p_matched) = p_score(scoring, prev_p_score, prev_m_score);
             let m_cell = if FIRST_ROW {
                 let cell = if c[0] == needle_char {
                     ScoreCell {
-                        score: bonus[0] as u16 * BONUS_FIRST_CHAR_MULTIPLIER
-                            + SCORE_MATCH
+                        score: bonus[0] as u16 * scoring.bonus_first_char_multiplier
+                            + scoring.score_match
                             + prefix_bonus / PREFIX_BONUS_SCALE,
                         matched: false,
                         consecutive_bonus: bonus[0],
@@ -248,13 +252,13 @@ impl<H: Char> MatcherDataView<'_, H> {
                 } else {
                     UNMATCHED
                 };
-                prefix_bonus = prefix_bonus.saturating_sub(PENALTY_GAP_EXTENSION);
+                prefix_bonus = prefix_bonus.saturating_sub(scoring.penalty_gap_extension);
                 cell
             } else {
                 *score_cell
             };
             *score_cell = if c[1] == next_needle_char {
-                next_m_cell(p_score, bonus[1] as u16, m_cell)
+                next_m_cell(scoring, p_score, bonus[1] as u16, m_cell)
             } else {
                 UNMATCHED
             };
@@ -266,7 +270,11 @@ impl<H: Char> MatcherDataView<'_, H> {
         }
     }
 
-    fn populate_matrix<const INDICES: bool, N: Char>(&mut self, needle: &[N]) -> usize
+    fn populate_matrix<const INDICES: bool, N: Char>(
+        &mut self,
+        needle: &[N],
+        scoring: &Scoring,
+    ) -> usize
     where
         H: PartialEq<N>,
     {
@@ -279,6 +287,7 @@ impl<H: Char> MatcherDataView<'_, H> {
         let (mut needle_idx, (mut needle_char, mut row_off)) = row_iter.next().unwrap();
         for (next_needle_idx, (next_needle_char, next_row_off)) in row_iter {
             Self::score_row::<false, INDICES, _>(
+                scoring,
                 self.current_row,
                 matrix_cells,
                 self.haystack,
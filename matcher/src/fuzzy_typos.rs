@@ -0,0 +1,478 @@
+use std::cmp::max;
+
+use crate::chars::{AsciiChar, Char};
+use crate::matrix::MAX_MATRIX_SIZE;
+use crate::score::{PREFIX_BONUS_SCALE, TYPO_MIN_NEEDLE_LEN};
+use crate::{Config, Matcher, Scoring, Utf32Str};
+
+const NONE: i32 = i32::MIN / 4;
+
+/// The operation that produced a cell of the DP table, used to reconstruct
+/// the indices.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Start,
+    Match,
+    Substitute,
+    Transpose,
+    Insert,
+    Gap,
+}
+
+/// Where a path through the DP table came from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum From {
+    Start,
+    Matched,
+    Skipped,
+}
+
+/// Encodes an [`Op`] together with the state it came [`From`].
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+struct Trace(u8);
+
+impl Trace {
+    fn new(op: Op, from: From) -> Trace {
+        Trace(op as u8 | (from as u8) << 4)
+    }
+
+    fn op(self) -> Op {
+        match self.0 & 0xf {
+            0 => Op::Start,
+            1 => Op::Match,
+            2 => Op::Substitute,
+            3 => Op::Transpose,
+            4 => Op::Insert,
+            _ => Op::Gap,
+        }
+    }
+
+    fn from(self) -> From {
+        match self.0 >> 4 {
+            0 => From::Start,
+            1 => From::Matched,
+            _ => From::Skipped,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Cell {
+    /// best score if the needle prefix ends with the current haystack char
+    matched: i32,
+    consecutive_bonus: u16,
+    /// best score if the current haystack char is skipped
+    skipped: i32,
+}
+
+const EMPTY: Cell = Cell {
+    matched: NONE,
+    consecutive_bonus: 0,
+    skipped: NONE,
+};
+
+/// Scratch memory for typo tolerant matching, grown on demand.
+#[derive(Default)]
+pub(crate) struct TypoBuf {
+    /// the three most recent columns of the DP table
+    columns: Vec<Cell>,
+    /// `(matched, skipped)` traces of the entire DP table, only used when
+    /// computing indices
+    traces: Vec<(Trace, Trace)>,
+}
+
+impl Matcher {
+    /// Fuzzy matching that tolerates up to [`Config::max_typos`] typos. Only
+    /// used as a fallback when the needle doesn't match without typos.
+    pub(crate) fn fuzzy_match_typos<const INDICES: bool>(
+        &mut self,
+        haystack: Utf32Str<'_>,
+        needle: Utf32Str<'_>,
+        indices: &mut Vec<u32>,
+    ) -> Option<u16> {
+        let max_typos = (self.config.max_typos as usize).min(needle.len() / TYPO_MIN_NEEDLE_LEN);
+        if max_typos == 0 || haystack.len() + max_typos < needle.len() {
+            return None;
+        }
+        let Matcher {
+            config, typo_buf, ..
+        } = self;
+        match (haystack, needle) {
+            (Utf32Str::Ascii(haystack), Utf32Str::Ascii(needle)) => typo_buf
+                .fuzzy_match::<INDICES, _, _>(
+                    config,
+                    AsciiChar::cast(haystack),
+                    AsciiChar::cast(needle),
+                    max_typos,
+                    indices,
+                ),
+            (Utf32Str::Ascii(haystack), Utf32Str::Unicode(needle)) => typo_buf
+                .fuzzy_match::<INDICES, _, _>(
+                    config,
+                    AsciiChar::cast(haystack),
+                    needle,
+                    max_typos,
+                    indices,
+                ),
+            (Utf32Str::Unicode(haystack), Utf32Str::Ascii(needle)) => typo_buf
+                .fuzzy_match::<INDICES, _, _>(
+                    config,
+                    haystack,
+                    AsciiChar::cast(needle),
+                    max_typos,
+                    indices,
+                ),
+            (Utf32Str::Unicode(haystack), Utf32Str::Unicode(needle)) => {
+                typo_buf.fuzzy_match::<INDICES, _, _>(config, haystack, needle, max_typos, indices)
+            }
+            (Utf32Str::Clusters { .. }, _) | (_, Utf32Str::Clusters { .. }) => {
+                unreachable!("multi char graphemes are replaced before matching")
+            }
+        }
+    }
+}
+
+/// Returns the bonus awarded by [`Config::prefer_prefix`] if the match starts
+/// at haystack index `start`, this mirrors the optimal fuzzy matcher.
+fn prefix_bonus(config: &Config, start: usize) -> u16 {
+    if !config.prefer_prefix {
+        return 0;
+    }
+    let scoring = &config.scoring;
+    let max_prefix_bonus = scoring.bonus_boundary * PREFIX_BONUS_SCALE;
+    let prefix_bonus = if start == 0 {
+        max_prefix_bonus
+    } else {
+        max_prefix_bonus
+            .saturating_sub(scoring.penalty_gap_start)
+            .saturating_sub(
+                ((start - 1).min(u16::MAX as usize) as u16)
+                    .saturating_mul(scoring.penalty_gap_extension),
+            )
+    };
+    prefix_bonus / PREFIX_BONUS_SCALE
+}
+
+/// Scores matching a haystack char with `bonus` after the state `from`
+/// (with score `score` and `consecutive_bonus`), this mirrors the scoring of
+/// the optimal fuzzy matcher.
+fn match_char(
+    scoring: &Scoring,
+    from: From,
+    score: i32,
+    consecutive_bonus: u16,
+    bonus: u16,
+) -> (i32, u16) {
+    let score_match = scoring.score_match as i32;
+    match from {
+        From::Start => (
+            (bonus * scoring.bonus_first_char_multiplier) as i32 + score_match,
+            bonus,
+        ),
+        From::Skipped => (score + bonus as i32 + score_match, bonus),
+        From::Matched => {
+            let mut consecutive_bonus = max(consecutive_bonus, scoring.bonus_consecutive);
+            if bonus >= scoring.bonus_boundary && bonus > consecutive_bonus {
+                consecutive_bonus = bonus
+            }
+            let score = score + max(consecutive_bonus, bonus) as i32 + score_match;
+            (score, consecutive_bonus)
+        }
+    }
+}
+
+/// Returns the best score for matching a haystack char with `bonus` after
+/// any of the states in `cell` (or the start of the needle, which receives
+/// `prefix_bonus`).
+fn extend(
+    scoring: &Scoring,
+    cell: Cell,
+    start: bool,
+    bonus: u16,
+    prefix_bonus: u16,
+) -> (From, i32, u16) {
+    if start {
+        let (score, consecutive_bonus) = match_char(scoring, From::Start, 0, 0, bonus);
+        return (From::Start, score + prefix_bonus as i32, consecutive_bonus);
+    }
+    let mut best = (From::Matched, NONE, 0);
+    if cell.matched > NONE {
+        let (score, consecutive_bonus) = match_char(
+            scoring,
+            From::Matched,
+            cell.matched,
+            cell.consecutive_bonus,
+            bonus,
+        );
+        best = (From::Matched, score, consecutive_bonus);
+    }
+    if cell.skipped > NONE {
+        let (score, consecutive_bonus) = match_char(scoring, From::Skipped, cell.skipped, 0, bonus);
+        if score > best.1 {
+            best = (From::Skipped, score, consecutive_bonus);
+        }
+    }
+    best
+}
+
+/// Returns the best state to continue from in `cell` without matching a
+/// char.
+fn best_from(cell: Cell, start: bool) -> (From, i32, u16) {
+    if start {
+        (From::Start, 0, 0)
+    } else if cell.matched >= cell.skipped {
+        (From::Matched, cell.matched, cell.consecutive_bonus)
+    } else {
+        (From::Skipped, cell.skipped, 0)
+    }
+}
+
+impl TypoBuf {
+    /// A Smith-Waterman style DP with an additional dimension for the
+    /// number of typos. The table is indexed by haystack position `j`, number
+    /// of typos `t` and needle prefix length `i`.
+    fn fuzzy_match<const INDICES: bool, H: Char + PartialEq<N>, N: Char>(
+        &mut self,
+        config: &Config,
+        haystack: &[H],
+        needle: &[N],
+        max_typos: usize,
+        indices: &mut Vec<u32>,
+    ) -> Option<u16> {
+        // cheap prefilter: every typo can account for at most one missing char
+        let mut missing = 0;
+        for &c in needle {
+            if !haystack.iter().any(|&h| h.normalize(config) == c) {
+                missing += 1;
+                if missing > max_typos {
+                    return None;
+                }
+            }
+        }
+
+        let scoring = &config.scoring;
+        let rows = needle.len() + 1;
+        let layer = (max_typos + 1) * rows;
+        if INDICES && (haystack.len() + 1) * layer > MAX_MATRIX_SIZE {
+            // the traces would be too large (like the matrix of the optimal
+            // fuzzy matcher) so the match is reported without indices
+            return self.fuzzy_match::<false, _, _>(config, haystack, needle, max_typos, indices);
+        }
+        let idx = |t: usize, i: usize| t * rows + i;
+        self.columns.clear();
+        self.columns.resize(3 * layer, EMPTY);
+        if INDICES {
+            self.traces.clear();
+            self.traces
+                .resize((haystack.len() + 1) * layer, Default::default());
+        }
+
+        // first column: nothing consumed, only insertions are possible
+        for t in 0..=max_typos {
+            let cell = &mut self.columns[idx(t, t)];
+            cell.skipped = -(t as i32) * scoring.penalty_typo as i32;
+            if INDICES {
+                let op = if t == 0 { Op::Start } else { Op::Insert };
+                self.traces[idx(t, t)].1 = Trace::new(op, From::Skipped);
+            }
+        }
+
+        let mut best = (NONE, 0, 0);
+        let mut prev_class = config.initial_char_class;
+        let mut prev_char = None;
+        let mut prev_bonus = 0;
+        for (j, &c) in haystack.iter().enumerate() {
+            let j = j + 1;
+            let (c, class) = c.char_class_and_normalize(config);
+            let bonus = config.bonus_for(prev_class, class);
+            prev_class = class;
+
+            let (before, rest) = self.columns.split_at_mut(layer * (j % 3));
+            let (current, after) = rest.split_at_mut(layer);
+            let (prev, prev2) = match j % 3 {
+                0 => (&after[layer..], &after[..layer]),
+                1 => (&before[..], &after[..]),
+                _ => (&before[layer..], &before[..layer]),
+            };
+            let traces = if INDICES {
+                &mut self.traces[j * layer..(j + 1) * layer]
+            } else {
+                &mut []
+            };
+            for t in 0..=max_typos {
+                for i in 0..rows {
+                    let mut cell = EMPTY;
+                    let mut trace = (Trace::default(), Trace::default());
+                    if i == 0 {
+                        if t == 0 {
+                            cell.skipped = 0;
+                            trace.1 = Trace::new(Op::Start, From::Start);
+                        }
+                        current[idx(t, i)] = cell;
+                        if INDICES {
+                            traces[idx(t, i)] = trace;
+                        }
+                        continue;
+                    }
+
+                    // skip the haystack char
+                    let p = prev[idx(t, i)];
+                    let gap_start = p.matched - scoring.penalty_gap_start as i32;
+                    let gap_extend = p.skipped - scoring.penalty_gap_extension as i32;
+                    if gap_start >= gap_extend {
+                        cell.skipped = gap_start;
+                        trace.1 = Trace::new(Op::Gap, From::Matched);
+                    } else {
+                        cell.skipped = gap_extend;
+                        trace.1 = Trace::new(Op::Gap, From::Skipped);
+                    }
+
+                    // match the haystack char
+                    let needle_char = needle[i - 1];
+                    if c == needle_char {
+                        let (from, score, consecutive_bonus) = extend(
+                            scoring,
+                            prev[idx(t, i - 1)],
+                            i == 1 && t == 0,
+                            bonus,
+                            prefix_bonus(config, j - 1),
+                        );
+                        if score > NONE {
+                            cell.matched = score;
+                            cell.consecutive_bonus = consecutive_bonus;
+                            trace.0 = Trace::new(Op::Match, from);
+                        }
+                    }
+
+                    if t != 0 {
+                        let penalty = scoring.penalty_typo as i32;
+                        // substitute the haystack char
+                        if c != needle_char {
+                            let (from, score, _) =
+                                best_from(prev[idx(t - 1, i - 1)], i == 1 && t == 1);
+                            if score - penalty > cell.matched {
+                                cell.matched = score - penalty;
+                                cell.consecutive_bonus = 0;
+                                trace.0 = Trace::new(Op::Substitute, from);
+                            }
+                        }
+
+                        // transposition of this and the previous haystack char
+                        if i >= 2
+                            && needle[i - 2] != needle_char
+                            && c == needle[i - 2]
+                            && matches!(prev_char, Some(prev_char) if prev_char == needle_char)
+                        {
+                            let (from, score, consecutive_bonus) = extend(
+                                scoring,
+                                prev2[idx(t - 1, i - 2)],
+                                i == 2 && t == 1,
+                                prev_bonus,
+                                prefix_bonus(config, j - 2),
+                            );
+                            if score > NONE {
+                                let (score, consecutive_bonus) = match_char(
+                                    scoring,
+                                    From::Matched,
+                                    score,
+                                    consecutive_bonus,
+                                    bonus,
+                                );
+                                if score - penalty > cell.matched {
+                                    cell.matched = score - penalty;
+                                    cell.consecutive_bonus = consecutive_bonus;
+                                    trace.0 = Trace::new(Op::Transpose, from);
+                                }
+                            }
+                        }
+
+                        // extra needle char
+                        let p = current[idx(t - 1, i - 1)];
+                        if p.matched - penalty > cell.matched {
+                            cell.matched = p.matched - penalty;
+                            cell.consecutive_bonus = p.consecutive_bonus;
+                            trace.0 = Trace::new(Op::Insert, From::Matched);
+                        }
+                        if p.skipped - penalty > cell.skipped {
+                            cell.skipped = p.skipped - penalty;
+                            trace.1 = Trace::new(Op::Insert, From::Skipped);
+                        }
+                    }
+
+                    cell.matched = cell.matched.max(NONE);
+                    cell.skipped = cell.skipped.max(NONE);
+                    current[idx(t, i)] = cell;
+                    if INDICES {
+                        traces[idx(t, i)] = trace;
+                    }
+                    if i == rows - 1 && cell.matched > best.0 {
+                        best = (cell.matched, t, j);
+                    }
+                }
+            }
+            prev_char = Some(c);
+            prev_bonus = bonus;
+        }
+
+        let (score, t, j) = best;
+        if score == NONE {
+            return None;
+        }
+        if INDICES {
+            self.reconstruct_path(layer, rows, t, j, indices);
+        }
+        Some(score.clamp(0, u16::MAX as i32) as u16)
+    }
+
+    fn reconstruct_path(
+        &self,
+        layer: usize,
+        rows: usize,
+        mut t: usize,
+        mut j: usize,
+        indices: &mut Vec<u32>,
+    ) {
+        let indices_start = indices.len();
+        let mut i = rows - 1;
+        let mut matched = true;
+        loop {
+            let (matched_trace, skipped_trace) = self.traces[j * layer + t * rows + i];
+            let trace = if matched {
+                matched_trace
+            } else {
+                skipped_trace
+            };
+            match trace.op() {
+                Op::Start => break,
+                Op::Match => {
+                    indices.push(j as u32 - 1);
+                    i -= 1;
+                    j -= 1;
+                }
+                Op::Substitute => {
+                    t -= 1;
+                    i -= 1;
+                    j -= 1;
+                }
+                Op::Transpose => {
+                    indices.push(j as u32 - 1);
+                    indices.push(j as u32 - 2);
+                    t -= 1;
+                    i -= 2;
+                    j -= 2;
+                }
+                Op::Insert => {
+                    t -= 1;
+                    i -= 1;
+                }
+                Op::Gap => j -= 1,
+            }
+            match trace.from() {
+                From::Start => break,
+                From::Matched => matched = true,
+                From::Skipped => matched = false,
+            }
+        }
+        indices[indices_start..].reverse();
+    }
+}
@@ -89,19 +89,26 @@ mod debug;
 mod exact;
 mod fuzzy_greedy;
 mod fuzzy_optimal;
+mod fuzzy_typos;
 mod matrix;
 pub mod pattern;
 mod prefilter;
+#[cfg(feature = "regex")]
+mod regex;
 mod score;
 mod utf32_str;
 
 #[cfg(test)]
 mod tests;
 
-pub use crate::config::Config;
+pub use crate::config::{Config, Delimiters, Scoring, ScoringError};
+use crate::utf32_str::ReplacedClusters;
 pub use crate::utf32_str::{Utf32Str, Utf32String};
 
+#[cfg(any(feature = "unicode-casefold", feature = "unicode-transliteration"))]
+use crate::chars::FoldedHaystack;
 use crate::chars::{AsciiChar, Char};
+use crate::fuzzy_typos::TypoBuf;
 use crate::matrix::MatrixSlab;
 
 /// A matcher engine that can execute (fuzzy) matches.
@@ -153,6 +160,18 @@ pub struct Matcher {
     #[allow(missing_docs)]
     pub config: Config,
     slab: MatrixSlab,
+    typo_buf: TypoBuf,
+    /// Scratch buffer to convert unicode haystacks to a `str` for regex matching.
+    #[cfg(feature = "regex")]
+    regex_buf: String,
+    /// Scratch buffer for haystacks and needles with multi char graphemes.
+    replaced_clusters: ReplacedClusters,
+    /// Scratch buffer for atoms with full case folding.
+    #[cfg(feature = "unicode-casefold")]
+    folded_haystack: FoldedHaystack,
+    /// Scratch buffer for the transliterated haystack.
+    #[cfg(feature = "unicode-transliteration")]
+    transliterated_haystack: FoldedHaystack,
 }
 
 // this is just here for convenience not sure if we should implement this
@@ -161,6 +180,14 @@ impl Clone for Matcher {
         Matcher {
             config: self.config.clone(),
             slab: MatrixSlab::new(),
+            typo_buf: TypoBuf::default(),
+            #[cfg(feature = "regex")]
+            regex_buf: String::new(),
+            replaced_clusters: ReplacedClusters::default(),
+            #[cfg(feature = "unicode-casefold")]
+            folded_haystack: FoldedHaystack::default(),
+            #[cfg(feature = "unicode-transliteration")]
+            transliterated_haystack: FoldedHaystack::default(),
         }
     }
 }
@@ -178,6 +205,14 @@ impl Default for Matcher {
         Matcher {
             config: Config::DEFAULT,
             slab: MatrixSlab::new(),
+            typo_buf: TypoBuf::default(),
+            #[cfg(feature = "regex")]
+            regex_buf: String::new(),
+            replaced_clusters: ReplacedClusters::default(),
+            #[cfg(feature = "unicode-casefold")]
+            folded_haystack: FoldedHaystack::default(),
+            #[cfg(feature = "unicode-transliteration")]
+            transliterated_haystack: FoldedHaystack::default(),
         }
     }
 }
@@ -190,9 +225,76 @@ impl Matcher {
         Self {
             config,
             slab: MatrixSlab::new(),
+            typo_buf: TypoBuf::default(),
+            #[cfg(feature = "regex")]
+            regex_buf: String::new(),
+            replaced_clusters: ReplacedClusters::default(),
+            #[cfg(feature = "unicode-casefold")]
+            folded_haystack: FoldedHaystack::default(),
+            #[cfg(feature = "unicode-transliteration")]
+            transliterated_haystack: FoldedHaystack::default(),
         }
     }
 
+    /// Runs `matcher` on `haystack` and `needle` with their multi char
+    /// graphemes replaced by single chars (see [`Utf32Str::Clusters`]). The
+    /// indices computed by `matcher` don't need to be mapped since every
+    /// grapheme is replaced by exactly one char. Returns `None` if neither of
+    /// them is a `Clusters` string.
+    fn replaced_clusters(
+        &mut self,
+        haystack: Utf32Str<'_>,
+        needle: Utf32Str<'_>,
+        indices: &mut Vec<u32>,
+        matcher: impl FnOnce(&mut Self, Utf32Str<'_>, Utf32Str<'_>, &mut Vec<u32>) -> Option<u16>,
+    ) -> Option<Option<u16>> {
+        if !matches!(haystack, Utf32Str::Clusters { .. })
+            && !matches!(needle, Utf32Str::Clusters { .. })
+        {
+            return None;
+        }
+        let mut replaced = std::mem::take(&mut self.replaced_clusters);
+        replaced.replace(haystack, needle);
+        let score = matcher(
+            self,
+            Utf32Str::Unicode(&replaced.haystack),
+            Utf32Str::Unicode(&replaced.needle),
+            indices,
+        );
+        self.replaced_clusters = replaced;
+        Some(score)
+    }
+
+    /// Runs `matcher` on the transliterated `haystack` if
+    /// [`transliterate`](Config::transliterate) is enabled and any char of
+    /// `haystack` is transliterated. The indices computed by `matcher` are
+    /// mapped back to the original haystack. Returns `None` if the haystack
+    /// was not transliterated.
+    #[cfg(feature = "unicode-transliteration")]
+    fn transliterated(
+        &mut self,
+        haystack: Utf32Str<'_>,
+        indices: &mut Vec<u32>,
+        matcher: impl FnOnce(&mut Self, Utf32Str<'_>, &mut Vec<u32>) -> Option<u16>,
+    ) -> Option<Option<u16>> {
+        let (true, Utf32Str::Unicode(haystack)) = (self.config.transliterate, haystack) else {
+            return None;
+        };
+        let mut transliterated = std::mem::take(&mut self.transliterated_haystack);
+        let score = transliterated
+            .fold(haystack, chars::transliterate)
+            .then(|| {
+                self.config.transliterate = false;
+                let start = indices.len();
+                let score = matcher(self, Utf32Str::Unicode(&transliterated.chars), indices);
+                transliterated.map_indices(indices, start);
+                self.config.transliterate = true;
+                score
+            });
+        self.transliterated_haystack = transliterated;
+        score
+    }
+
     /// Find the fuzzy match with the highest score in the `haystack`.
     ///
     /// This functions has `O(mn)` time complexity for short inputs.
@@ -202,8 +304,25 @@ impl Matcher {
     ///
     /// See the [matcher documentation](crate::Matcher) for more details.
     pub fn fuzzy_match(&mut self, haystack: Utf32Str<'_>, needle: Utf32Str<'_>) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            &mut Vec::new(),
+            |matcher, haystack, needle, _| matcher.fuzzy_match(haystack, needle),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) =
+            self.transliterated(haystack, &mut Vec::new(), |matcher, haystack, _| {
+                matcher.fuzzy_match(haystack, needle)
+            })
+        {
+            return score;
+        }
         assert!(haystack.len() <= u32::MAX as usize);
         self.fuzzy_matcher_impl::<false>(haystack, needle, &mut Vec::new())
+            .or_else(|| self.fuzzy_match_typos::<false>(haystack, needle, &mut Vec::new()))
     }
 
     /// Find the fuzzy match with the highest score in the `haystack` and
@@ -221,8 +340,27 @@ impl Matcher {
         needle: Utf32Str<'_>,
         indices: &mut Vec<u32>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            indices,
+            |matcher, haystack, needle, indices| matcher.fuzzy_indices(haystack, needle, indices),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) = self.transliterated(haystack, indices, |matcher, haystack, indices| {
+            matcher.fuzzy_indices(haystack, needle, indices)
+        }) {
+            return score;
+        }
         assert!(haystack.len() <= u32::MAX as usize);
+        let indices_len = indices.len();
         self.fuzzy_matcher_impl::<true>(haystack, needle, indices)
+            .or_else(|| {
+                indices.truncate(indices_len);
+                self.fuzzy_match_typos::<true>(haystack, needle, indices)
+            })
     }
 
     fn fuzzy_matcher_impl<const INDICES: bool>(
@@ -325,6 +463,9 @@ impl Matcher {
                     indices,
                 )
             }
+            (Utf32Str::Clusters { .. }, _) | (_, Utf32Str::Clusters { .. }) => {
+                unreachable!("multi char graphemes are replaced before matching")
+            }
         }
     }
 
@@ -340,6 +481,22 @@ impl Matcher {
         haystack: Utf32Str<'_>,
         needle: Utf32Str<'_>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            &mut Vec::new(),
+            |matcher, haystack, needle, _| matcher.fuzzy_match_greedy(haystack, needle),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) =
+            self.transliterated(haystack, &mut Vec::new(), |matcher, haystack, _| {
+                matcher.fuzzy_match_greedy(haystack, needle)
+            })
+        {
+            return score;
+        }
         assert!(haystack.len() <= u32::MAX as usize);
         self.fuzzy_match_greedy_impl::<false>(haystack, needle, &mut Vec::new())
     }
@@ -357,6 +514,22 @@ impl Matcher {
         needle: Utf32Str<'_>,
         indices: &mut Vec<u32>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            indices,
+            |matcher, haystack, needle, indices| {
+                matcher.fuzzy_indices_greedy(haystack, needle, indices)
+            },
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) = self.transliterated(haystack, indices, |matcher, haystack, indices| {
+            matcher.fuzzy_indices_greedy(haystack, needle, indices)
+        }) {
+            return score;
+        }
         assert!(haystack.len() <= u32::MAX as usize);
         self.fuzzy_match_greedy_impl::<true>(haystack, needle, indices)
     }
@@ -425,6 +598,9 @@ impl Matcher {
                     indices,
                 )
             }
+            (Utf32Str::Clusters { .. }, _) | (_, Utf32Str::Clusters { .. }) => {
+                unreachable!("multi char graphemes are replaced before matching")
+            }
         }
     }
 
@@ -440,6 +616,22 @@ impl Matcher {
         haystack: Utf32Str<'_>,
         needle_: Utf32Str<'_>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle_,
+            &mut Vec::new(),
+            |matcher, haystack, needle, _| matcher.substring_match(haystack, needle),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) =
+            self.transliterated(haystack, &mut Vec::new(), |matcher, haystack, _| {
+                matcher.substring_match(haystack, needle_)
+            })
+        {
+            return score;
+        }
         self.substring_match_impl::<false>(haystack, needle_, &mut Vec::new())
     }
 
@@ -457,6 +649,22 @@ impl Matcher {
         needle_: Utf32Str<'_>,
         indices: &mut Vec<u32>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle_,
+            indices,
+            |matcher, haystack, needle, indices| {
+                matcher.substring_indices(haystack, needle, indices)
+            },
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) = self.transliterated(haystack, indices, |matcher, haystack, indices| {
+            matcher.substring_indices(haystack, needle_, indices)
+        }) {
+            return score;
+        }
         self.substring_match_impl::<true>(haystack, needle_, indices)
     }
 
@@ -520,6 +728,9 @@ impl Matcher {
                 let (start, _) = self.prefilter_non_ascii(haystack, needle_, false)?;
                 self.substring_match_non_ascii::<INDICES, _>(haystack, needle, start, indices)
             }
+            (Utf32Str::Clusters { .. }, _) | (_, Utf32Str::Clusters { .. }) => {
+                unreachable!("multi char graphemes are replaced before matching")
+            }
         }
     }
 
@@ -529,6 +740,22 @@ impl Matcher {
     ///
     /// See the [matcher documentation](crate::Matcher) for more details.
     pub fn exact_match(&mut self, haystack: Utf32Str<'_>, needle: Utf32Str<'_>) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            &mut Vec::new(),
+            |matcher, haystack, needle, _| matcher.exact_match(haystack, needle),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) =
+            self.transliterated(haystack, &mut Vec::new(), |matcher, haystack, _| {
+                matcher.exact_match(haystack, needle)
+            })
+        {
+            return score;
+        }
         if needle.is_empty() {
             return Some(0);
         }
@@ -564,6 +791,20 @@ impl Matcher {
         needle: Utf32Str<'_>,
         indices: &mut Vec<u32>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            indices,
+            |matcher, haystack, needle, indices| matcher.exact_indices(haystack, needle, indices),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) = self.transliterated(haystack, indices, |matcher, haystack, indices| {
+            matcher.exact_indices(haystack, needle, indices)
+        }) {
+            return score;
+        }
         if needle.is_empty() {
             return Some(0);
         }
@@ -594,6 +835,22 @@ impl Matcher {
     ///
     /// See the [matcher documentation](crate::Matcher) for more details.
     pub fn prefix_match(&mut self, haystack: Utf32Str<'_>, needle: Utf32Str<'_>) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            &mut Vec::new(),
+            |matcher, haystack, needle, _| matcher.prefix_match(haystack, needle),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) =
+            self.transliterated(haystack, &mut Vec::new(), |matcher, haystack, _| {
+                matcher.prefix_match(haystack, needle)
+            })
+        {
+            return score;
+        }
         if needle.is_empty() {
             return Some(0);
         }
@@ -625,6 +882,20 @@ impl Matcher {
         needle: Utf32Str<'_>,
         indices: &mut Vec<u32>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            indices,
+            |matcher, haystack, needle, indices| matcher.prefix_indices(haystack, needle, indices),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) = self.transliterated(haystack, indices, |matcher, haystack, indices| {
+            matcher.prefix_indices(haystack, needle, indices)
+        }) {
+            return score;
+        }
         if needle.is_empty() {
             return Some(0);
         }
@@ -651,6 +922,22 @@ impl Matcher {
     ///
     /// See the [matcher documentation](crate::Matcher) for more details.
     pub fn postfix_match(&mut self, haystack: Utf32Str<'_>, needle: Utf32Str<'_>) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            &mut Vec::new(),
+            |matcher, haystack, needle, _| matcher.postfix_match(haystack, needle),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) =
+            self.transliterated(haystack, &mut Vec::new(), |matcher, haystack, _| {
+                matcher.postfix_match(haystack, needle)
+            })
+        {
+            return score;
+        }
         if needle.is_empty() {
             return Some(0);
         }
@@ -682,6 +969,20 @@ impl Matcher {
         needle: Utf32Str<'_>,
         indices: &mut Vec<u32>,
     ) -> Option<u16> {
+        if let Some(score) = self.replaced_clusters(
+            haystack,
+            needle,
+            indices,
+            |matcher, haystack, needle, indices| matcher.postfix_indices(haystack, needle, indices),
+        ) {
+            return score;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        if let Some(score) = self.transliterated(haystack, indices, |matcher, haystack, indices| {
+            matcher.postfix_indices(haystack, needle, indices)
+        }) {
+            return score;
+        }
         if needle.is_empty() {
             return Some(0);
         }
@@ -702,6 +1003,38 @@ impl Matcher {
         }
     }
 
+    /// Finds the match of `regex` in `haystack` with the highest score. Each
+    /// match of the regex is scored like a
+    /// [substring match](crate::Matcher::substring_match) of the matched
+    /// text. Case matching is entirely controlled by the `regex` so
+    /// [`ignore_case`](Config::ignore_case) and [`normalize`](Config::normalize)
+    /// are ignored.
+    ///
+    /// Note that the regex is matched against the chars stored in the
+    /// `haystack`, which only contains the first char of each grapheme.
+    ///
+    /// See the [matcher documentation](crate::Matcher) for more details.
+    #[cfg(feature = "regex")]
+    pub fn regex_match(&mut self, haystack: Utf32Str<'_>, regex: &::regex::Regex) -> Option<u16> {
+        self.regex_match_impl::<false>(haystack, regex, &mut Vec::new())
+    }
+
+    /// Finds the match of `regex` in `haystack` with the highest score and
+    /// computes its indices (see [`regex_match`](crate::Matcher::regex_match)).
+    /// All chars of the matched text are reported as indices, a regex that
+    /// matches the empty string produces no indices.
+    ///
+    /// See the [matcher documentation](crate::Matcher) for more details.
+    #[cfg(feature = "regex")]
+    pub fn regex_indices(
+        &mut self,
+        haystack: Utf32Str<'_>,
+        regex: &::regex::Regex,
+        indices: &mut Vec<u32>,
+    ) -> Option<u16> {
+        self.regex_match_impl::<true>(haystack, regex, indices)
+    }
+
     fn exact_match_impl<const INDICES: bool>(
         &mut self,
         haystack: Utf32Str<'_>,
@@ -774,6 +1107,9 @@ impl Matcher {
                 }
                 self.calculate_score::<INDICES, _, _>(haystack, needle, start, end, indices)
             }
+            (Utf32Str::Clusters { .. }, _) | (_, Utf32Str::Clusters { .. }) => {
+                unreachable!("multi char graphemes are replaced before matching")
+            }
         };
         Some(score)
     }
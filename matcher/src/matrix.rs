@@ -6,7 +6,7 @@ use std::ptr::{slice_from_raw_parts_mut, NonNull};
 
 use crate::chars::Char;
 
-const MAX_MATRIX_SIZE: usize = 100 * 1024; // 100*1024 = 100KB
+pub(crate) const MAX_MATRIX_SIZE: usize = 100 * 1024; // 100*1024 = 100KB
 
 // these two aren't hard maxima, instead we simply allow whatever will fit into memory
 const MAX_HAYSTACK_LEN: usize = 2048; // 64KB
@@ -2,6 +2,8 @@
 
 use std::cmp::Reverse;
 
+#[cfg(feature = "regex")]
+use crate::regex::CompiledRegex;
 use crate::{chars, Matcher, Utf32Str};
 
 #[cfg(test)]
@@ -24,6 +26,16 @@ pub enum CaseMatching {
     #[default]
     #[cfg(feature = "unicode-casefold")]
     Smart,
+    /// Like [`Ignore`](CaseMatching::Ignore) but uses full unicode case
+    /// folding, so characters also match their multi character case folding
+    /// (`ß == ss`, `ﬁ == fi`, `İ == i`). Match indices still refer to the
+    /// characters of the haystack (see [`chars::case_fold_full`]).
+    #[cfg(feature = "unicode-casefold")]
+    IgnoreFull,
+    /// Like [`Smart`](CaseMatching::Smart) but uses full unicode case folding
+    /// if the case is ignored (see [`IgnoreFull`](CaseMatching::IgnoreFull)).
+    #[cfg(feature = "unicode-casefold")]
+    SmartFull,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -38,6 +50,64 @@ pub enum Normalization {
     #[default]
     #[cfg(feature = "unicode-normalization")]
     Smart,
+    /// Like [`Smart`](Normalization::Smart) but additionally normalizes
+    /// full-width and half-width forms to their regular width (`Ａ`
+    /// and `ｶ` match `A` and `カ`). Width normalization is disabled if any
+    /// character in a pattern atom is a full-width or half-width form (see
+    /// [`chars::normalize_width`]).
+    #[cfg(feature = "unicode-width-normalization")]
+    SmartWidth,
+    /// Like [`SmartWidth`](Normalization::SmartWidth) but additionally
+    /// normalizes katakana to hiragana so that a hiragana pattern atom also
+    /// matches katakana (`か` matches `カ` but `カ` does not match `か`, see
+    /// [`chars::normalize_kana`]).
+    #[cfg(feature = "unicode-kana-normalization")]
+    SmartKana,
+    /// Like [`Smart`](Normalization::Smart) but additionally transliterates
+    /// Cyrillic and Greek characters of the haystack to Latin so that a Latin
+    /// pattern atom matches them (`privet` matches `привет`). Transliteration
+    /// is disabled if any character in a pattern atom would be transliterated
+    /// (see [`chars::transliterate`]). Match indices still refer to the
+    /// characters of the haystack.
+    #[cfg(feature = "unicode-transliteration")]
+    SmartTransliterate,
+    /// Like [`Smart`](Normalization::Smart) but keeps graphemes that consist
+    /// of multiple characters intact instead of only using their first
+    /// character, so `👍🏽` only matches `👍🏽` (see [`Utf32Str::Clusters`]).
+    /// Haystacks must be created with [`Utf32String::from_grapheme_clusters`]
+    /// or [`Utf32Str::new_grapheme_clusters`].
+    #[cfg(all(feature = "unicode-normalization", feature = "unicode-segmentation"))]
+    SmartClusters,
+}
+
+impl Normalization {
+    #[cfg(feature = "unicode-width-normalization")]
+    fn normalizes_width(self) -> bool {
+        match self {
+            Normalization::SmartWidth => true,
+            #[cfg(feature = "unicode-kana-normalization")]
+            Normalization::SmartKana => true,
+            _ => false,
+        }
+    }
+
+    #[cfg(feature = "unicode-kana-normalization")]
+    fn normalizes_kana(self) -> bool {
+        self == Normalization::SmartKana
+    }
+
+    #[cfg(feature = "unicode-transliteration")]
+    fn transliterates(self) -> bool {
+        self == Normalization::SmartTransliterate
+    }
+
+    fn keeps_clusters(self) -> bool {
+        #[cfg(all(feature = "unicode-normalization", feature = "unicode-segmentation"))]
+        let res = self == Normalization::SmartClusters;
+        #[cfg(not(all(feature = "unicode-normalization", feature = "unicode-segmentation")))]
+        let res = false;
+        res
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -75,6 +145,18 @@ pub enum AtomKind {
     ///
     /// See also [`Matcher::exact_match`](crate::Matcher::exact_match).
     Exact,
+    /// The needle is a regular expression that must match (a part of) the
+    /// haystack. This atom kind is parsed from the following syntax: `/foo/`
+    /// and `!/foo/` (negated).
+    ///
+    /// Case matching follows the [`CaseMatching`] of the atom, escape
+    /// sequences like `\D` don't count as uppercase characters. No unicode
+    /// normalization is performed. A needle that is not a valid regex never
+    /// matches.
+    ///
+    /// See also [`Matcher::regex_match`](crate::Matcher::regex_match).
+    #[cfg(feature = "regex")]
+    Regex,
 }
 
 /// A single pattern component that is matched with a single [`Matcher`] function
@@ -89,6 +171,18 @@ pub struct Atom {
     needle: Utf32String,
     ignore_case: bool,
     normalize: bool,
+    #[cfg(feature = "unicode-width-normalization")]
+    normalize_width: bool,
+    #[cfg(feature = "unicode-kana-normalization")]
+    normalize_kana: bool,
+    #[cfg(feature = "unicode-transliteration")]
+    transliterate: bool,
+    /// Whether haystacks are folded with full unicode case folding.
+    fold_full: bool,
+    alternative: bool,
+    /// The compiled regex for [`AtomKind::Regex`], `None` if it is invalid.
+    #[cfg(feature = "regex")]
+    regex: Option<CompiledRegex>,
 }
 
 impl Atom {
@@ -113,16 +207,26 @@ impl Atom {
         escape_whitespace: bool,
         append_dollar: bool,
     ) -> Atom {
+        #[cfg(feature = "regex")]
+        if kind == AtomKind::Regex {
+            return Atom::new_regex(needle, case, escape_whitespace);
+        }
         let mut ignore_case;
         let mut normalize;
         #[cfg(feature = "unicode-normalization")]
         {
-            normalize = matches!(normalization, Normalization::Smart);
+            normalize = normalization != Normalization::Never;
         }
         #[cfg(not(feature = "unicode-normalization"))]
         {
             normalize = false;
         }
+        #[cfg(feature = "unicode-width-normalization")]
+        let mut normalize_width = normalization.normalizes_width();
+        #[cfg(feature = "unicode-kana-normalization")]
+        let mut normalize_kana = normalization.normalizes_kana();
+        #[cfg(feature = "unicode-transliteration")]
+        let mut transliterate = normalization.transliterates();
         let needle = if needle.is_ascii() {
             let mut needle = if escape_whitespace {
                 if let Some((start, rem)) = needle.split_once("\\ ") {
@@ -141,12 +245,12 @@ impl Atom {
 
             match case {
                 #[cfg(feature = "unicode-casefold")]
-                CaseMatching::Ignore => {
+                CaseMatching::Ignore | CaseMatching::IgnoreFull => {
                     ignore_case = true;
                     needle.make_ascii_lowercase()
                 }
                 #[cfg(feature = "unicode-casefold")]
-                CaseMatching::Smart => {
+                CaseMatching::Smart | CaseMatching::SmartFull => {
                     ignore_case = !needle.bytes().any(|b| b.is_ascii_uppercase())
                 }
                 CaseMatching::Respect => ignore_case = false,
@@ -157,9 +261,11 @@ impl Atom {
             Utf32String::Ascii(needle.into_boxed_str())
         } else {
             let mut needle_ = Vec::with_capacity(needle.len());
+            // multi char graphemes (and their index) that are kept intact
+            let mut clusters = Vec::new();
             #[cfg(feature = "unicode-casefold")]
             {
-                ignore_case = matches!(case, CaseMatching::Ignore | CaseMatching::Smart);
+                ignore_case = case != CaseMatching::Respect;
             }
             #[cfg(not(feature = "unicode-casefold"))]
             {
@@ -167,11 +273,11 @@ impl Atom {
             }
             #[cfg(feature = "unicode-normalization")]
             {
-                normalize = matches!(normalization, Normalization::Smart);
+                normalize = normalization != Normalization::Never;
             }
             if escape_whitespace {
                 let mut saw_backslash = false;
-                for mut c in chars::graphemes(needle) {
+                for (mut c, cluster) in needle_graphemes(needle, normalization.keeps_clusters()) {
                     if saw_backslash {
                         if c == ' ' {
                             needle_.push(' ');
@@ -181,42 +287,83 @@ impl Atom {
                             needle_.push('\\');
                         }
                     }
+                    if let Some(cluster) = cluster {
+                        // case matching and normalization don't apply to clusters
+                        saw_backslash = false;
+                        clusters.push((needle_.len(), cluster));
+                        needle_.push(c);
+                        continue;
+                    }
                     saw_backslash = c == '\\';
                     match case {
                         #[cfg(feature = "unicode-casefold")]
-                        CaseMatching::Ignore => c = chars::to_lower_case(c),
+                        CaseMatching::Ignore | CaseMatching::IgnoreFull => {
+                            c = chars::to_lower_case(c)
+                        }
                         #[cfg(feature = "unicode-casefold")]
-                        CaseMatching::Smart => {
+                        CaseMatching::Smart | CaseMatching::SmartFull => {
                             ignore_case = ignore_case && !chars::is_upper_case(c)
                         }
                         CaseMatching::Respect => (),
                     }
                     match normalization {
+                        Normalization::Never => (),
                         #[cfg(feature = "unicode-normalization")]
-                        Normalization::Smart => {
+                        _ => {
                             normalize = normalize && chars::normalize(c) == c;
+                            #[cfg(feature = "unicode-width-normalization")]
+                            {
+                                normalize_width = normalize_width && chars::normalize_width(c) == c;
+                            }
+                            #[cfg(feature = "unicode-kana-normalization")]
+                            {
+                                normalize_kana = normalize_kana && chars::normalize_kana(c) == c;
+                            }
+                            #[cfg(feature = "unicode-transliteration")]
+                            {
+                                transliterate = transliterate && chars::transliterate(c).is_none();
+                            }
                         }
-                        Normalization::Never => (),
                     }
                     needle_.push(c);
                 }
             } else {
-                let chars = chars::graphemes(needle).map(|mut c| {
+                let graphemes = needle_graphemes(needle, normalization.keeps_clusters());
+                let chars = graphemes.enumerate().map(|(i, (mut c, cluster))| {
+                    if let Some(cluster) = cluster {
+                        // case matching and normalization don't apply to clusters
+                        clusters.push((i, cluster));
+                        return c;
+                    }
                     match case {
                         #[cfg(feature = "unicode-casefold")]
-                        CaseMatching::Ignore => c = chars::to_lower_case(c),
+                        CaseMatching::Ignore | CaseMatching::IgnoreFull => {
+                            c = chars::to_lower_case(c)
+                        }
                         #[cfg(feature = "unicode-casefold")]
-                        CaseMatching::Smart => {
+                        CaseMatching::Smart | CaseMatching::SmartFull => {
                             ignore_case = ignore_case && !chars::is_upper_case(c);
                         }
                         CaseMatching::Respect => (),
                     }
                     match normalization {
+                        Normalization::Never => (),
                         #[cfg(feature = "unicode-normalization")]
-                        Normalization::Smart => {
+                        _ => {
                             normalize = normalize && chars::normalize(c) == c;
+                            #[cfg(feature = "unicode-width-normalization")]
+                            {
+                                normalize_width = normalize_width && chars::normalize_width(c) == c;
+                            }
+                            #[cfg(feature = "unicode-kana-normalization")]
+                            {
+                                normalize_kana = normalize_kana && chars::normalize_kana(c) == c;
+                            }
+                            #[cfg(feature = "unicode-transliteration")]
+                            {
+                                transliterate = transliterate && chars::transliterate(c).is_none();
+                            }
                         }
-                        Normalization::Never => (),
                     }
                     c
                 });
@@ -225,7 +372,21 @@ impl Atom {
             if append_dollar {
                 needle_.push('$');
             }
-            Utf32String::Unicode(needle_.into_boxed_slice())
+            if clusters.is_empty() {
+                Utf32String::Unicode(needle_.into_boxed_slice())
+            } else {
+                cluster_needle(&needle_, &clusters)
+            }
+        };
+        #[cfg(feature = "unicode-casefold")]
+        let fold_full =
+            ignore_case && matches!(case, CaseMatching::IgnoreFull | CaseMatching::SmartFull);
+        #[cfg(not(feature = "unicode-casefold"))]
+        let fold_full = false;
+        #[cfg(feature = "unicode-casefold")]
+        let needle = match needle {
+            Utf32String::Unicode(needle) if fold_full => fold_needle(&needle),
+            needle => needle,
         };
         Atom {
             kind,
@@ -233,6 +394,61 @@ impl Atom {
             negative: false,
             ignore_case,
             normalize,
+            #[cfg(feature = "unicode-width-normalization")]
+            normalize_width,
+            #[cfg(feature = "unicode-kana-normalization")]
+            normalize_kana,
+            #[cfg(feature = "unicode-transliteration")]
+            transliterate,
+            fold_full,
+            alternative: false,
+            #[cfg(feature = "regex")]
+            regex: None,
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    fn new_regex(source: &str, case: CaseMatching, escape_whitespace: bool) -> Atom {
+        let source = if escape_whitespace {
+            source.replace("\\ ", " ")
+        } else {
+            source.to_owned()
+        };
+        let ignore_case = match case {
+            #[cfg(feature = "unicode-casefold")]
+            CaseMatching::Ignore | CaseMatching::IgnoreFull => true,
+            #[cfg(feature = "unicode-casefold")]
+            CaseMatching::Smart | CaseMatching::SmartFull => {
+                // uppercase escape sequences (`\D`, `\W`, ...) are not literal characters
+                let mut escaped = false;
+                !source.chars().any(|c| {
+                    let upper = !escaped && c.is_uppercase();
+                    escaped = !escaped && c == '\\';
+                    upper
+                })
+            }
+            CaseMatching::Respect => false,
+        };
+        let regex = regex::RegexBuilder::new(&source)
+            .case_insensitive(ignore_case)
+            .build()
+            .ok()
+            .map(CompiledRegex);
+        Atom {
+            kind: AtomKind::Regex,
+            needle: Utf32String::from(&*source),
+            negative: false,
+            ignore_case,
+            normalize: false,
+            #[cfg(feature = "unicode-width-normalization")]
+            normalize_width: false,
+            #[cfg(feature = "unicode-kana-normalization")]
+            normalize_kana: false,
+            #[cfg(feature = "unicode-transliteration")]
+            transliterate: false,
+            fold_full: false,
+            alternative: false,
+            regex,
         }
     }
 
@@ -253,6 +469,17 @@ impl Atom {
             _ => false,
         };
 
+        #[cfg(feature = "regex")]
+        match atom.as_bytes() {
+            [b'/', .., b'/'] => {
+                let mut pattern = Atom::new_regex(&atom[1..atom.len() - 1], case, true);
+                pattern.negative = invert;
+                return pattern;
+            }
+            [b'\\', b'/', ..] => atom = &atom[1..],
+            _ => (),
+        }
+
         let mut kind = match atom.as_bytes() {
             [b'^', ..] => {
                 atom = &atom[1..];
@@ -302,14 +529,43 @@ impl Atom {
     /// *Note:*  The `ignore_case` setting is overwritten to match the casing of
     /// each pattern atom.
     pub fn score(&self, haystack: Utf32Str<'_>, matcher: &mut Matcher) -> Option<u16> {
+        #[cfg(feature = "unicode-casefold")]
+        if let (true, Utf32Str::Unicode(chars)) = (self.fold_full, haystack) {
+            let mut folded = std::mem::take(&mut matcher.folded_haystack);
+            let score = if folded.fold(chars, chars::case_fold_full) {
+                self.score_impl(Utf32Str::Unicode(&folded.chars), matcher)
+            } else {
+                self.score_impl(haystack, matcher)
+            };
+            matcher.folded_haystack = folded;
+            return score;
+        }
+        self.score_impl(haystack, matcher)
+    }
+
+    fn score_impl(&self, haystack: Utf32Str<'_>, matcher: &mut Matcher) -> Option<u16> {
         matcher.config.ignore_case = self.ignore_case;
         matcher.config.normalize = self.normalize;
+        #[cfg(feature = "unicode-width-normalization")]
+        {
+            matcher.config.normalize_width = self.normalize_width;
+        }
+        #[cfg(feature = "unicode-kana-normalization")]
+        {
+            matcher.config.normalize_kana = self.normalize_kana;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        {
+            matcher.config.transliterate = self.transliterate;
+        }
         let pattern_score = match self.kind {
             AtomKind::Exact => matcher.exact_match(haystack, self.needle.slice(..)),
             AtomKind::Fuzzy => matcher.fuzzy_match(haystack, self.needle.slice(..)),
             AtomKind::Substring => matcher.substring_match(haystack, self.needle.slice(..)),
             AtomKind::Prefix => matcher.prefix_match(haystack, self.needle.slice(..)),
             AtomKind::Postfix => matcher.postfix_match(haystack, self.needle.slice(..)),
+            #[cfg(feature = "regex")]
+            AtomKind::Regex => self.regex_match(haystack, matcher),
         };
         if self.negative {
             if pattern_score.is_some() {
@@ -335,9 +591,44 @@ impl Atom {
         haystack: Utf32Str<'_>,
         matcher: &mut Matcher,
         indices: &mut Vec<u32>,
+    ) -> Option<u16> {
+        #[cfg(feature = "unicode-casefold")]
+        if let (true, Utf32Str::Unicode(chars)) = (self.fold_full, haystack) {
+            let mut folded = std::mem::take(&mut matcher.folded_haystack);
+            let score = if folded.fold(chars, chars::case_fold_full) {
+                let start = indices.len();
+                let score = self.indices_impl(Utf32Str::Unicode(&folded.chars), matcher, indices);
+                folded.map_indices(indices, start);
+                score
+            } else {
+                self.indices_impl(haystack, matcher, indices)
+            };
+            matcher.folded_haystack = folded;
+            return score;
+        }
+        self.indices_impl(haystack, matcher, indices)
+    }
+
+    fn indices_impl(
+        &self,
+        haystack: Utf32Str<'_>,
+        matcher: &mut Matcher,
+        indices: &mut Vec<u32>,
     ) -> Option<u16> {
         matcher.config.ignore_case = self.ignore_case;
         matcher.config.normalize = self.normalize;
+        #[cfg(feature = "unicode-width-normalization")]
+        {
+            matcher.config.normalize_width = self.normalize_width;
+        }
+        #[cfg(feature = "unicode-kana-normalization")]
+        {
+            matcher.config.normalize_kana = self.normalize_kana;
+        }
+        #[cfg(feature = "unicode-transliteration")]
+        {
+            matcher.config.transliterate = self.transliterate;
+        }
         if self.negative {
             let pattern_score = match self.kind {
                 AtomKind::Exact => matcher.exact_match(haystack, self.needle.slice(..)),
@@ -345,6 +636,8 @@ impl Atom {
                 AtomKind::Substring => matcher.substring_match(haystack, self.needle.slice(..)),
                 AtomKind::Prefix => matcher.prefix_match(haystack, self.needle.slice(..)),
                 AtomKind::Postfix => matcher.postfix_match(haystack, self.needle.slice(..)),
+                #[cfg(feature = "regex")]
+                AtomKind::Regex => self.regex_match(haystack, matcher),
             };
             pattern_score.is_none().then_some(0)
         } else {
@@ -360,10 +653,27 @@ impl Atom {
                 AtomKind::Postfix => {
                     matcher.postfix_indices(haystack, self.needle.slice(..), indices)
                 }
+                #[cfg(feature = "regex")]
+                AtomKind::Regex => {
+                    let regex = &self.regex.as_ref()?.0;
+                    matcher.regex_indices(haystack, regex, indices)
+                }
             }
         }
     }
 
+    #[cfg(feature = "regex")]
+    fn regex_match(&self, haystack: Utf32Str<'_>, matcher: &mut Matcher) -> Option<u16> {
+        matcher.regex_match(haystack, &self.regex.as_ref()?.0)
+    }
+
+    /// Whether this atom is an alternative to the preceding atom of a
+    /// [`Pattern`] (separated by `|`). Consecutive alternatives form a group
+    /// that matches if any of its atoms match.
+    pub fn is_alternative(&self) -> bool {
+        self.alternative
+    }
+
     /// Returns the needle text that is passed to the matcher. All indices
     /// produced by the `indices` functions produce char indices used to index
     /// this text
@@ -399,6 +709,67 @@ impl Atom {
     }
 }
 
+/// Replaces all chars of a (case folded) needle with their
+/// [full case folding](chars::case_fold_full).
+#[cfg(feature = "unicode-casefold")]
+fn fold_needle(needle: &[char]) -> Utf32String {
+    let mut folded = String::with_capacity(needle.len());
+    for &c in needle {
+        match chars::case_fold_full(c) {
+            Some(c) => folded.push_str(c),
+            None => folded.push(c),
+        }
+    }
+    if folded.is_ascii() {
+        Utf32String::Ascii(folded.into_boxed_str())
+    } else {
+        Utf32String::Unicode(folded.chars().collect())
+    }
+}
+
+/// Returns the first char of each grapheme of `needle` and the grapheme
+/// itself if it consists of multiple chars and `keep_clusters` is set.
+fn needle_graphemes(
+    needle: &str,
+    keep_clusters: bool,
+) -> impl Iterator<Item = (char, Option<&str>)> + '_ {
+    #[cfg(feature = "unicode-segmentation")]
+    let res = chars::grapheme_clusters(needle).map(move |grapheme| {
+        let mut chars = grapheme.chars();
+        let c = chars.next().expect("graphemes must be non-empty");
+        (
+            c,
+            (keep_clusters && chars.next().is_some()).then_some(grapheme),
+        )
+    });
+    #[cfg(not(feature = "unicode-segmentation"))]
+    let res = {
+        let _ = keep_clusters;
+        chars::graphemes(needle).map(|c| (c, None))
+    };
+    res
+}
+
+/// Creates a `Clusters` needle from the first chars of its graphemes and the
+/// graphemes that consist of multiple chars (with their index).
+fn cluster_needle(needle: &[char], clusters: &[(usize, &str)]) -> Utf32String {
+    let mut chars = Vec::with_capacity(needle.len());
+    let mut offsets = Vec::with_capacity(needle.len() + 1);
+    let mut clusters = clusters.iter().peekable();
+    for (i, &c) in needle.iter().enumerate() {
+        offsets.push(chars.len() as u32);
+        match clusters.next_if(|&&(j, _)| i == j) {
+            Some((_, cluster)) => chars.extend(cluster.chars()),
+            None => chars.push(c),
+        }
+    }
+    offsets.push(chars.len() as u32);
+    Utf32String::Clusters {
+        chars: chars.into_boxed_slice(),
+        offsets: offsets.into_boxed_slice(),
+    }
+}
+
 fn pattern_atoms(pattern: &str) -> impl Iterator<Item = &str> + '_ {
     let mut saw_backslash = false;
     pattern.split(move |c| {
@@ -411,11 +782,54 @@ fn pattern_atoms(pattern: &str) -> impl Iterator<Item = &str> + '_ {
     })
 }
 
+/// Parses the atoms of a pattern (see [`Pattern::parse`]). A standalone `|`
+/// between two atoms marks the second one as an alternative of the first.
+fn parse_atoms(
+    pattern: &str,
+    case_matching: CaseMatching,
+    normalize: Normalization,
+) -> impl Iterator<Item = Atom> + '_ {
+    let mut saw_atom = false;
+    let mut alternative = false;
+    pattern_atoms(pattern).filter_map(move |raw| {
+        if raw.is_empty() {
+            return None;
+        }
+        if raw == "|" {
+            alternative = saw_atom;
+            return None;
+        }
+        let mut atom = Atom::parse(raw, case_matching, normalize);
+        atom.alternative = std::mem::take(&mut alternative);
+        if atom.needle.is_empty() {
+            return None;
+        }
+        saw_atom = true;
+        Some(atom)
+    })
+}
+
+/// Splits `atoms` into groups of alternatives.
+fn atom_groups(atoms: &[Atom]) -> impl Iterator<Item = &[Atom]> {
+    let mut rem = atoms;
+    std::iter::from_fn(move || {
+        if rem.is_empty() {
+            return None;
+        }
+        let len = 1 + rem[1..].iter().take_while(|atom| atom.alternative).count();
+        let (group, rest) = rem.split_at(len);
+        rem = rest;
+        Some(group)
+    })
+}
+
 #[derive(Debug, Default)]
 /// A text pattern made up of (potentially multiple) [atoms](crate::pattern::Atom).
 #[non_exhaustive]
 pub struct Pattern {
-    /// The individual pattern (words) in this pattern
+    /// The individual pattern (words) in this pattern. Atoms are AND-ed
+    /// together, except for [alternatives](Atom::is_alternative) which are
+    /// OR-ed with the preceding atom.
     pub atoms: Vec<Atom>,
 }
 
@@ -442,13 +856,14 @@ impl Pattern {
     /// can be escaped with `\`). And `$`, `!`, `'` and `^` at word boundaries will
     /// cause different matching behaviour (see [`AtomKind`]). These can be
     /// escaped with backslash.
+    ///
+    /// Words separated by a standalone `|` are alternatives: the haystack
+    /// only has to match one of them (for example `^core | ^util` matches
+    /// everything that starts with `core` or `util`). Alternatives bind
+    /// stronger than the implicit AND between words, so `foo | bar baz`
+    /// requires `baz` and either `foo` or `bar` to match.
     pub fn parse(pattern: &str, case_matching: CaseMatching, normalize: Normalization) -> Pattern {
-        let atoms = pattern_atoms(pattern)
-            .filter_map(|pat| {
-                let pat = Atom::parse(pat, case_matching, normalize);
-                (!pat.needle.is_empty()).then_some(pat)
-            })
-            .collect();
+        let atoms = parse_atoms(pattern, case_matching, normalize).collect();
         Pattern { atoms }
     }
 
@@ -486,13 +901,20 @@ impl Pattern {
     ///
     /// *Note:*  The `ignore_case` setting is overwritten to match the casing of
     /// each pattern atom.
+    ///
+    /// A group of [alternatives](Atom::is_alternative) contributes the
+    /// score of its best matching atom.
     pub fn score(&self, haystack: Utf32Str<'_>, matcher: &mut Matcher) -> Option<u32> {
         if self.atoms.is_empty() {
             return Some(0);
         }
         let mut score = 0;
-        for pattern in &self.atoms {
-            score += pattern.score(haystack, matcher)? as u32;
+        for group in atom_groups(&self.atoms) {
+            let group_score = group
+                .iter()
+                .filter_map(|atom| atom.score(haystack, matcher))
+                .max()?;
+            score += group_score as u32;
         }
         Some(score)
     }
@@ -516,6 +938,9 @@ impl Pattern {
     /// indices.sort_unstable();
     /// indices.dedup();
     /// ```
+    ///
+    /// For a group of [alternatives](Atom::is_alternative) only the indices
+    /// of the best matching atom are reported.
     pub fn indices(
         &self,
         haystack: Utf32Str<'_>,
@@ -526,8 +951,18 @@ impl Pattern {
             return Some(0);
         }
         let mut score = 0;
-        for pattern in &self.atoms {
-            score += pattern.indices(haystack, matcher, indices)? as u32;
+        for group in atom_groups(&self.atoms) {
+            let best = match group {
+                [atom] => atom,
+                _ => {
+                    group
+                        .iter()
+                        .filter_map(|atom| Some((atom, atom.score(haystack, matcher)?)))
+                        .reduce(|best, alt| if alt.1 > best.1 { alt } else { best })?
+                        .0
+                }
+            };
+            score += best.indices(haystack, matcher, indices)? as u32;
         }
         Some(score)
     }
@@ -542,14 +977,8 @@ impl Pattern {
         normalize: Normalization,
     ) {
         self.atoms.clear();
-        let atoms = pattern_atoms(pattern).filter_map(|atom| {
-            let atom = Atom::parse(atom, case_matching, normalize);
-            if atom.needle.is_empty() {
-                return None;
-            }
-            Some(atom)
-        });
-        self.atoms.extend(atoms);
+        self.atoms
+            .extend(parse_atoms(pattern, case_matching, normalize));
     }
 }
 
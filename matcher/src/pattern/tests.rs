@@ -1,4 +1,5 @@
 use crate::pattern::{Atom, AtomKind, CaseMatching, Normalization, Pattern};
+use crate::{Config, Matcher, Utf32Str, Utf32String};
 
 #[test]
 fn negative() {
@@ -147,3 +148,388 @@ fn pattern_atoms() {
         ],
     )
 }
+
+#[test]
+fn alternatives() {
+    let pat = Pattern::parse(
+        "| ^core | ^util foo |",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    let alternatives: Vec<_> = pat.atoms.iter().map(|atom| atom.is_alternative()).collect();
+    assert_eq!(alternatives, [false, true, false]);
+    assert_eq!(pat.atoms[0].kind, AtomKind::Prefix);
+    assert_eq!(pat.atoms[1].needle.to_string(), "util");
+
+    // empty atoms don't take part in alternatives
+    let pat = Pattern::parse("foo | ^ bar", CaseMatching::Smart, Normalization::Smart);
+    assert!(pat.atoms.iter().all(|atom| !atom.is_alternative()));
+    // `|` is only special as a standalone word
+    let pat = Pattern::parse("foo |bar", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.atoms[1].needle.to_string(), "|bar");
+    assert!(!pat.atoms[1].is_alternative());
+}
+
+#[test]
+fn alternatives_score() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pat = Pattern::parse("^core | ^util", CaseMatching::Smart, Normalization::Smart);
+    let matches = pat.match_list(["core/a", "util/b", "src/c"], &mut matcher);
+    let matched: Vec<_> = matches.iter().map(|(item, _)| *item).collect();
+    assert_eq!(matched, ["core/a", "util/b"]);
+
+    // the best alternative determines the score
+    let pat = Pattern::parse("x | 'foo", CaseMatching::Smart, Normalization::Smart);
+    let foo = Pattern::parse("'foo", CaseMatching::Smart, Normalization::Smart);
+    let mut buf = Vec::new();
+    let haystack = Utf32Str::new("x_foo", &mut buf);
+    assert_eq!(
+        pat.score(haystack, &mut matcher),
+        foo.score(haystack, &mut matcher)
+    );
+
+    // alternatives bind stronger than the implicit and
+    let pat = Pattern::parse("a | b c", CaseMatching::Smart, Normalization::Smart);
+    let matches = pat.match_list(["ac", "bc", "ab"], &mut matcher);
+    let matched: Vec<_> = matches.iter().map(|(item, _)| *item).collect();
+    assert_eq!(matched.len(), 2);
+    assert!(!matched.contains(&"ab"));
+}
+
+#[test]
+fn alternatives_indices() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pat = Pattern::parse("x | 'foo", CaseMatching::Smart, Normalization::Smart);
+    let mut buf = Vec::new();
+    let haystack = Utf32Str::new("x_foo", &mut buf);
+    let mut indices = Vec::new();
+    let score = pat.indices(haystack, &mut matcher, &mut indices);
+    assert_eq!(score, pat.score(haystack, &mut matcher));
+    assert_eq!(indices, [2, 3, 4]);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex() {
+    let pat = Atom::parse("/test_\\d+/", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.kind, AtomKind::Regex);
+    assert!(!pat.negative);
+    assert!(pat.ignore_case);
+    assert_eq!(pat.needle.to_string(), "test_\\d+");
+    let pat = Atom::parse(
+        "!/\\.rs$|\\.toml$/",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    assert_eq!(pat.kind, AtomKind::Regex);
+    assert!(pat.negative);
+    let pat = Atom::parse("/Foo/", CaseMatching::Smart, Normalization::Smart);
+    assert!(!pat.ignore_case);
+    // escape sequences don't disable smart case
+    let pat = Atom::parse("/\\D\\\\/", CaseMatching::Smart, Normalization::Smart);
+    assert!(pat.ignore_case);
+    let pat = Atom::parse("\\/foo/", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.kind, AtomKind::Fuzzy);
+    assert_eq!(pat.needle.to_string(), "/foo/");
+    let pat = Atom::parse("/", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.kind, AtomKind::Fuzzy);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_match() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pat = Pattern::parse(
+        "/\\.rs$|\\.toml$/",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    let matches = pat.match_list(["lib.rs", "Cargo.toml", "README.md"], &mut matcher);
+    let mut matched: Vec<_> = matches.iter().map(|(item, _)| *item).collect();
+    matched.sort_unstable();
+    assert_eq!(matched, ["Cargo.toml", "lib.rs"]);
+
+    let pat = Pattern::parse("!/test_\\d+/", CaseMatching::Smart, Normalization::Smart);
+    let matches = pat.match_list(["test_12", "test_x", "TEST_3"], &mut matcher);
+    assert_eq!(matches, [("test_x", 0)]);
+
+    // invalid regexes never match
+    let pat = Pattern::parse("/(foo/", CaseMatching::Smart, Normalization::Smart);
+    assert!(pat.match_list(["(foo", "foo"], &mut matcher).is_empty());
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_indices() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pat = Pattern::parse("/b\\w/", CaseMatching::Smart, Normalization::Smart);
+    let mut buf = Vec::new();
+    // indices are char indices, not byte offsets
+    let haystack = Utf32Str::new("äö bx", &mut buf);
+    let mut indices = Vec::new();
+    let score = pat.indices(haystack, &mut matcher, &mut indices);
+    assert_eq!(score, pat.score(haystack, &mut matcher));
+    assert_eq!(indices, [3, 4]);
+}
+
+#[test]
+fn full_case_folding() {
+    let pat = Atom::parse("Straße", CaseMatching::IgnoreFull, Normalization::Never);
+    assert_eq!(pat.needle_text(), Utf32Str::Ascii(b"strasse"));
+    let pat = Atom::parse("ﬁle", CaseMatching::SmartFull, Normalization::Never);
+    assert_eq!(pat.needle_text(), Utf32Str::Ascii(b"file"));
+    // smart case respects the case of uppercase needles, so nothing is folded
+    let pat = Atom::parse("Straße", CaseMatching::SmartFull, Normalization::Never);
+    assert_eq!(pat.needle.to_string(), "Straße");
+    let pat = Atom::parse("straße", CaseMatching::Ignore, Normalization::Never);
+    assert_eq!(pat.needle.to_string(), "straße");
+}
+
+#[test]
+fn full_case_folding_indices() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut buf = Vec::new();
+    let mut indices = Vec::new();
+    for (haystack, needle, expected) in [
+        ("Straße", "strasse", &[0, 1, 2, 3, 4, 5][..]),
+        ("Straße", "'ss", &[4]),
+        ("ﬁle", "file", &[0, 1, 2]),
+        ("ﬁle", "^fi", &[0]),
+        ("İstanbul", "istanbul", &[0, 1, 2, 3, 4, 5, 6, 7]),
+        ("ılık", "ilik", &[0, 1, 2, 3]),
+    ] {
+        let haystack = Utf32Str::new(haystack, &mut buf);
+        for case in [CaseMatching::IgnoreFull, CaseMatching::SmartFull] {
+            let pat = Atom::parse(needle, case, Normalization::Never);
+            indices.clear();
+            assert!(pat.score(haystack, &mut matcher).is_some());
+            assert!(pat.indices(haystack, &mut matcher, &mut indices).is_some());
+            assert_eq!(indices, expected, "{needle:?} {haystack:?}");
+            let pat = Atom::parse(needle, CaseMatching::Ignore, Normalization::Never);
+            assert_eq!(pat.score(haystack, &mut matcher), None);
+        }
+    }
+    // full case folding applies to both the needle and the haystack
+    let haystack = Utf32Str::new("STRASSE", &mut buf);
+    let pat = Atom::parse("straße", CaseMatching::SmartFull, Normalization::Never);
+    assert!(pat.score(haystack, &mut matcher).is_some());
+}
+
+#[cfg(feature = "unicode-width-normalization")]
+#[test]
+fn width_normalization() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut buf = Vec::new();
+    let matches = |needle: &str, haystack: &str, normalization, matcher: &mut Matcher| {
+        let pat = Atom::parse(needle, CaseMatching::Smart, normalization);
+        let mut buf = Vec::new();
+        pat.score(Utf32Str::new(haystack, &mut buf), matcher)
+            .is_some()
+    };
+    assert!(matches(
+        "abc",
+        "ＡＢＣ",
+        Normalization::SmartWidth,
+        &mut matcher
+    ));
+    assert!(!matches(
+        "abc",
+        "ＡＢＣ",
+        Normalization::Smart,
+        &mut matcher
+    ));
+    assert!(matches(
+        "カナ",
+        "ｶﾅ",
+        Normalization::SmartWidth,
+        &mut matcher
+    ));
+    // the special form only matches literally
+    assert!(!matches(
+        "ＡＢＣ",
+        "ABC",
+        Normalization::SmartWidth,
+        &mut matcher
+    ));
+    assert!(!matches(
+        "ｶﾅ",
+        "カナ",
+        Normalization::SmartWidth,
+        &mut matcher
+    ));
+    assert!(matches("ｶﾅ", "ｶﾅ", Normalization::SmartWidth, &mut matcher));
+    // other normalizations still apply
+    assert!(matches(
+        "ａｂｃ a",
+        "ａｂｃ ä",
+        Normalization::SmartWidth,
+        &mut matcher
+    ));
+    let pat = Atom::parse("foo bar", CaseMatching::Smart, Normalization::SmartWidth);
+    let mut indices = Vec::new();
+    let haystack = Utf32Str::new("ｆｏｏ\u{3000}ｂａｒ", &mut buf);
+    assert!(pat.indices(haystack, &mut matcher, &mut indices).is_some());
+    assert_eq!(indices, [0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[cfg(feature = "unicode-kana-normalization")]
+#[test]
+fn kana_normalization() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let matches = |needle: &str, haystack: &str, normalization, matcher: &mut Matcher| {
+        let pat = Atom::parse(needle, CaseMatching::Smart, normalization);
+        let mut buf = Vec::new();
+        pat.score(Utf32Str::new(haystack, &mut buf), matcher)
+            .is_some()
+    };
+    assert!(matches(
+        "かな",
+        "カナ",
+        Normalization::SmartKana,
+        &mut matcher
+    ));
+    assert!(matches(
+        "かな",
+        "ｶﾅ",
+        Normalization::SmartKana,
+        &mut matcher
+    ));
+    assert!(matches(
+        "かな",
+        "かな",
+        Normalization::SmartKana,
+        &mut matcher
+    ));
+    assert!(!matches(
+        "かな",
+        "カナ",
+        Normalization::SmartWidth,
+        &mut matcher
+    ));
+    // katakana only matches literally
+    assert!(!matches(
+        "カナ",
+        "かな",
+        Normalization::SmartKana,
+        &mut matcher
+    ));
+    assert!(matches(
+        "カナ",
+        "ｶﾅ",
+        Normalization::SmartKana,
+        &mut matcher
+    ));
+}
+
+#[cfg(feature = "unicode-transliteration")]
+#[test]
+fn transliteration() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut buf = Vec::new();
+    let haystack = Utf32Str::new("Привет, мир", &mut buf);
+    let pat = Atom::parse(
+        "privet",
+        CaseMatching::Smart,
+        Normalization::SmartTransliterate,
+    );
+    let mut indices = Vec::new();
+    assert!(pat.indices(haystack, &mut matcher, &mut indices).is_some());
+    assert_eq!(indices, [0, 1, 2, 3, 4, 5]);
+    let pat = Atom::parse(
+        "'mir",
+        CaseMatching::Smart,
+        Normalization::SmartTransliterate,
+    );
+    assert!(pat.score(haystack, &mut matcher).is_some());
+    let pat = Atom::parse("privet", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.score(haystack, &mut matcher), None);
+    // a cyrillic needle disables transliteration and only matches literally
+    let pat = Atom::parse(
+        "привет",
+        CaseMatching::Smart,
+        Normalization::SmartTransliterate,
+    );
+    assert!(pat.score(haystack, &mut matcher).is_some());
+    let haystack = Utf32Str::new("privet", &mut buf);
+    assert_eq!(pat.score(haystack, &mut matcher), None);
+}
+
+#[cfg(all(feature = "unicode-normalization", feature = "unicode-segmentation"))]
+#[test]
+fn grapheme_clusters() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let items = ["👍🏽 ok", "👍🏿 ok", "👍 ok"];
+    let matches = |needle: &str, normalization, matcher: &mut Matcher| -> Vec<usize> {
+        let pat = Atom::parse(needle, CaseMatching::Smart, normalization);
+        items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                let haystack = match normalization {
+                    Normalization::SmartClusters => Utf32String::from_grapheme_clusters(item),
+                    _ => Utf32String::from(**item),
+                };
+                pat.score(haystack.slice(..), matcher).is_some()
+            })
+            .map(|(i, _)| i)
+            .collect()
+    };
+    assert_eq!(matches("👍🏽", Normalization::Smart, &mut matcher), [0, 1, 2]);
+    assert_eq!(
+        matches("👍🏽", Normalization::SmartClusters, &mut matcher),
+        [0]
+    );
+    assert_eq!(
+        matches("👍", Normalization::SmartClusters, &mut matcher),
+        [2]
+    );
+    assert_eq!(
+        matches("ok", Normalization::SmartClusters, &mut matcher),
+        [0, 1, 2]
+    );
+
+    let haystack = Utf32String::from_grapheme_clusters("cafe\u{301} cafe");
+    let pat = Atom::parse(
+        "e\u{301}",
+        CaseMatching::Smart,
+        Normalization::SmartClusters,
+    );
+    let mut indices = Vec::new();
+    assert!(pat
+        .indices(haystack.slice(..), &mut matcher, &mut indices)
+        .is_some());
+    assert_eq!(indices, [3]);
+
+    // chars of the private use area never match clusters
+    let haystack = Utf32String::from_grapheme_clusters("\u{100000}\u{100001} e\u{301}");
+    indices.clear();
+    assert!(pat
+        .indices(haystack.slice(..), &mut matcher, &mut indices)
+        .is_some());
+    assert_eq!(indices, [3]);
+    let haystack = Utf32String::from_grapheme_clusters("\u{100000}");
+    assert_eq!(pat.score(haystack.slice(..), &mut matcher), None);
+    let pat = Atom::parse(
+        "\u{100000}",
+        CaseMatching::Smart,
+        Normalization::SmartClusters,
+    );
+    assert_eq!(
+        pat.score(
+            Utf32String::from_grapheme_clusters("👍🏽").slice(..),
+            &mut matcher
+        ),
+        None
+    );
+
+    // clusters work with all kinds of atoms
+    let haystack = Utf32String::from_grapheme_clusters("👍🏽 e\u{301}");
+    for (needle, expected) in [("^👍🏽", [0]), ("'e\u{301}", [2]), ("e\u{301}$", [2])] {
+        let pat = Atom::parse(needle, CaseMatching::Smart, Normalization::SmartClusters);
+        indices.clear();
+        assert!(pat
+            .indices(haystack.slice(..), &mut matcher, &mut indices)
+            .is_some());
+        assert_eq!(indices, expected);
+    }
+}
@@ -0,0 +1,150 @@
+use regex::Regex;
+
+use crate::chars::{AsciiChar, Char};
+use crate::{Matcher, Utf32Str};
+
+/// A compiled regex that compares equal to other regexes with the same
+/// source (so that [`Atom`](crate::pattern::Atom) can derive `PartialEq`).
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledRegex(pub(crate) Regex);
+
+impl PartialEq for CompiledRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for CompiledRegex {}
+
+impl Matcher {
+    pub(crate) fn regex_match_impl<const INDICES: bool>(
+        &mut self,
+        haystack: Utf32Str<'_>,
+        regex: &Regex,
+        indices: &mut Vec<u32>,
+    ) -> Option<u16> {
+        assert!(
+            haystack.len() <= u32::MAX as usize,
+            "matching is only support for up to 2^32-1 codepoints"
+        );
+        // case insensitivity is handled by the regex itself, the matched
+        // text is only scored against itself
+        let ignore_case = std::mem::replace(&mut self.config.ignore_case, false);
+        let normalize = std::mem::replace(&mut self.config.normalize, false);
+        #[cfg(feature = "unicode-width-normalization")]
+        let normalize_width = std::mem::replace(&mut self.config.normalize_width, false);
+        #[cfg(feature = "unicode-kana-normalization")]
+        let normalize_kana = std::mem::replace(&mut self.config.normalize_kana, false);
+        let res = match haystack {
+            Utf32Str::Ascii(haystack) => {
+                // SAFETY: ascii is always valid utf-8
+                let text = unsafe { std::str::from_utf8_unchecked(haystack) };
+                let matches = regex.find_iter(text).map(|m| (m.start(), m.end()));
+                self.best_regex_match::<INDICES, _>(AsciiChar::cast(haystack), matches, indices)
+            }
+            Utf32Str::Unicode(haystack) => {
+                let mut text = std::mem::take(&mut self.regex_buf);
+                text.clear();
+                text.extend(haystack);
+                // matches are returned in order so the byte offsets can be
+                // translated to char indices in a single pass
+                let mut char_idx = 0;
+                let mut byte_idx = 0;
+                let mut to_char_idx = |byte: usize| {
+                    while byte_idx < byte {
+                        byte_idx += haystack[char_idx].len_utf8();
+                        char_idx += 1;
+                    }
+                    char_idx
+                };
+                let matches = regex
+                    .find_iter(&text)
+                    .map(|m| (to_char_idx(m.start()), to_char_idx(m.end())));
+                let res = self.best_regex_match::<INDICES, _>(haystack, matches, indices);
+                self.regex_buf = text;
+                res
+            }
+            Utf32Str::Clusters { chars, offsets } => {
+                let mut text = std::mem::take(&mut self.regex_buf);
+                text.clear();
+                text.extend(chars);
+                // only the first char of each grapheme is scored
+                let mut replaced = std::mem::take(&mut self.replaced_clusters);
+                replaced.haystack.clear();
+                replaced.haystack.extend(haystack.chars());
+                let mut char_idx = 0;
+                let mut byte_idx = 0;
+                let mut to_char_idx = |byte: usize| {
+                    while byte_idx < byte {
+                        byte_idx += chars[char_idx].len_utf8();
+                        char_idx += 1;
+                    }
+                    char_idx
+                };
+                // matches that start or end within a grapheme cover all of it
+                let grapheme_idx = |char_idx: usize, round_up: bool| {
+                    let graphemes = offsets.partition_point(|&offset| {
+                        let offset = (offset - offsets[0]) as usize;
+                        offset < char_idx || (!round_up && offset == char_idx)
+                    });
+                    if round_up {
+                        graphemes
+                    } else {
+                        graphemes - 1
+                    }
+                };
+                let matches = regex.find_iter(&text).map(|m| {
+                    let start = to_char_idx(m.start());
+                    let end = to_char_idx(m.end());
+                    // empty matches stay empty
+                    let round_up = start == end;
+                    (grapheme_idx(start, round_up), grapheme_idx(end, true))
+                });
+                let res = self.best_regex_match::<INDICES, _>(&replaced.haystack, matches, indices);
+                self.replaced_clusters = replaced;
+                self.regex_buf = text;
+                res
+            }
+        };
+        self.config.ignore_case = ignore_case;
+        self.config.normalize = normalize;
+        #[cfg(feature = "unicode-width-normalization")]
+        {
+            self.config.normalize_width = normalize_width;
+        }
+        #[cfg(feature = "unicode-kana-normalization")]
+        {
+            self.config.normalize_kana = normalize_kana;
+        }
+        res
+    }
+
+    /// Scores each (char index) range in `matches` like a substring match
+    /// of its own text and returns the best score.
+    fn best_regex_match<const INDICES: bool, H: Char + PartialEq<H>>(
+        &mut self,
+        haystack: &[H],
+        matches: impl Iterator<Item = (usize, usize)>,
+        indices: &mut Vec<u32>,
+    ) -> Option<u16> {
+        let mut best = None;
+        for (start, end) in matches {
+            // empty matches (like `x*`) match anything but don't score
+            let score = if start == end {
+                0
+            } else {
+                let needle = &haystack[start..end];
+                self.calculate_score::<false, _, _>(haystack, needle, start, end, &mut Vec::new())
+            };
+            match best {
+                Some((best_score, _, _)) if best_score >= score => (),
+                _ => best = Some((score, start, end)),
+            }
+        }
+        let (score, start, end) = best?;
+        if INDICES {
+            indices.extend(start as u32..end as u32);
+        }
+        Some(score)
+    }
+}
@@ -8,8 +8,21 @@ pub(crate) const PENALTY_GAP_START: u16 = 3;
 pub(crate) const PENALTY_GAP_EXTENSION: u16 = 1;
 /// If the prefer_prefix option is enabled we want to penalize
 /// the initial gap. The prefix should not be too much  
+/// The largest prefix bonus is [`Scoring::bonus_boundary`](crate::Scoring::bonus_boundary).
 pub(crate) const PREFIX_BONUS_SCALE: u16 = 2;
-pub(crate) const MAX_PREFIX_BONUS: u16 = BONUS_BOUNDARY;
+/// Penalty for each typo in typo tolerant fuzzy matching (see
+/// [`Config::max_typos`]). On top of that a substituted or extra needle char
+/// doesn't receive [`SCORE_MATCH`].
+pub(crate) const PENALTY_TYPO: u16 = SCORE_MATCH;
+/// Typo tolerant matching allows one typo per `TYPO_MIN_NEEDLE_LEN` needle
+/// chars, shorter needles produce too many false positives.
+pub(crate) const TYPO_MIN_NEEDLE_LEN: usize = 4;
+/// The largest score a single matched char may receive with a custom
+/// [`Scoring`](crate::Scoring). The optimal fuzzy matcher only handles needles
+/// of up to 320 chars (the matrix has at most 100K cells and the haystack is
+/// never shorter than the needle) so this ensures that its scores never
+/// overflow. All other matchers saturate instead.
+pub(crate) const MAX_CHAR_SCORE: u16 = u16::MAX / 320;
 
 // We prefer matches at the beginning of a word, but the bonus should not be
 // too great to prevent the longer acronym matches from always winning over
@@ -53,9 +66,9 @@ impl Config {
         if class > CharClass::Delimiter {
             // transition from non word to word
             match prev_class {
-                CharClass::Whitespace => return self.bonus_boundary_white,
-                CharClass::Delimiter => return self.bonus_boundary_delimiter,
-                CharClass::NonWord => return BONUS_BOUNDARY,
+                CharClass::Whitespace => return self.scoring.bonus_boundary_white,
+                CharClass::Delimiter => return self.scoring.bonus_boundary_delimiter,
+                CharClass::NonWord => return self.scoring.bonus_boundary,
                 _ => (),
             }
         }
@@ -63,11 +76,11 @@ impl Config {
             || prev_class != CharClass::Number && class == CharClass::Number
         {
             // camelCase letter123
-            BONUS_CAMEL123
+            self.scoring.bonus_camel123
         } else if class == CharClass::Whitespace {
-            self.bonus_boundary_white
+            self.scoring.bonus_boundary_white
         } else if class == CharClass::NonWord {
-            return BONUS_NON_WORD;
+            self.scoring.bonus_non_word
         } else {
             0
         }
@@ -107,7 +120,8 @@ impl Matcher {
         }
         let class = haystack[start].char_class(&self.config);
         let mut first_bonus = self.bonus_for(prev_class, class);
-        let mut score = SCORE_MATCH + first_bonus * BONUS_FIRST_CHAR_MULTIPLIER;
+        let scoring = self.config.scoring;
+        let mut score = scoring.score_match + first_bonus * scoring.bonus_first_char_multiplier;
         prev_class = class;
         needle_char = *needle_iter.next().unwrap_or(&needle_char);
 
@@ -119,14 +133,14 @@ impl Matcher {
                 }
                 let mut bonus = self.bonus_for(prev_class, class);
                 if consecutive != 0 {
-                    if bonus >= BONUS_BOUNDARY && bonus > first_bonus {
+                    if bonus >= scoring.bonus_boundary && bonus > first_bonus {
                         first_bonus = bonus
                     }
-                    bonus = max(max(bonus, first_bonus), BONUS_CONSECUTIVE);
+                    bonus = max(max(bonus, first_bonus), scoring.bonus_consecutive);
                 } else {
                     first_bonus = bonus;
                 }
-                score += SCORE_MATCH + bonus;
+                score = score.saturating_add(scoring.score_match + bonus);
                 in_gap = false;
                 consecutive += 1;
                 if let Some(&next) = needle_iter.next() {
@@ -134,9 +148,9 @@ impl Matcher {
                 }
             } else {
                 let penalty = if in_gap {
-                    PENALTY_GAP_EXTENSION
+                    scoring.penalty_gap_extension
                 } else {
-                    PENALTY_GAP_START
+                    scoring.penalty_gap_start
                 };
                 score = score.saturating_sub(penalty);
                 in_gap = true;
@@ -146,11 +160,18 @@ impl Matcher {
         }
         if self.config.prefer_prefix {
             if start != 0 {
-                let penalty = PENALTY_GAP_START
-                    + PENALTY_GAP_START * (start - 1).min(u16::MAX as usize) as u16;
-                score += MAX_PREFIX_BONUS.saturating_sub(penalty / PREFIX_BONUS_SCALE);
+                let penalty = scoring.penalty_gap_start.saturating_add(
+                    scoring
+                        .penalty_gap_start
+                        .saturating_mul((start - 1).min(u16::MAX as usize) as u16),
+                );
+                score = score.saturating_add(
+                    scoring
+                        .bonus_boundary
+                        .saturating_sub(penalty / PREFIX_BONUS_SCALE),
+                );
             } else {
-                score += MAX_PREFIX_BONUS;
+                score = score.saturating_add(scoring.bonus_boundary);
             }
         }
         score
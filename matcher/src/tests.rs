@@ -2,10 +2,10 @@ use crate::chars::Char;
 use crate::pattern::{CaseMatching, Normalization, Pattern};
 use crate::score::{
     BONUS_BOUNDARY, BONUS_CAMEL123, BONUS_CONSECUTIVE, BONUS_FIRST_CHAR_MULTIPLIER, BONUS_NON_WORD,
-    MAX_PREFIX_BONUS, PENALTY_GAP_EXTENSION, PENALTY_GAP_START, SCORE_MATCH,
+    PENALTY_GAP_EXTENSION, PENALTY_GAP_START, SCORE_MATCH,
 };
 use crate::utf32_str::Utf32Str;
-use crate::{Config, Matcher};
+use crate::{Config, Delimiters, Matcher, Scoring, ScoringError};
 
 use Algorithm::*;
 
@@ -133,8 +133,9 @@ pub fn assert_not_matches(normalize: bool, case_sensitive: bool, cases: &[(&str,
     )
 }
 
-const BONUS_BOUNDARY_WHITE: u16 = Config::DEFAULT.bonus_boundary_white;
-const BONUS_BOUNDARY_DELIMITER: u16 = Config::DEFAULT.bonus_boundary_delimiter;
+const BONUS_BOUNDARY_WHITE: u16 = Scoring::DEFAULT.bonus_boundary_white();
+const BONUS_BOUNDARY_DELIMITER: u16 = Scoring::DEFAULT.bonus_boundary_delimiter();
+const MAX_PREFIX_BONUS: u16 = BONUS_BOUNDARY;
 
 #[test]
 fn test_fuzzy() {
@@ -769,3 +770,341 @@ fn umlaut() {
         .match_list(paths, &mut matcher);
     assert_eq!(matches.len(), 2);
 }
+
+#[test]
+fn unicode_substring_at_end() {
+    let mut matcher = Matcher::new(Config {
+        normalize: false,
+        ..Config::DEFAULT
+    });
+    let mut needle_buf = Vec::new();
+    let mut haystack_buf = Vec::new();
+    let mut indices = Vec::new();
+    for (haystack, needle, expected) in [("äbc", "bc", &[1, 2][..]), ("äbcö", "cö", &[2, 3])] {
+        let haystack = Utf32Str::new(haystack, &mut haystack_buf);
+        let needle = Utf32Str::new(needle, &mut needle_buf);
+        indices.clear();
+        assert!(matcher
+            .substring_indices(haystack, needle, &mut indices)
+            .is_some());
+        assert_eq!(indices, expected);
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_regex() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut buf = Vec::new();
+    let mut indices = Vec::new();
+    let regex = regex::Regex::new("o+").unwrap();
+    // the best scoring match wins, not the first one
+    let haystack = Utf32Str::new("xoo_foo ooo", &mut buf);
+    let score = matcher.regex_indices(haystack, &regex, &mut indices);
+    assert_eq!(indices, [8, 9, 10]);
+    assert_eq!(score, matcher.regex_match(haystack, &regex));
+    assert_eq!(
+        score,
+        matcher.substring_match(haystack, Utf32Str::Ascii(b"ooo"))
+    );
+
+    // unicode haystacks
+    indices.clear();
+    let haystack = Utf32Str::new("Ünïcödé ööo", &mut buf);
+    let regex = regex::Regex::new("ö+o").unwrap();
+    assert!(matcher
+        .regex_indices(haystack, &regex, &mut indices)
+        .is_some());
+    assert_eq!(indices, [8, 9, 10]);
+
+    // empty matches
+    indices.clear();
+    let regex = regex::Regex::new("x*").unwrap();
+    assert_eq!(
+        matcher.regex_indices(haystack, &regex, &mut indices),
+        Some(0)
+    );
+    assert!(indices.is_empty());
+
+    // matches within a grapheme cover all of it
+    #[cfg(feature = "unicode-segmentation")]
+    {
+        let mut offsets = Vec::new();
+        let haystack = Utf32Str::new_grapheme_clusters("cafe\u{301} x", &mut buf, &mut offsets);
+        for (regex, expected) in [("\u{301}", 3), ("x", 5)] {
+            let regex = regex::Regex::new(regex).unwrap();
+            indices.clear();
+            assert!(matcher
+                .regex_indices(haystack, &regex, &mut indices)
+                .is_some());
+            assert_eq!(indices, [expected]);
+        }
+    }
+}
+
+#[test]
+fn test_typos() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut buf = Vec::new();
+    let mut needle_buf = Vec::new();
+    let mut indices = Vec::new();
+    let haystack = Utf32Str::new("receive", &mut buf);
+    let mut fuzzy_indices = |matcher: &mut Matcher, needle: &str, indices: &mut Vec<u32>| {
+        indices.clear();
+        let needle = Utf32Str::new(needle, &mut needle_buf);
+        let score = matcher.fuzzy_indices(haystack, needle, indices);
+        assert_eq!(score, matcher.fuzzy_match(haystack, needle));
+        score
+    };
+    assert_eq!(fuzzy_indices(&mut matcher, "recieve", &mut indices), None);
+
+    matcher.config.max_typos = 1;
+    let exact = fuzzy_indices(&mut matcher, "receive", &mut indices).unwrap();
+    // transposition
+    let transposed = fuzzy_indices(&mut matcher, "recieve", &mut indices).unwrap();
+    assert!(transposed < exact);
+    assert_eq!(indices, [0, 1, 2, 3, 4, 5, 6]);
+    // substitution
+    let substituted = fuzzy_indices(&mut matcher, "rexeive", &mut indices).unwrap();
+    assert!(substituted < exact);
+    assert_eq!(indices, [0, 1, 3, 4, 5, 6]);
+    // extra needle char
+    let inserted = fuzzy_indices(&mut matcher, "receivve", &mut indices).unwrap();
+    assert!(inserted < exact);
+    assert_eq!(indices, [0, 1, 2, 3, 4, 5, 6]);
+
+    // too many typos
+    assert_eq!(fuzzy_indices(&mut matcher, "rcieeve", &mut indices), None);
+    assert_eq!(fuzzy_indices(&mut matcher, "recievve", &mut indices), None);
+    // short needles must match exactly
+    assert_eq!(fuzzy_indices(&mut matcher, "rxc", &mut indices), None);
+    matcher.config.max_typos = 2;
+    assert!(fuzzy_indices(&mut matcher, "recievve", &mut indices).is_some());
+    assert_eq!(indices, [0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_typos_prefer_prefix() {
+    let mut matcher = Matcher::new(Config {
+        max_typos: 1,
+        prefer_prefix: true,
+        ..Config::DEFAULT
+    });
+    let mut buf = Vec::new();
+    let mut needle_buf = Vec::new();
+    let needle = Utf32Str::new("recieve", &mut needle_buf);
+    let prefix = matcher.fuzzy_match(Utf32Str::new("receive foo", &mut buf), needle);
+    let postfix = matcher.fuzzy_match(Utf32Str::new("foo receive", &mut buf), needle);
+    assert!(prefix.unwrap() > postfix.unwrap());
+}
+
+#[test]
+fn test_typos_long_haystack() {
+    let mut matcher = Matcher::new(Config {
+        max_typos: 1,
+        ..Config::DEFAULT
+    });
+    let mut buf = Vec::new();
+    let mut needle_buf = Vec::new();
+    let mut indices = Vec::new();
+    let haystack = format!("{}receive", "x".repeat(20_000));
+    let haystack = Utf32Str::new(&haystack, &mut buf);
+    let needle = Utf32Str::new("recieve", &mut needle_buf);
+    // the match is still found but without indices
+    let score = matcher.fuzzy_indices(haystack, needle, &mut indices);
+    assert_eq!(score, matcher.fuzzy_match(haystack, needle));
+    assert!(score.is_some());
+    assert!(indices.is_empty());
+}
+
+#[test]
+fn test_typos_unicode() {
+    let mut matcher = Matcher::new(Config {
+        max_typos: 1,
+        ..Config::DEFAULT
+    });
+    let mut buf = Vec::new();
+    let mut needle_buf = Vec::new();
+    let mut indices = Vec::new();
+    let haystack = Utf32Str::new("привет", &mut buf);
+    let needle = Utf32Str::new("пирвет", &mut needle_buf);
+    assert!(matcher
+        .fuzzy_indices(haystack, needle, &mut indices)
+        .is_some());
+    assert_eq!(indices, [0, 1, 2, 3, 4, 5]);
+    // ascii haystack with a unicode needle
+    indices.clear();
+    let haystack = Utf32Str::new("hello", &mut buf);
+    let needle = Utf32Str::new("hellж", &mut needle_buf);
+    assert!(matcher
+        .fuzzy_indices(haystack, needle, &mut indices)
+        .is_some());
+    assert_eq!(indices, [0, 1, 2, 3]);
+}
+
+#[test]
+fn test_scoring_validate() {
+    assert_eq!(Scoring::DEFAULT.validate(), Ok(()));
+    assert_eq!(
+        Scoring::DEFAULT.with_score_match(0).validate(),
+        Err(ScoringError::ZeroScoreMatch)
+    );
+    assert_eq!(
+        Scoring::DEFAULT.with_bonus_consecutive(0).validate(),
+        Err(ScoringError::ZeroBonusConsecutive)
+    );
+    // 100 + 50 * 2 + 8 > 204
+    let scoring = Scoring::DEFAULT
+        .with_score_match(100)
+        .with_bonus_non_word(50);
+    assert_eq!(scoring.validate(), Err(ScoringError::CharScoreTooLarge));
+    let mut config = Config::DEFAULT;
+    assert_eq!(
+        config.set_scoring(scoring),
+        Err(ScoringError::CharScoreTooLarge)
+    );
+    assert_eq!(config, Config::DEFAULT);
+    // 100 + 48 * 2 + 8 = 204
+    let scoring = Scoring::DEFAULT
+        .with_score_match(100)
+        .with_bonus_non_word(48);
+    assert_eq!(
+        Config::DEFAULT
+            .with_scoring(scoring)
+            .map(|config| config.scoring),
+        Ok(scoring)
+    );
+}
+
+#[test]
+fn test_custom_scoring() {
+    let scoring = Scoring::DEFAULT
+        .with_score_match(20)
+        .with_bonus_camel123(8)
+        .with_penalty_gap_start(4)
+        .with_penalty_gap_extension(2)
+        .with_bonus_first_char_multiplier(3);
+    let mut matcher = Matcher::new(Config::DEFAULT.with_scoring(scoring).unwrap());
+    let mut needle_buf = Vec::new();
+    let mut haystack_buf = Vec::new();
+    let mut indices = Vec::new();
+
+    let haystack = Utf32Str::new("fooBarBaz", &mut haystack_buf);
+    let needle = Utf32Str::new("fbb", &mut needle_buf);
+    // f: 20 + 10 * 3, b: 20 + 8 - 4 - 2, b: 20 + 8 - 4 - 2
+    assert_eq!(
+        matcher.fuzzy_indices(haystack, needle, &mut indices),
+        Some(94)
+    );
+    assert_eq!(indices, [0, 3, 6]);
+    indices.clear();
+    assert_eq!(
+        matcher.fuzzy_indices_greedy(haystack, needle, &mut indices),
+        Some(94)
+    );
+    assert_eq!(indices, [0, 3, 6]);
+
+    let haystack = Utf32Str::new("foo bar", &mut haystack_buf);
+    let needle = Utf32Str::new("bar", &mut needle_buf);
+    // b: 20 + 10 * 3, a and r: 20 + 10
+    assert_eq!(matcher.substring_match(haystack, needle), Some(110));
+}
+
+#[test]
+fn test_scoring_no_overflow() {
+    // the largest valid score for each char
+    let scoring = Scoring::DEFAULT
+        .with_score_match(100)
+        .with_bonus_boundary_white(48);
+    let mut matcher = Matcher::new(Config::DEFAULT.with_scoring(scoring).unwrap());
+    let text = " a".repeat(1000);
+    let mut needle_buf = Vec::new();
+    let mut haystack_buf = Vec::new();
+    let haystack = Utf32Str::new(&text, &mut haystack_buf);
+    let needle = Utf32Str::new(&text, &mut needle_buf);
+    assert_eq!(matcher.fuzzy_match(haystack, needle), Some(u16::MAX));
+    assert_eq!(matcher.substring_match(haystack, needle), Some(u16::MAX));
+    assert_eq!(matcher.exact_match(haystack, needle), Some(u16::MAX));
+}
+
+#[test]
+fn test_delimiters() {
+    let mut default = Matcher::new(Config::DEFAULT);
+    let mut matcher =
+        Matcher::new(Config::DEFAULT.with_delimiters(Delimiters::new("/#→、".chars())));
+    let mut needle_buf = Vec::new();
+    let mut haystack_buf = Vec::new();
+    for (haystack, needle) in [("a#b", "b"), ("a→b", "b"), ("東京、大阪", "大")] {
+        let haystack = Utf32Str::new(haystack, &mut haystack_buf);
+        let needle = Utf32Str::new(needle, &mut needle_buf);
+        assert_eq!(
+            default.fuzzy_match(haystack, needle),
+            Some(SCORE_MATCH + BONUS_BOUNDARY * BONUS_FIRST_CHAR_MULTIPLIER),
+        );
+        assert_eq!(
+            matcher.fuzzy_match(haystack, needle),
+            Some(SCORE_MATCH + BONUS_BOUNDARY_DELIMITER * BONUS_FIRST_CHAR_MULTIPLIER),
+        );
+    }
+    // `:` is no longer a delimiter
+    let haystack = Utf32Str::new("Foo::Bar", &mut haystack_buf);
+    let needle = Utf32Str::new("bar", &mut needle_buf);
+    assert!(default.fuzzy_match(haystack, needle) > matcher.fuzzy_match(haystack, needle));
+
+    let delimiters = Delimiters::new("→a/→".chars());
+    assert!(delimiters.contains('→') && delimiters.contains('/') && !delimiters.contains('、'));
+    assert_eq!(delimiters, Delimiters::new("a/→".chars()));
+    assert_eq!(Delimiters::from_ascii(b"/a"), Delimiters::new("a/".chars()));
+
+    // `match_paths` can be used in a const context and keeps non-ASCII delimiters
+    const PATHS: Config = Config::DEFAULT.match_paths();
+    assert!(PATHS.delimiters().contains('/') && !PATHS.delimiters().contains(':'));
+    let config = Config::DEFAULT
+        .with_delimiters(Delimiters::new(":→".chars()))
+        .match_paths();
+    assert!(config.delimiters().contains('→') && !config.delimiters().contains(':'));
+}
+
+#[cfg(feature = "unicode-transliteration")]
+#[test]
+fn test_transliteration() {
+    let mut default = Matcher::new(Config::DEFAULT);
+    let mut config = Config::DEFAULT;
+    config.transliterate = true;
+    let mut matcher = Matcher::new(config);
+    let mut needle_buf = Vec::new();
+    let mut haystack_buf = Vec::new();
+    let mut indices = Vec::new();
+    for (haystack, needle, expected) in [
+        ("Объект.txt", "obekt", &[0, 1, 3, 4, 5][..]),
+        ("Αθήνα", "athina", &[0, 1, 2, 3, 4]),
+        ("Щука", "shchuka", &[0, 1, 2, 3]),
+        ("dir/Мир", "dmir", &[0, 4, 5, 6]),
+    ] {
+        let haystack = Utf32Str::new(haystack, &mut haystack_buf);
+        let needle = Utf32Str::new(needle, &mut needle_buf);
+        assert_eq!(default.fuzzy_match(haystack, needle), None);
+        indices.clear();
+        let score = matcher.fuzzy_indices(haystack, needle, &mut indices);
+        assert_eq!(matcher.fuzzy_match(haystack, needle), score);
+        assert!(score.is_some());
+        assert_eq!(indices, expected, "{needle:?} {haystack:?}");
+    }
+    let haystack = Utf32Str::new("борщ", &mut haystack_buf);
+    let needle = Utf32Str::new("shch", &mut needle_buf);
+    indices.clear();
+    assert!(matcher
+        .substring_indices(haystack, needle, &mut indices)
+        .is_some());
+    assert_eq!(indices, [3]);
+    indices.clear();
+    assert!(matcher
+        .postfix_indices(haystack, needle, &mut indices)
+        .is_some());
+    assert_eq!(indices, [3]);
+    let needle = Utf32Str::new("borshch", &mut needle_buf);
+    assert!(matcher.exact_match(haystack, needle).is_some());
+    // a transliterated haystack no longer matches the original chars
+    let needle = Utf32Str::new("борщ", &mut needle_buf);
+    assert_eq!(matcher.fuzzy_match(haystack, needle), None);
+}
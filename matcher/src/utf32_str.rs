@@ -2,7 +2,7 @@
 mod tests;
 
 use std::borrow::Cow;
-use std::ops::{Bound, RangeBounds};
+use std::ops::{Bound, Range, RangeBounds};
 use std::{fmt, slice};
 
 use memchr::memmem;
@@ -21,6 +21,78 @@ fn has_ascii_graphemes(string: &str) -> bool {
     string.is_ascii() && memmem::find(string.as_bytes(), b"\r\n").is_none()
 }
 
+/// Returns the length of each grapheme of `text` in UTF-8 bytes or UTF-16
+/// code units.
+fn grapheme_lens(text: &str, utf16: bool) -> impl Iterator<Item = usize> + '_ {
+    #[cfg(feature = "unicode-segmentation")]
+    let graphemes = unicode_segmentation::UnicodeSegmentation::graphemes(text, true);
+    #[cfg(not(feature = "unicode-segmentation"))]
+    let graphemes = text.char_indices().map(|(i, c)| &text[i..i + c.len_utf8()]);
+    graphemes.map(move |grapheme| {
+        if utf16 {
+            grapheme.encode_utf16().count()
+        } else {
+            grapheme.len()
+        }
+    })
+}
+
+/// Pushes the chars of each grapheme of `text` to `chars` and the offsets at
+/// which the graphemes start (followed by the total number of chars) to
+/// `offsets`. Returns whether any grapheme consists of multiple chars.
+#[cfg(feature = "unicode-segmentation")]
+fn push_grapheme_clusters(text: &str, chars: &mut Vec<char>, offsets: &mut Vec<u32>) -> bool {
+    for grapheme in chars::grapheme_clusters(text) {
+        offsets.push(chars.len() as u32);
+        chars.extend(grapheme.chars());
+    }
+    offsets.push(chars.len() as u32);
+    chars.len() + 1 != offsets.len()
+}
+
+/// Creates a slice of the graphemes `start..end` of a `Clusters` string.
+#[inline]
+fn slice_clusters<'a>(
+    chars: &'a [char],
+    offsets: &'a [u32],
+    start: usize,
+    end: usize,
+) -> Utf32Str<'a> {
+    let base = offsets[0];
+    Utf32Str::Clusters {
+        chars: &chars[(offsets[start] - base) as usize..(offsets[end] - base) as usize],
+        offsets: &offsets[start..=end],
+    }
+}
+
+/// Converts sorted grapheme `indices` to ranges of the graphemes (with the
+/// lengths `lens`) that they cover. Adjacent indices are coalesced.
+fn coalesce_ranges(indices: &[u32], lens: impl Iterator<Item = usize>) -> Vec<Range<usize>> {
+    let mut graphemes = lens
+        .scan(0, |offset, len| {
+            let start = *offset;
+            *offset += len;
+            Some(start..*offset)
+        })
+        .enumerate();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut prev = None;
+    for &idx in indices {
+        if prev == Some(idx) {
+            continue;
+        }
+        prev = Some(idx);
+        let (_, range) = graphemes
+            .find(|&(i, _)| i == idx as usize)
+            .expect("match indices must be sorted and in bounds");
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range),
+        }
+    }
+    ranges
+}
+
 /// A UTF-32 encoded (char array) string that is used as an input to (fuzzy) matching.
 ///
 /// This is mostly intended as an internal string type, but some methods are exposed for
@@ -48,6 +120,7 @@ fn has_ascii_graphemes(string: &str) -> bool {
 /// # use nucleo_matcher::Utf32String;
 /// assert_eq!(Utf32String::from("u\u{0308}").to_string(), "u");
 /// ```
+/// The `Clusters` variant keeps these graphemes intact instead.
 ///
 /// ### Indexing is done by grapheme
 /// Indexing into a string is done by grapheme rather than by codepoint.
@@ -99,6 +172,18 @@ pub enum Utf32Str<'a> {
     Ascii(&'a [u8]),
     /// A string represented as an array of unicode codepoints (basically UTF-32).
     Unicode(&'a [char]),
+    /// A string represented as an array of unicode codepoints where graphemes
+    /// may consist of multiple codepoints. Only identical graphemes match
+    /// each other, so `👍🏽` doesn't match `👍🏿`. Case matching and
+    /// normalization don't apply to graphemes with multiple codepoints.
+    Clusters {
+        /// The codepoints of all graphemes.
+        chars: &'a [char],
+        /// The (increasing) offsets in `chars` at which the graphemes start,
+        /// followed by the end of the last grapheme. Grapheme `i` consists of
+        /// `chars[offsets[i] - offsets[0]..offsets[i + 1] - offsets[0]]`.
+        offsets: &'a [u32],
+    },
 }
 
 impl<'a> Utf32Str<'a> {
@@ -113,12 +198,39 @@ impl<'a> Utf32Str<'a> {
         }
     }
 
+    /// Like [`new`](Utf32Str::new) but keeps graphemes that consist of
+    /// multiple chars intact (producing the `Clusters` variant if there are
+    /// any). The grapheme offsets are stored in `offsets`. Patterns must use
+    /// [`Normalization::SmartClusters`](crate::pattern::Normalization::SmartClusters)
+    /// to match these graphemes.
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn new_grapheme_clusters(
+        str: &'a str,
+        buf: &'a mut Vec<char>,
+        offsets: &'a mut Vec<u32>,
+    ) -> Self {
+        if has_ascii_graphemes(str) {
+            return Utf32Str::Ascii(str.as_bytes());
+        }
+        buf.clear();
+        offsets.clear();
+        if push_grapheme_clusters(str, buf, offsets) {
+            Utf32Str::Clusters {
+                chars: buf,
+                offsets,
+            }
+        } else {
+            Utf32Str::Unicode(buf)
+        }
+    }
+
     /// Returns the number of characters in this string.
     #[inline]
     pub fn len(self) -> usize {
         match self {
             Utf32Str::Unicode(codepoints) => codepoints.len(),
             Utf32Str::Ascii(ascii_bytes) => ascii_bytes.len(),
+            Utf32Str::Clusters { offsets, .. } => offsets.len().saturating_sub(1),
         }
     }
 
@@ -128,6 +240,7 @@ impl<'a> Utf32Str<'a> {
         match self {
             Utf32Str::Unicode(codepoints) => codepoints.is_empty(),
             Utf32Str::Ascii(ascii_bytes) => ascii_bytes.is_empty(),
+            Utf32Str::Clusters { offsets, .. } => offsets.len() <= 1,
         }
     }
 
@@ -148,6 +261,7 @@ impl<'a> Utf32Str<'a> {
         match self {
             Utf32Str::Ascii(bytes) => Utf32Str::Ascii(&bytes[start..end]),
             Utf32Str::Unicode(codepoints) => Utf32Str::Unicode(&codepoints[start..end]),
+            Utf32Str::Clusters { chars, offsets } => slice_clusters(chars, offsets, start, end),
         }
     }
 
@@ -163,6 +277,7 @@ impl<'a> Utf32Str<'a> {
                 .iter()
                 .position(|c| !c.is_whitespace())
                 .unwrap_or(0),
+            Utf32Str::Clusters { .. } => self.chars().position(|c| !c.is_whitespace()).unwrap_or(0),
         }
     }
 
@@ -180,6 +295,11 @@ impl<'a> Utf32Str<'a> {
                 .rev()
                 .position(|c| !c.is_whitespace())
                 .unwrap_or(0),
+            Utf32Str::Clusters { .. } => self
+                .chars()
+                .rev()
+                .position(|c| !c.is_whitespace())
+                .unwrap_or(0),
         }
     }
 
@@ -200,6 +320,7 @@ impl<'a> Utf32Str<'a> {
         match self {
             Utf32Str::Ascii(bytes) => Utf32Str::Ascii(&bytes[start..end]),
             Utf32Str::Unicode(codepoints) => Utf32Str::Unicode(&codepoints[start..end]),
+            Utf32Str::Clusters { chars, offsets } => slice_clusters(chars, offsets, start, end),
         }
     }
 
@@ -212,14 +333,29 @@ impl<'a> Utf32Str<'a> {
         matches!(self, Utf32Str::Ascii(_))
     }
 
-    /// Returns the `n`th character in this string, zero-indexed
+    /// Returns the `n`th character in this string, zero-indexed. For the
+    /// `Clusters` variant this is the first char of the `n`th grapheme.
     pub fn get(self, n: u32) -> char {
         match self {
             Utf32Str::Ascii(bytes) => bytes[n as usize] as char,
             Utf32Str::Unicode(codepoints) => codepoints[n as usize],
+            Utf32Str::Clusters { chars, offsets } => {
+                chars[(offsets[n as usize] - offsets[0]) as usize]
+            }
         }
     }
 
+    /// Returns the `n`th grapheme of this string if it consists of multiple
+    /// chars.
+    pub(crate) fn cluster(self, n: usize) -> Option<&'a [char]> {
+        let Utf32Str::Clusters { chars, offsets } = self else {
+            return None;
+        };
+        let start = (offsets[n] - offsets[0]) as usize;
+        let end = (offsets[n + 1] - offsets[0]) as usize;
+        (end - start > 1).then(|| &chars[start..end])
+    }
+
     /// Returns the last character in this string.
     ///
     /// Panics if the string is empty.
@@ -227,6 +363,7 @@ impl<'a> Utf32Str<'a> {
         match self {
             Utf32Str::Ascii(bytes) => bytes[bytes.len() - 1] as char,
             Utf32Str::Unicode(codepoints) => codepoints[codepoints.len() - 1],
+            Utf32Str::Clusters { .. } => self.get(self.len() as u32 - 1),
         }
     }
 
@@ -237,14 +374,83 @@ impl<'a> Utf32Str<'a> {
         match self {
             Utf32Str::Ascii(bytes) => bytes[0] as char,
             Utf32Str::Unicode(codepoints) => codepoints[0],
+            Utf32Str::Clusters { chars, .. } => chars[0],
         }
     }
 
-    /// Returns an iterator over the characters in this string
+    /// Returns an iterator over the characters in this string. For the
+    /// `Clusters` variant only the first char of each grapheme is returned.
     pub fn chars(self) -> Chars<'a> {
         match self {
             Utf32Str::Ascii(bytes) => Chars::Ascii(bytes.iter()),
             Utf32Str::Unicode(codepoints) => Chars::Unicode(codepoints.iter()),
+            Utf32Str::Clusters { chars, offsets } => Chars::Clusters {
+                chars,
+                base: offsets.first().copied().unwrap_or(0),
+                offsets: offsets[..self.len()].iter(),
+            },
+        }
+    }
+
+    /// Returns an iterator over all chars of this string, including the
+    /// trailing chars of graphemes that consist of multiple chars.
+    fn codepoints(self) -> Chars<'a> {
+        match self {
+            Utf32Str::Clusters { chars, .. } => Chars::Unicode(chars.iter()),
+            _ => self.chars(),
+        }
+    }
+
+    /// Converts match `indices` (as computed by the `*_indices` functions of
+    /// the [`Matcher`](crate::Matcher)) into the UTF-8 byte ranges of `text`
+    /// that they cover, for example to highlight them. Adjacent indices are
+    /// coalesced into a single range and duplicate indices are ignored.
+    ///
+    /// `text` must be the string that this `Utf32Str` was created from.
+    ///
+    /// ```
+    /// # use nucleo_matcher::Utf32Str;
+    /// let text = "äbc d";
+    /// let mut buf = Vec::new();
+    /// let haystack = Utf32Str::new(text, &mut buf);
+    /// assert_eq!(haystack.byte_ranges(text, &[0, 1, 4]), [0..3, 5..6]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indices` is not sorted or contains an index that is out of
+    /// bounds.
+    pub fn byte_ranges(self, text: &str, indices: &[u32]) -> Vec<Range<usize>> {
+        match self {
+            Utf32Str::Ascii(bytes) => coalesce_ranges(indices, bytes.iter().map(|_| 1)),
+            Utf32Str::Unicode(_) | Utf32Str::Clusters { .. } => {
+                coalesce_ranges(indices, grapheme_lens(text, false))
+            }
+        }
+    }
+
+    /// Like [`byte_ranges`](Utf32Str::byte_ranges) but returns ranges of
+    /// UTF-16 code units (as used by the language server protocol for
+    /// example).
+    ///
+    /// ```
+    /// # use nucleo_matcher::Utf32Str;
+    /// let text = "𝔞bc d";
+    /// let mut buf = Vec::new();
+    /// let haystack = Utf32Str::new(text, &mut buf);
+    /// assert_eq!(haystack.utf16_ranges(text, &[0, 1, 4]), [0..3, 5..6]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indices` is not sorted or contains an index that is out of
+    /// bounds.
+    pub fn utf16_ranges(self, text: &str, indices: &[u32]) -> Vec<Range<usize>> {
+        match self {
+            Utf32Str::Ascii(bytes) => coalesce_ranges(indices, bytes.iter().map(|_| 1)),
+            Utf32Str::Unicode(_) | Utf32Str::Clusters { .. } => {
+                coalesce_ranges(indices, grapheme_lens(text, true))
+            }
         }
     }
 }
@@ -252,7 +458,13 @@ impl<'a> Utf32Str<'a> {
 impl fmt::Debug for Utf32Str<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "\"")?;
-        for c in self.chars() {
+        for (i, c) in self.chars().enumerate() {
+            if let Some(cluster) = self.cluster(i) {
+                // escape the cluster as a whole so that combining chars aren't escaped
+                let cluster: String = cluster.iter().collect();
+                write!(f, "{}", cluster.escape_debug())?;
+                continue;
+            }
             for c in c.escape_debug() {
                 write!(f, "{c}")?
             }
@@ -263,7 +475,7 @@ impl fmt::Debug for Utf32Str<'_> {
 
 impl fmt::Display for Utf32Str<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for c in self.chars() {
+        for c in self.codepoints() {
             write!(f, "{c}")?
         }
         Ok(())
@@ -273,6 +485,11 @@ impl fmt::Display for Utf32Str<'_> {
 pub enum Chars<'a> {
     Ascii(slice::Iter<'a, u8>),
     Unicode(slice::Iter<'a, char>),
+    Clusters {
+        chars: &'a [char],
+        base: u32,
+        offsets: slice::Iter<'a, u32>,
+    },
 }
 
 impl Iterator for Chars<'_> {
@@ -282,6 +499,13 @@ impl Iterator for Chars<'_> {
         match self {
             Chars::Ascii(iter) => iter.next().map(|&c| c as char),
             Chars::Unicode(iter) => iter.next().copied(),
+            Chars::Clusters {
+                chars,
+                base,
+                offsets,
+            } => offsets
+                .next()
+                .map(|&offset| chars[(offset - *base) as usize]),
         }
     }
 }
@@ -291,6 +515,13 @@ impl DoubleEndedIterator for Chars<'_> {
         match self {
             Chars::Ascii(iter) => iter.next_back().map(|&c| c as char),
             Chars::Unicode(iter) => iter.next_back().copied(),
+            Chars::Clusters {
+                chars,
+                base,
+                offsets,
+            } => offsets
+                .next_back()
+                .map(|&offset| chars[(offset - *base) as usize]),
         }
     }
 }
@@ -305,6 +536,15 @@ pub enum Utf32String {
     Ascii(Box<str>),
     /// A string represented as an array of unicode codepoints (basically UTF-32).
     Unicode(Box<[char]>),
+    /// A string represented as an array of unicode codepoints where graphemes
+    /// may consist of multiple codepoints, see [`Utf32Str::Clusters`].
+    Clusters {
+        /// The codepoints of all graphemes.
+        chars: Box<[char]>,
+        /// The offsets in `chars` at which the graphemes start, followed by
+        /// the end of the last grapheme.
+        offsets: Box<[u32]>,
+    },
 }
 
 impl Default for Utf32String {
@@ -314,12 +554,42 @@ impl Default for Utf32String {
 }
 
 impl Utf32String {
+    /// Like the `From<&str>` implementation but keeps graphemes that consist
+    /// of multiple chars intact (producing the `Clusters` variant if there
+    /// are any). Patterns must use
+    /// [`Normalization::SmartClusters`](crate::pattern::Normalization::SmartClusters)
+    /// to match these graphemes. Unlike other conversions this one round-trips:
+    ///
+    /// ```
+    /// # use nucleo_matcher::Utf32String;
+    /// let s = Utf32String::from_grapheme_clusters("u\u{0308}👍🏽");
+    /// assert_eq!(s.len(), 2);
+    /// assert_eq!(s.to_string(), "u\u{0308}👍🏽");
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn from_grapheme_clusters(value: &str) -> Self {
+        if has_ascii_graphemes(value) {
+            return Self::Ascii(value.to_owned().into_boxed_str());
+        }
+        let mut chars = Vec::new();
+        let mut offsets = Vec::new();
+        if push_grapheme_clusters(value, &mut chars, &mut offsets) {
+            Self::Clusters {
+                chars: chars.into_boxed_slice(),
+                offsets: offsets.into_boxed_slice(),
+            }
+        } else {
+            Self::Unicode(chars.into_boxed_slice())
+        }
+    }
+
     /// Returns the number of characters in this string.
     #[inline]
     pub fn len(&self) -> usize {
         match self {
             Utf32String::Unicode(codepoints) => codepoints.len(),
             Utf32String::Ascii(ascii_bytes) => ascii_bytes.len(),
+            Utf32String::Clusters { offsets, .. } => offsets.len().saturating_sub(1),
         }
     }
 
@@ -329,13 +599,14 @@ impl Utf32String {
         match self {
             Utf32String::Unicode(codepoints) => codepoints.is_empty(),
             Utf32String::Ascii(ascii_bytes) => ascii_bytes.is_empty(),
+            Utf32String::Clusters { offsets, .. } => offsets.len() <= 1,
         }
     }
 
     /// Creates a slice with a string that contains the characters in
     /// the specified **character range**.
     #[inline]
-    pub fn slice(&self, range: impl RangeBounds<usize>) -> Utf32Str {
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Utf32Str<'_> {
         let start = match range.start_bound() {
             Bound::Included(&start) => start,
             Bound::Excluded(&start) => start + 1,
@@ -349,13 +620,26 @@ impl Utf32String {
         match self {
             Utf32String::Ascii(bytes) => Utf32Str::Ascii(&bytes.as_bytes()[start..end]),
             Utf32String::Unicode(codepoints) => Utf32Str::Unicode(&codepoints[start..end]),
+            Utf32String::Clusters { chars, offsets } => slice_clusters(chars, offsets, start, end),
         }
     }
 
+    /// Converts match `indices` into the UTF-8 byte ranges of `text`, see
+    /// [`Utf32Str::byte_ranges`].
+    pub fn byte_ranges(&self, text: &str, indices: &[u32]) -> Vec<Range<usize>> {
+        self.slice(..).byte_ranges(text, indices)
+    }
+
+    /// Converts match `indices` into the UTF-16 code unit ranges of `text`,
+    /// see [`Utf32Str::utf16_ranges`].
+    pub fn utf16_ranges(&self, text: &str, indices: &[u32]) -> Vec<Range<usize>> {
+        self.slice(..).utf16_ranges(text, indices)
+    }
+
     /// Same as `slice` but accepts a u32 range for convenience since
     /// those are the indices returned by the matcher.
     #[inline]
-    pub fn slice_u32(&self, range: impl RangeBounds<u32>) -> Utf32Str {
+    pub fn slice_u32(&self, range: impl RangeBounds<u32>) -> Utf32Str<'_> {
         let start = match range.start_bound() {
             Bound::Included(&start) => start,
             Bound::Excluded(&start) => start + 1,
@@ -373,6 +657,9 @@ impl Utf32String {
             Utf32String::Unicode(codepoints) => {
                 Utf32Str::Unicode(&codepoints[start as usize..end as usize])
             }
+            Utf32String::Clusters { chars, offsets } => {
+                slice_clusters(chars, offsets, start as usize, end as usize)
+            }
         }
     }
 }
@@ -426,3 +713,75 @@ impl fmt::Display for Utf32String {
         write!(f, "{}", self.slice(..))
     }
 }
+
+/// A haystack and needle where every grapheme that consists of multiple chars
+/// (see [`Utf32Str::Clusters`]) was replaced by a single char, so that they can
+/// be passed to the matcher. Identical graphemes of the haystack and needle
+/// are replaced by the same char, all other graphemes of the haystack by a
+/// char that doesn't occur in the needle. The indices of the graphemes don't
+/// change.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ReplacedClusters {
+    pub(crate) haystack: Vec<char>,
+    pub(crate) needle: Vec<char>,
+    /// The index of the first occurrence of each distinct multi char grapheme
+    /// in the needle and the char that replaces it.
+    clusters: Vec<(usize, char)>,
+}
+
+impl ReplacedClusters {
+    pub(crate) fn replace(&mut self, haystack: Utf32Str<'_>, needle: Utf32Str<'_>) {
+        self.haystack.clear();
+        self.needle.clear();
+        self.clusters.clear();
+        // the replacements are taken from the supplementary private use area B
+        // (or the rest of the unicode range should the needle contain those)
+        let mut replacements = (0x10_0000..=0x10_FFFF)
+            .chain(0..0x10_0000)
+            .filter_map(char::from_u32)
+            .filter(|&c| {
+                (0..needle.len()).all(|i| needle.cluster(i).is_some() || needle.get(i as u32) != c)
+            });
+        for i in 0..needle.len() {
+            let c = match needle.cluster(i) {
+                Some(cluster) => match self.replacement(needle, cluster) {
+                    Some(c) => c,
+                    None => {
+                        let c = replacements.next().expect("needle is too long");
+                        self.clusters.push((i, c));
+                        c
+                    }
+                },
+                None => needle.get(i as u32),
+            };
+            self.needle.push(c);
+        }
+        let unmatched = replacements.next().expect("needle is too long");
+        for i in 0..haystack.len() {
+            let c = match haystack.cluster(i) {
+                Some(cluster) => self.replacement(needle, cluster).unwrap_or(unmatched),
+                None => {
+                    let c = haystack.get(i as u32);
+                    if self
+                        .clusters
+                        .iter()
+                        .any(|&(_, replacement)| replacement == c)
+                    {
+                        unmatched
+                    } else {
+                        c
+                    }
+                }
+            };
+            self.haystack.push(c);
+        }
+    }
+
+    /// Returns the char that replaces the multi char grapheme `cluster` of the needle.
+    fn replacement(&self, needle: Utf32Str<'_>, cluster: &[char]) -> Option<char> {
+        self.clusters
+            .iter()
+            .find(|&&(i, _)| needle.cluster(i) == Some(cluster))
+            .map(|&(_, replacement)| replacement)
+    }
+}
@@ -42,3 +42,70 @@ fn test_grapheme_truncation() {
     assert_eq!(s.slice(..).get(0), 'u');
     assert_eq!(s.slice(..).get(1), '\n');
 }
+
+#[cfg(feature = "unicode-segmentation")]
+#[test]
+fn test_grapheme_clusters() {
+    // ascii and single char graphemes are preserved
+    let s = Utf32String::from_grapheme_clusters("ab");
+    assert!(s.slice(..).is_ascii());
+    let s = Utf32String::from_grapheme_clusters("aü\r\n");
+    assert_eq!(s.slice(..).chars().collect::<String>(), "aü\n");
+
+    // distinct clusters remain distinct
+    let medium = Utf32String::from_grapheme_clusters("👍🏽");
+    let dark = Utf32String::from_grapheme_clusters("👍🏿");
+    assert_eq!(medium.len(), 1);
+    assert_ne!(medium, dark);
+    assert_eq!(medium, Utf32String::from_grapheme_clusters("👍🏽"));
+    assert_eq!(Utf32String::from("👍🏽"), Utf32String::from("👍🏿"));
+
+    let mut buffer = Vec::new();
+    let mut offsets = Vec::new();
+    let s = Utf32Str::new_grapheme_clusters("u\u{0308}👍🏽x", &mut buffer, &mut offsets);
+    assert_eq!(s.len(), 3);
+    assert_eq!(s.to_string(), "u\u{0308}👍🏽x");
+    assert_eq!(format!("{s:?}"), "\"u\u{308}👍🏽x\"");
+    assert_eq!(s.chars().rev().collect::<String>(), "x👍u");
+    assert_eq!(s.get(1), '👍');
+    assert_eq!(s.last(), 'x');
+    // slices keep the clusters intact
+    assert_eq!(s.slice(1..).to_string(), "👍🏽x");
+    assert_eq!(s.slice(1..2).to_string(), "👍🏽");
+    assert_eq!(s.slice(1..2).slice_u32(1..).len(), 0);
+
+    // chars of the private use area are not mistaken for clusters
+    let s = Utf32String::from_grapheme_clusters("\u{100000}👍🏽");
+    assert_eq!(s.to_string(), "\u{100000}👍🏽");
+}
+
+#[test]
+fn test_byte_and_utf16_ranges() {
+    // a single range in an array looks like a mistake to clippy
+    let range = |start, end| start..end;
+    let mut buffer = Vec::new();
+    let text = "foo bar";
+    let s = Utf32Str::new(text, &mut buffer);
+    assert!(s.byte_ranges(text, &[]).is_empty());
+    assert_eq!(s.byte_ranges(text, &[0, 1, 2, 4, 6]), [0..3, 4..5, 6..7]);
+    assert_eq!(s.utf16_ranges(text, &[0, 1, 2, 4, 6]), [0..3, 4..5, 6..7]);
+    // duplicate indices are ignored
+    assert_eq!(s.byte_ranges(text, &[1, 1, 2]), [range(1, 3)]);
+
+    // `ü` is 2 bytes but a single UTF-16 code unit, `𝔞` is 4 bytes and a
+    // surrogate pair, `u\u{0308}` is a single grapheme of two chars
+    let text = "ü𝔞u\u{0308}\r\nx";
+    let s = Utf32String::from(text);
+    assert_eq!(s.len(), 5);
+    assert_eq!(s.byte_ranges(text, &[0, 1]), [range(0, 6)]);
+    assert_eq!(s.utf16_ranges(text, &[0, 1]), [range(0, 3)]);
+    assert_eq!(s.byte_ranges(text, &[2, 4]), [6..9, 11..12]);
+    assert_eq!(s.utf16_ranges(text, &[2, 3, 4]), [range(3, 8)]);
+}
+
+#[test]
+#[should_panic]
+fn test_byte_ranges_out_of_bounds() {
+    let text = "äb";
+    Utf32String::from(text).byte_ranges(text, &[2]);
+}
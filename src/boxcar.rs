@@ -25,7 +25,7 @@ use std::alloc::Layout;
 use std::cell::UnsafeCell;
 use std::fmt::Debug;
 use std::mem::MaybeUninit;
-use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicU32, AtomicU64, AtomicU8, Ordering};
 use std::{ptr, slice};
 
 use crate::{Item, Utf32String};
@@ -33,13 +33,24 @@ use crate::{Item, Utf32String};
 const BUCKETS: u32 = u32::BITS - SKIP_BUCKET;
 const MAX_ENTRIES: u32 = u32::MAX - SKIP;
 
-/// A lock-free, append-only vector.
+/// The entry has not been initialized yet.
+const PENDING: u8 = 0;
+/// The entry is initialized.
+const ACTIVE: u8 = 1;
+/// The entry is initialized but was removed. Its value is only dropped
+/// together with the vector since there may still be references to it.
+const REMOVED: u8 = 2;
+
+/// A lock-free, append-only vector. Elements can't be removed but they can be
+/// marked as removed (tombstoned).
 pub(crate) struct Vec<T> {
     /// a counter used to retrieve a unique index to push to.
     ///
     /// this value may be more than the true length as it will
     /// be incremented before values are actually stored.
     inflight: AtomicU64,
+    /// the number of elements that were marked as removed
+    removed: AtomicU32,
     /// buckets of length 32, 64 .. 2^31
     buckets: [Bucket<T>; BUCKETS as usize],
     /// the number of matcher columns in this vector, its absolutely critical that
@@ -68,6 +79,7 @@ impl<T> Vec<T> {
         Vec {
             buckets: buckets.map(Bucket::new),
             inflight: AtomicU64::new(0),
+            removed: AtomicU32::new(0),
             columns,
         }
     }
@@ -83,11 +95,18 @@ impl<T> Vec<T> {
             .min(MAX_ENTRIES as u64) as u32
     }
 
+    /// Returns the number of elements that were marked as removed.
+    #[inline]
+    pub fn removed(&self) -> u32 {
+        self.removed.load(Ordering::Acquire)
+    }
+
     // Returns a reference to the element at the given index.
     //
     // # Safety
     //
-    // Entry at `index` must be initialized.
+    // Entry at `index` must be initialized. Entries that were removed remain
+    // initialized.
     #[inline]
     pub unsafe fn get_unchecked(&self, index: u32) -> Item<'_, T> {
         let location = Location::of(index);
@@ -104,13 +123,113 @@ impl<T> Vec<T> {
             // thread synchronization (essentially acting as a memory barrier)
             // since the caller must only guarantee that he has observed active on any thread
             // but the current thread might still have an old value cached (although unlikely)
-            let _ = (*entry).active.load(Ordering::Acquire);
+            let _ = (*entry).state.load(Ordering::Acquire);
             Entry::read(entry, self.columns)
         }
     }
 
     /// Returns a reference to the element at the given index.
+    ///
+    /// Returns `None` if the element is not initialized yet or was removed.
     pub fn get(&self, index: u32) -> Option<Item<'_, T>> {
+        let entry = self.entry(index)?;
+        unsafe {
+            // safety: the entry is active
+            ((*entry).state.load(Ordering::Acquire) == ACTIVE)
+                .then(|| Entry::read(entry, self.columns))
+        }
+    }
+
+    /// Returns the key of the element at the given index, `None` if the
+    /// element has no key or is not active.
+    pub fn key(&self, index: u32) -> Option<u64> {
+        let entry = self.entry(index)?;
+        unsafe {
+            // safety: the key is initialized together with the entry
+            if (*entry).state.load(Ordering::Acquire) != ACTIVE {
+                return None;
+            }
+            (*entry).key
+        }
+    }
+
+    /// Returns whether the element at the given index was marked as removed.
+    pub fn is_removed(&self, index: u32) -> bool {
+        match self.entry(index) {
+            Some(entry) => unsafe { (*entry).state.load(Ordering::Acquire) == REMOVED },
+            None => false,
+        }
+    }
+
+    /// Marks the element at the given index as removed. Returns `false` if
+    /// the element is not initialized yet or was already removed.
+    ///
+    /// The element itself is only dropped together with the vector since
+    /// other threads may still hold references to it.
+    pub fn remove(&self, index: u32) -> bool {
+        let Some(entry) = self.entry(index) else {
+            return false;
+        };
+        let removed = unsafe {
+            (*entry)
+                .state
+                .compare_exchange(ACTIVE, REMOVED, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+        };
+        if removed {
+            self.removed.fetch_add(1, Ordering::Release);
+        }
+        removed
+    }
+
+    /// Moves all active elements into a new vector (preserving their order)
+    /// and drops the removed elements. Returns the new vector and the new
+    /// index of every element of this vector, `u32::MAX` if the element was
+    /// dropped or never initialized.
+    pub fn compact(self) -> (Vec<T>, std::vec::Vec<u32>) {
+        let count = self.count();
+        let columns = self.columns;
+        let compacted = Vec::with_capacity(count - self.removed(), columns);
+        let mut new_indices = std::vec::Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let Some(entry) = self.entry(index) else {
+                new_indices.push(u32::MAX);
+                continue;
+            };
+            // safety: we own the vector so nothing else can access the entries
+            unsafe {
+                let state = (*entry).state.get_mut();
+                let new_index = match *state {
+                    ACTIVE => {
+                        let value = ptr::read((*(*entry).slot.get()).as_ptr());
+                        let old_cols = Entry::matcher_cols_raw(entry, columns);
+                        compacted.push_with_key(value, (*entry).key, |_, cols| {
+                            for (col, old_col) in cols.iter_mut().zip(old_cols) {
+                                *col = ptr::read((*old_col.get()).as_ptr());
+                            }
+                        })
+                    }
+                    REMOVED => {
+                        ptr::drop_in_place((*(*entry).slot.get()).as_mut_ptr());
+                        for matcher_col in Entry::matcher_cols_raw(entry, columns) {
+                            ptr::drop_in_place((*matcher_col.get()).as_mut_ptr());
+                        }
+                        u32::MAX
+                    }
+                    _ => u32::MAX,
+                };
+                // the values were moved or dropped so they must not be
+                // dropped again when this vector is dropped
+                *state = PENDING;
+                new_indices.push(new_index);
+            }
+        }
+        (compacted, new_indices)
+    }
+
+    /// Returns a pointer to the entry at the given index, `None` if its
+    /// bucket is not allocated yet.
+    fn entry(&self, index: u32) -> Option<*mut Entry<T>> {
         let location = Location::of(index);
 
         unsafe {
@@ -127,18 +246,22 @@ impl<T> Vec<T> {
             }
 
             // safety: `location.entry` is always in bounds for it's bucket
-            let entry = Bucket::<T>::get(entries, location.entry, self.columns);
-
-            // safety: the entry is active
-            (*entry)
-                .active
-                .load(Ordering::Acquire)
-                .then(|| Entry::read(entry, self.columns))
+            Some(Bucket::<T>::get(entries, location.entry, self.columns))
         }
     }
 
     /// Appends an element to the back of the vector.
     pub fn push(&self, value: T, fill_columns: impl FnOnce(&T, &mut [Utf32String])) -> u32 {
+        self.push_with_key(value, None, fill_columns)
+    }
+
+    /// Appends an element with an (optional) key to the back of the vector.
+    pub fn push_with_key(
+        &self,
+        value: T,
+        key: Option<u64>,
+        fill_columns: impl FnOnce(&T, &mut [Utf32String]),
+    ) -> u32 {
         let index = self.inflight.fetch_add(1, Ordering::Release);
         // the inflight counter is a `u64` to catch overflows of the vector'scapacity
         let index: u32 = index.try_into().expect("overflowed maximum capacity");
@@ -169,15 +292,16 @@ impl<T> Vec<T> {
             // 1. it is impossible for another thread to attempt a `push`
             // to this location as we retrieved it from `inflight.fetch_add`
             //
-            // 2. any thread trying to `get` this entry will see `active == false`,
+            // 2. any thread trying to `get` this entry will see `state == PENDING`,
             // and will not try to access it
             for col in Entry::matcher_cols_raw(entry, self.columns) {
                 col.get().write(MaybeUninit::new(Utf32String::default()))
             }
             fill_columns(&value, Entry::matcher_cols_mut(entry, self.columns));
+            ptr::addr_of_mut!((*entry).key).write(key);
             (*entry).slot.get().write(MaybeUninit::new(value));
             // let other threads know that this entry is active
-            (*entry).active.store(true, Ordering::Release);
+            (*entry).state.store(ACTIVE, Ordering::Release);
         }
 
         index
@@ -265,8 +389,9 @@ impl<T> Vec<T> {
                     col.get().write(MaybeUninit::new(Utf32String::default()));
                 }
                 fill_columns(&v, Entry::matcher_cols_mut(entry, self.columns));
+                ptr::addr_of_mut!((*entry).key).write(None);
                 (*entry).slot.get().write(MaybeUninit::new(v));
-                (*entry).active.store(true, Ordering::Release);
+                (*entry).state.store(ACTIVE, Ordering::Release);
             }
         }
     }
@@ -338,6 +463,8 @@ impl<T> Drop for Vec<T> {
         }
     }
 }
+/// An element yielded by [`Iter`], `None` if the element is not initialized
+/// yet or was removed.
 type SnapshotItem<'v, T> = (u32, Option<Item<'v, T>>);
 
 pub struct Iter<'v, T> {
@@ -393,9 +520,7 @@ impl<'v, T> Iterator for Iter<'v, T> {
                 self.idx += 1;
 
                 let entry = unsafe {
-                    (*entry)
-                        .active
-                        .load(Ordering::Acquire)
+                    ((*entry).state.load(Ordering::Acquire) == ACTIVE)
                         .then(|| Entry::read(entry, self.vec.columns))
                 };
                 return Some((index, entry));
@@ -521,8 +646,8 @@ impl<T> Bucket<T> {
         }
 
         for i in 0..len {
-            let active = entries.add(i as usize * layout.size()) as *mut AtomicBool;
-            active.write(AtomicBool::new(false))
+            let state = entries.add(i as usize * layout.size()) as *mut AtomicU8;
+            state.write(AtomicU8::new(PENDING))
         }
         entries as *mut Entry<T>
     }
@@ -532,7 +657,7 @@ impl<T> Bucket<T> {
         let arr_layout = Self::layout(len, layout);
         for i in 0..len {
             let entry = Bucket::get(entries, i, cols);
-            if *(*entry).active.get_mut() {
+            if *(*entry).state.get_mut() != PENDING {
                 ptr::drop_in_place((*(*entry).slot.get()).as_mut_ptr());
                 for matcher_col in Entry::matcher_cols_raw(entry, cols) {
                     ptr::drop_in_place((*matcher_col.get()).as_mut_ptr());
@@ -557,7 +682,9 @@ impl<T> Bucket<T> {
 
 #[repr(C)]
 struct Entry<T> {
-    active: AtomicBool,
+    state: AtomicU8,
+    /// only initialized once the entry is not `PENDING` anymore
+    key: Option<u64>,
     slot: UnsafeCell<MaybeUninit<T>>,
     tail: [UnsafeCell<MaybeUninit<Utf32String>>; 0],
 }
@@ -775,6 +902,71 @@ mod tests {
         assert!(std::panic::catch_unwind(|| vec.extend(iter, |_, _| {})).is_err());
     }
 
+    #[test]
+    fn remove() {
+        let vec = Vec::<u32>::with_capacity(1, 1);
+        vec.extend(0..100, |_, _| {});
+        assert!(!vec.remove(100));
+        assert!(vec.remove(50));
+        assert!(!vec.remove(50));
+        assert_eq!(vec.count(), 100);
+        assert_eq!(vec.removed(), 1);
+        assert!(vec.get(50).is_none());
+        assert!(vec.is_removed(50));
+        assert!(!vec.is_removed(49));
+        assert!(!vec.is_removed(100));
+        // removed items stay initialized
+        assert_eq!(unsafe { *vec.get_unchecked(50).data }, 50);
+        let snapshot: std::vec::Vec<_> = unsafe { vec.snapshot(0) }
+            .filter_map(|(_, item)| Some(*item?.data))
+            .collect();
+        assert_eq!(snapshot.len(), 99);
+        assert!(!snapshot.contains(&50));
+    }
+
+    #[test]
+    fn compact() {
+        let vec = Vec::<String>::with_capacity(1, 2);
+        vec.extend((0..100).map(|i| i.to_string()), |value, cols| {
+            cols[0] = value.as_str().into();
+            cols[1] = "col".into();
+        });
+        for i in (0..100).step_by(3) {
+            assert!(vec.remove(i));
+        }
+        let (vec, new_indices) = vec.compact();
+        assert_eq!(vec.count(), 66);
+        assert_eq!(vec.removed(), 0);
+        assert_eq!(new_indices.len(), 100);
+        for (old, &new) in new_indices.iter().enumerate() {
+            if old % 3 == 0 {
+                assert_eq!(new, u32::MAX);
+                continue;
+            }
+            assert_eq!(new, old as u32 - old as u32 / 3 - 1);
+            let item = vec.get(new).unwrap();
+            assert_eq!(*item.data, old.to_string());
+            assert_eq!(item.matcher_columns[0].to_string(), old.to_string());
+            assert_eq!(item.matcher_columns[1].to_string(), "col");
+        }
+    }
+
+    #[test]
+    fn keys() {
+        let vec = Vec::<u32>::with_capacity(1, 1);
+        vec.push(0, |_, _| {});
+        vec.push_with_key(1, Some(42), |_, _| {});
+        vec.push_with_key(2, Some(43), |_, _| {});
+        assert_eq!(vec.key(0), None);
+        assert_eq!(vec.key(1), Some(42));
+        assert_eq!(vec.key(3), None);
+        assert!(vec.remove(1));
+        assert_eq!(vec.key(1), None);
+        let (vec, new_indices) = vec.compact();
+        assert_eq!(new_indices, [0, u32::MAX, 1]);
+        assert_eq!(vec.key(1), Some(43));
+    }
+
     // test |values| does not fit in the boxcar
     #[test]
     fn extend_over_max_capacity() {
@@ -0,0 +1,67 @@
+//! Async integration for [`Nucleo`], enabled with the `async` feature.
+//!
+//! Neither type depends on a specific runtime, they are woken by the same
+//! mechanism that invokes the `notify` callback passed to [`Nucleo::new`].
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::{Nucleo, Status};
+
+/// Future returned by [`Nucleo::changed`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Changed<'a, T: Sync + Send + 'static> {
+    nucleo: &'a mut Nucleo<T>,
+}
+
+impl<'a, T: Sync + Send + 'static> Changed<'a, T> {
+    pub(crate) fn new(nucleo: &'a mut Nucleo<T>) -> Self {
+        Changed { nucleo }
+    }
+}
+
+impl<T: Sync + Send + 'static> Future for Changed<'_, T> {
+    type Output = Status;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Status> {
+        self.get_mut().nucleo.poll_tick(cx)
+    }
+}
+
+/// Stream returned by [`Nucleo::status_stream`].
+#[must_use = "streams do nothing unless polled"]
+pub struct StatusStream<'a, T: Sync + Send + 'static> {
+    nucleo: &'a mut Nucleo<T>,
+    running: bool,
+}
+
+impl<'a, T: Sync + Send + 'static> StatusStream<'a, T> {
+    pub(crate) fn new(nucleo: &'a mut Nucleo<T>) -> Self {
+        StatusStream {
+            nucleo,
+            running: false,
+        }
+    }
+}
+
+impl<T: Sync + Send + 'static> Stream for StatusStream<'_, T> {
+    type Item = Status;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Status>> {
+        let this = self.get_mut();
+        loop {
+            let generation = this.nucleo.notify.generation();
+            let status = this.nucleo.tick(0);
+            if status.changed || status.running != this.running {
+                this.running = status.running;
+                return Poll::Ready(Some(status));
+            }
+            if this.nucleo.notify.register(cx.waker(), generation) {
+                return Poll::Pending;
+            }
+        }
+    }
+}
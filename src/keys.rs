@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Maps the keys of items (see [`Injector::push_with_key`](crate::Injector::push_with_key))
+/// to their index. If multiple items share a key the largest index wins.
+///
+/// The index is shared between the worker and the snapshot. To avoid cloning
+/// the entire map every time keys are added while the snapshot holds on to
+/// it, the index consists of immutable segments that are (at least) twice as
+/// large as the next segment. New keys are added as a new segment and merged
+/// with the smaller segments, so each key is only copied `O(log n)` times.
+///
+/// Removed items are not removed from the index, lookups have to skip them.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct KeyIndex {
+    segments: Vec<Arc<HashMap<u64, u32>>>,
+}
+
+impl KeyIndex {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the largest index of an item with the given `key`.
+    pub(crate) fn get(&self, key: u64) -> Option<u32> {
+        self.segments
+            .iter()
+            .filter_map(|segment| segment.get(&key).copied())
+            .max()
+    }
+
+    /// Adds the `(key, index)` pairs of `keys` to the index.
+    pub(crate) fn extend(&mut self, keys: impl Iterator<Item = (u64, u32)>) {
+        let mut segment = HashMap::new();
+        for (key, idx) in keys {
+            insert(&mut segment, key, idx);
+        }
+        if segment.is_empty() {
+            return;
+        }
+        while let Some(last) = self.segments.last() {
+            if last.len() > 2 * segment.len() {
+                break;
+            }
+            let last = self.segments.pop().unwrap();
+            let mut larger = Arc::try_unwrap(last).unwrap_or_else(|last| (*last).clone());
+            if larger.len() < segment.len() {
+                std::mem::swap(&mut larger, &mut segment);
+            }
+            for (key, idx) in segment {
+                insert(&mut larger, key, idx);
+            }
+            segment = larger;
+        }
+        self.segments.push(Arc::new(segment));
+    }
+
+    /// Maps the indices to `new_indices[idx]` and only keeps the keys whose
+    /// new index is smaller than `end`.
+    pub(crate) fn compact(&mut self, new_indices: &[u32], end: u32) {
+        let mut segment = HashMap::new();
+        for (&key, &idx) in self.segments.iter().flat_map(|segment| segment.iter()) {
+            let idx = new_indices[idx as usize];
+            if idx < end {
+                insert(&mut segment, key, idx);
+            }
+        }
+        self.segments.clear();
+        if !segment.is_empty() {
+            self.segments.push(Arc::new(segment));
+        }
+    }
+}
+
+fn insert(segment: &mut HashMap<u64, u32>, key: u64, idx: u32) {
+    let entry = segment.entry(key).or_insert(idx);
+    *entry = (*entry).max(idx);
+}
@@ -26,21 +26,32 @@ level crate also need better documentation and will likely see a few minor API
 changes in the future.
 
 */
-use std::ops::{Bound, RangeBounds};
-use std::sync::atomic::{self, AtomicBool, Ordering};
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Bound, Range, RangeBounds};
+use std::sync::atomic::{self, AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use parking_lot::Mutex;
 use rayon::ThreadPool;
 
+use crate::keys::KeyIndex;
+use crate::notify::Notify;
 use crate::pattern::MultiPattern;
 use crate::worker::Worker;
 pub use nucleo_matcher::{chars, Config, Matcher, Utf32Str, Utf32String};
+pub use selection::Selection;
 
 mod boxcar;
+#[cfg(feature = "async")]
+pub mod future;
+mod keys;
+mod notify;
 mod par_sort;
 pub mod pattern;
+mod selection;
 mod worker;
 
 #[cfg(test)]
@@ -57,8 +68,10 @@ pub struct Item<'a, T> {
 /// It's internally reference counted and can be cheaply cloned
 /// and sent across threads.
 pub struct Injector<T> {
-    items: Arc<boxcar::Vec<T>>,
-    notify: Arc<(dyn Fn() + Sync + Send)>,
+    /// Dropped manually so that the reference is released before the drop
+    /// of the injector is signaled (see the `Drop` impl).
+    items: ManuallyDrop<Arc<boxcar::Vec<T>>>,
+    notify: Arc<Notify>,
 }
 
 impl<T> Clone for Injector<T> {
@@ -75,7 +88,24 @@ impl<T> Injector<T> {
     /// This function is lock-free and wait-free.
     pub fn push(&self, value: T, fill_columns: impl FnOnce(&T, &mut [Utf32String])) -> u32 {
         let idx = self.items.push(value, fill_columns);
-        (self.notify)();
+        self.notify.notify();
+        idx
+    }
+
+    /// Appends an element with a caller supplied `key` to the list of
+    /// matched items. Unlike the returned index, the key can identify the
+    /// item across [restarts](Nucleo::restart) and
+    /// [compactions](Nucleo::compact), for example a hash of a file path.
+    /// Use [`Snapshot::item_index`] to find the current index of a key.
+    /// This function is lock-free and wait-free.
+    pub fn push_with_key(
+        &self,
+        key: u64,
+        value: T,
+        fill_columns: impl FnOnce(&T, &mut [Utf32String]),
+    ) -> u32 {
+        let idx = self.items.push_with_key(value, Some(key), fill_columns);
+        self.notify.notify();
         idx
     }
 
@@ -84,25 +114,76 @@ impl<T> Injector<T> {
     ///
     /// You should favor this function over `push` if at least one of the following is true:
     /// - the number of items you're adding can be computed beforehand and is typically larger
-    ///     than 1k
+    ///   than 1k
     /// - you're able to batch incoming items
     /// - you're adding items from multiple threads concurrently (this function results in less
-    ///     contention)
+    ///   contention)
     pub fn extend<I>(&self, values: I, fill_columns: impl Fn(&T, &mut [Utf32String]))
     where
         I: IntoIterator<Item = T> + ExactSizeIterator,
     {
         self.items.extend(values, fill_columns);
-        (self.notify)();
+        self.notify.notify();
+    }
+
+    /// Removes the item at the given index from the matcher. The item is
+    /// excluded from the [`Snapshot`] after the next [`tick`](Nucleo::tick)
+    /// and [`get`](Injector::get) immediately returns `None` for it. Indices
+    /// of the other items are not affected.
+    /// This function is lock-free.
+    ///
+    /// Returns `false` if the item is not initialized yet or was already
+    /// removed.
+    ///
+    /// # Note
+    ///
+    /// The item itself is only dropped once the matcher and all injectors
+    /// were dropped (or the matcher was [restarted](Nucleo::restart)) as
+    /// snapshots may still reference it.
+    pub fn remove(&self, index: u32) -> bool {
+        let removed = self.items.remove(index);
+        if removed {
+            self.notify.notify();
+        }
+        removed
+    }
+
+    /// Replaces the item at the given index by removing it (see
+    /// [`remove`](Injector::remove)) and appending `value` instead. Items
+    /// can't be modified in place since snapshots may reference them
+    /// concurrently, so the new item receives a new index which is returned.
+    /// The new item keeps the [key](Injector::push_with_key) of the old item.
+    /// This function is lock-free.
+    ///
+    /// Returns `None` (and drops `value`) if the item is not initialized yet
+    /// or was already removed.
+    pub fn replace(
+        &self,
+        index: u32,
+        value: T,
+        fill_columns: impl FnOnce(&T, &mut [Utf32String]),
+    ) -> Option<u32> {
+        let key = self.items.key(index);
+        if !self.items.remove(index) {
+            return None;
+        }
+        let idx = self.items.push_with_key(value, key, fill_columns);
+        self.notify.notify();
+        Some(idx)
     }
 
-    /// Returns the total number of items injected in the matcher. This might
-    /// not match the number of items in the match snapshot (if the matcher
-    /// is still running)
+    /// Returns the total number of items injected in the matcher (including
+    /// removed items). This might not match the number of items in the match
+    /// snapshot (if the matcher is still running)
     pub fn injected_items(&self) -> u32 {
         self.items.count()
     }
 
+    /// Returns the number of items that were [removed](Injector::remove).
+    pub fn removed_items(&self) -> u32 {
+        self.items.removed()
+    }
+
     /// Returns a reference to the item at the given index.
     ///
     /// # Safety
@@ -116,11 +197,25 @@ impl<T> Injector<T> {
     }
 
     /// Returns a reference to the element at the given index.
+    ///
+    /// Returns `None` if the element is not initialized yet or was removed.
     pub fn get(&self, index: u32) -> Option<Item<'_, T>> {
         self.items.get(index)
     }
 }
 
+impl<T> Drop for Injector<T> {
+    fn drop(&mut self) {
+        // Safety: `items` is never used again
+        unsafe { ManuallyDrop::drop(&mut self.items) };
+        // dropping the last injector may complete the input which
+        // `Nucleo::tick_until_done` waits for, the reference must be
+        // released first so that `active_injectors` is up to date once
+        // the waiting thread wakes up
+        self.notify.wake();
+    }
+}
+
 /// An [item](crate::Item) that was successfully matched by a [`Nucleo`] worker.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct Match {
@@ -128,6 +223,42 @@ pub struct Match {
     pub idx: u32,
 }
 
+/// The mapping from old to new item indices produced by
+/// [`Nucleo::compact`].
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Compaction {
+    new_indices: Vec<u32>,
+}
+
+impl Compaction {
+    /// Returns the new index of the item that had index `old` before the
+    /// compaction, `None` if the item was removed.
+    pub fn new_index(&self, old: u32) -> Option<u32> {
+        self.new_indices
+            .get(old as usize)
+            .copied()
+            .filter(|&new| new != u32::MAX)
+    }
+
+    /// Returns an iterator over the `(old, new)` indices of all items that
+    /// were kept, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.new_indices
+            .iter()
+            .enumerate()
+            .filter(|(_, &new)| new != u32::MAX)
+            .map(|(old, &new)| (old as u32, new))
+    }
+
+    /// Returns the number of items that were dropped.
+    pub fn dropped_items(&self) -> u32 {
+        self.new_indices
+            .iter()
+            .filter(|&&new| new == u32::MAX)
+            .count() as u32
+    }
+}
+
 /// That status of a [`Nucleo`] worker after a match.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct Status {
@@ -143,26 +274,87 @@ pub struct Snapshot<T: Sync + Send + 'static> {
     item_count: u32,
     matches: Vec<Match>,
     pattern: MultiPattern,
+    /// The config the matches were computed with.
+    config: Arc<Config>,
     items: Arc<boxcar::Vec<T>>,
+    keys: KeyIndex,
+    selection: Selection,
+    /// Whether `ranks` is maintained, see [`Nucleo::track_match_ranks`].
+    track_ranks: bool,
+    /// Maps item indices to the position of their match in `matches`
+    /// (`u32::MAX` for items that didn't match).
+    ranks: Vec<u32>,
+    /// The number of matches at the start of `matches` that are sorted.
+    sorted: u32,
+    /// Shared with the worker, see [`Snapshot::request_sorted`].
+    sort_depth: Arc<AtomicU32>,
+    notify: Arc<Notify>,
 }
 
 impl<T: Sync + Send + 'static> Snapshot<T> {
     fn clear(&mut self, new_items: Arc<boxcar::Vec<T>>) {
         self.item_count = 0;
         self.matches.clear();
-        self.items = new_items
+        self.sorted = 0;
+        self.selection.detach(|idx| self.items.key(idx));
+        self.items = new_items;
+        self.keys = KeyIndex::default();
+        self.ranks.clear();
+    }
+
+    fn compact(&mut self, new_items: Arc<boxcar::Vec<T>>, new_indices: &[u32]) {
+        let (mut pos, mut sorted) = (0, 0);
+        self.matches.retain_mut(|match_| {
+            match_.idx = new_indices[match_.idx as usize];
+            pos += 1;
+            let keep = match_.idx != u32::MAX;
+            if keep && pos <= self.sorted {
+                sorted += 1;
+            }
+            keep
+        });
+        self.sorted = sorted;
+        if !self.keys.is_empty() {
+            self.keys.compact(new_indices, u32::MAX);
+        }
+        self.selection.remap(new_indices);
+        self.items = new_items;
+        self.update_ranks();
     }
 
     fn update(&mut self, worker: &Worker<T>) {
         self.item_count = worker.item_count();
         self.pattern.clone_from(&worker.pattern);
         self.matches.clone_from(&worker.matches);
+        self.sorted = worker.sorted.min(self.matches.len() as u32);
         if !Arc::ptr_eq(&worker.items, &self.items) {
+            self.selection.detach(|idx| self.items.key(idx));
             self.items = worker.items.clone()
         }
+        self.keys.clone_from(&worker.keys);
+        if !Arc::ptr_eq(&worker.config, &self.config) {
+            self.config = worker.config.clone()
+        }
+        if self.items.removed() != 0 {
+            self.selection.retain(|idx| !self.items.is_removed(idx));
+        }
+        let (keys, items) = (&self.keys, &self.items);
+        self.selection.resolve(|key| item_index(keys, items, key));
+        self.update_ranks();
+    }
+
+    fn update_ranks(&mut self) {
+        if !self.track_ranks {
+            return;
+        }
+        self.ranks.clear();
+        self.ranks.resize(self.items.count() as usize, u32::MAX);
+        for (rank, m) in self.matches.iter().enumerate() {
+            self.ranks[m.idx as usize] = rank as u32;
+        }
     }
 
-    /// Returns that total number of items
+    /// Returns that total number of items (excluding removed items)
     pub fn item_count(&self) -> u32 {
         self.item_count
     }
@@ -177,9 +369,32 @@ impl<T: Sync + Send + 'static> Snapshot<T> {
         self.matches.len() as u32
     }
 
+    /// Returns the number of matches at the start of
+    /// [`matches`](Snapshot::matches) that are sorted. This is less than the
+    /// [`matched_item_count`](Snapshot::matched_item_count) if a
+    /// [`sort_limit`](Nucleo::sort_limit) is set, the order of the remaining
+    /// matches is unspecified.
+    pub fn sorted_item_count(&self) -> u32 {
+        self.sorted
+    }
+
+    /// Asks the matcher to sort at least the first `count` matches if they
+    /// aren't sorted yet. The sorted matches are available in one of the
+    /// next snapshots.
+    fn request_sorted(&self, count: u32) {
+        if count > self.sorted && self.sort_depth.fetch_max(count, Ordering::Relaxed) < count {
+            self.notify.notify();
+        }
+    }
+
     /// Returns an iterator over the items that correspond to a subrange of
     /// all the matches in this snapshot.
     ///
+    /// If the range extends beyond the [sorted](Snapshot::sorted_item_count)
+    /// matches, the matcher is asked to sort the matches up to the end of
+    /// the range. Until then the iterator yields the remaining matches in
+    /// an unspecified order.
+    ///
     /// # Panics
     /// Panics if `range` has a range bound that is larger than
     /// the matched item count
@@ -198,6 +413,7 @@ impl<T: Sync + Send + 'static> Snapshot<T> {
             Bound::Excluded(&end) => end as usize,
             Bound::Unbounded => self.matches.len(),
         };
+        self.request_sorted(end as u32);
         self.matches[start..end]
             .iter()
             .map(|&m| unsafe { self.items.get_unchecked(m.idx) })
@@ -218,7 +434,8 @@ impl<T: Sync + Send + 'static> Snapshot<T> {
 
     /// Returns a reference to the item at the given index.
     ///
-    /// Returns `None` if the given `index` is not initialized. This function
+    /// Returns `None` if the given `index` is not initialized or the item was
+    /// [removed](Injector::remove) in the meantime. This function
     /// is only guarteed to return `Some` for item indices that can be found in
     /// the `matches` of this struct. Both smaller and larger indices may return
     /// `None`.
@@ -227,6 +444,64 @@ impl<T: Sync + Send + 'static> Snapshot<T> {
         self.items.get(index)
     }
 
+    /// Returns the index of the item that was pushed with the given `key`
+    /// (see [`Injector::push_with_key`]).
+    ///
+    /// Returns `None` if the matcher had not processed such an item when this
+    /// snapshot was taken or the item was [removed](Injector::remove) before.
+    /// If multiple items were pushed with the same key the index of the last
+    /// one is returned. Once that item is removed the key doesn't resolve to
+    /// the other items until the next [compaction](Nucleo::compact).
+    #[inline]
+    pub fn item_index(&self, key: u64) -> Option<u32> {
+        item_index(&self.keys, &self.items, key)
+    }
+
+    /// Returns the items selected by the user, see
+    /// [`Nucleo::selection_mut`] to change the selection.
+    #[inline]
+    pub fn selection(&self) -> &Selection {
+        &self.selection
+    }
+
+    /// Returns an iterator over the selected items in the order they were
+    /// selected.
+    pub fn selected_items(&self) -> impl Iterator<Item = Item<'_, T>> + '_ {
+        self.selection.iter().filter_map(|idx| self.get_item(idx))
+    }
+
+    /// Returns an iterator over the matches of the selected items in the
+    /// order of the matches. Selected items that don't match the pattern
+    /// are skipped.
+    pub fn selected_matches(&self) -> impl Iterator<Item = Match> + '_ {
+        self.matches
+            .iter()
+            .copied()
+            .filter(|m| self.selection.is_selected(m.idx))
+    }
+
+    /// Returns the position of the match of the item at the given index in
+    /// [`matches`](Snapshot::matches), `None` if the item didn't match. This
+    /// allows keeping the cursor of a UI on the same item when a new snapshot
+    /// reorders the matches.
+    ///
+    /// This is a constant time lookup if
+    /// [`Nucleo::track_match_ranks`] is enabled and requires a linear scan of
+    /// the matches otherwise.
+    pub fn match_rank(&self, index: u32) -> Option<u32> {
+        if self.track_ranks {
+            return self
+                .ranks
+                .get(index as usize)
+                .copied()
+                .filter(|&rank| rank != u32::MAX);
+        }
+        self.matches
+            .iter()
+            .position(|m| m.idx == index)
+            .map(|rank| rank as u32)
+    }
+
     /// Return the matches corresponding to this snapshot.
     #[inline]
     pub fn matches(&self) -> &[Match] {
@@ -237,12 +512,63 @@ impl<T: Sync + Send + 'static> Snapshot<T> {
     /// `n`th match.
     ///
     /// Returns `None` if `n` is greater than or equal to the match count.
+    ///
+    /// Like [`matched_items`](Snapshot::matched_items) this asks the matcher
+    /// to sort the first `n + 1` matches.
     #[inline]
     pub fn get_matched_item(&self, n: u32) -> Option<Item<'_, T>> {
+        self.request_sorted(n.saturating_add(1));
         // SAFETY: A match index is guaranteed to corresponding to a valid global index in this
         // snapshot.
         unsafe { Some(self.get_item_unchecked(self.matches.get(n as usize)?.idx)) }
     }
+
+    /// Computes the highlight ranges of the `n`th match by matching the
+    /// [`pattern`](Snapshot::pattern) of this snapshot against the item
+    /// again. Returns one list of ranges per column, each range covers
+    /// adjacent matched chars of [`Item::matcher_columns`]. The ranges are
+    /// sorted and don't overlap. Use [`Utf32Str::byte_ranges`] to convert
+    /// them to byte ranges of the original text.
+    ///
+    /// The item is matched with the [`Config`] that produced this snapshot,
+    /// `matcher` only provides the scratch memory (its config is restored
+    /// afterwards).
+    ///
+    /// Returns `None` if `n` is greater than or equal to the match count.
+    pub fn match_highlights(&self, n: u32, matcher: &mut Matcher) -> Option<Vec<Vec<Range<u32>>>> {
+        let item = self.get_matched_item(n)?;
+        let mut indices = vec![Vec::new(); item.matcher_columns.len()];
+        let config = (matcher.config != *self.config)
+            .then(|| mem::replace(&mut matcher.config, Config::clone(&self.config)));
+        let score = self
+            .pattern
+            .indices(item.matcher_columns, matcher, &mut indices);
+        if let Some(config) = config {
+            matcher.config = config;
+        }
+        score?;
+        Some(indices.iter_mut().map(highlight_ranges).collect())
+    }
+}
+
+/// Looks up the index of the item with `key`, see [`Snapshot::item_index`].
+fn item_index<T>(keys: &KeyIndex, items: &boxcar::Vec<T>, key: u64) -> Option<u32> {
+    keys.get(key).filter(|&idx| !items.is_removed(idx))
+}
+
+/// Sorts and deduplicates `indices` and coalesces adjacent indices into
+/// ranges.
+fn highlight_ranges(indices: &mut Vec<u32>) -> Vec<Range<u32>> {
+    indices.sort_unstable();
+    indices.dedup();
+    let mut ranges: Vec<Range<u32>> = Vec::new();
+    for &idx in indices.iter() {
+        match ranges.last_mut() {
+            Some(last) if last.end == idx => last.end += 1,
+            _ => ranges.push(idx..idx + 1),
+        }
+    }
+    ranges
 }
 
 #[repr(u8)]
@@ -283,12 +609,13 @@ pub struct Nucleo<T: Sync + Send + 'static> {
     pool: ThreadPool,
     state: State,
     items: Arc<boxcar::Vec<T>>,
-    notify: Arc<(dyn Fn() + Sync + Send)>,
+    notify: Arc<Notify>,
     snapshot: Snapshot<T>,
     /// The pattern matched by this matcher. To update the match pattern
     /// [`MultiPattern::reparse`](`pattern::MultiPattern::reparse`) should be used.
     /// Note that the matcher worker will only become aware of the new pattern
-    /// after a call to [`tick`](Nucleo::tick).
+    /// after a call to [`tick`](Nucleo::tick). Columns can be weighted with
+    /// [`MultiPattern::set_column_weight`](`pattern::MultiPattern::set_column_weight`).
     pub pattern: MultiPattern,
 }
 
@@ -308,10 +635,11 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
     /// number of columns cannot be changed after construction.
     pub fn new(
         config: Config,
-        notify: Arc<(dyn Fn() + Sync + Send)>,
+        notify: Arc<dyn Fn() + Sync + Send>,
         num_threads: Option<usize>,
         columns: u32,
     ) -> Self {
+        let notify = Arc::new(Notify::new(notify));
         let (pool, worker) = Worker::new(num_threads, config, notify.clone(), columns);
         Self {
             canceled: worker.canceled.clone(),
@@ -322,8 +650,16 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
             snapshot: Snapshot {
                 matches: Vec::with_capacity(2 * 1024),
                 pattern: MultiPattern::new(columns as usize),
+                config: worker.config.clone(),
                 item_count: 0,
                 items: worker.items.clone(),
+                keys: KeyIndex::default(),
+                selection: Selection::default(),
+                track_ranks: false,
+                ranks: Vec::new(),
+                sorted: 0,
+                sort_depth: worker.sort_depth.clone(),
+                notify: notify.clone(),
             },
             worker: Arc::new(Mutex::new(worker)),
             state: State::Init,
@@ -346,16 +682,32 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
     /// Returns an injector that can be used for adding candidates to the matcher.
     pub fn injector(&self) -> Injector<T> {
         Injector {
-            items: self.items.clone(),
+            items: ManuallyDrop::new(self.items.clone()),
             notify: self.notify.clone(),
         }
     }
 
+    /// Returns the items selected by the user. The selection is part of the
+    /// [`Snapshot`] and kept consistent with it by [`tick`](Nucleo::tick).
+    pub fn selection_mut(&mut self) -> &mut Selection {
+        &mut self.snapshot.selection
+    }
+
+    /// Selects all items that match the pattern in the current [`Snapshot`].
+    pub fn select_all_matched(&mut self) {
+        let snapshot = &mut self.snapshot;
+        for m in &snapshot.matches {
+            snapshot.selection.select(m.idx);
+        }
+    }
+
     /// Restart the the item stream. Removes all items and disconnects all
     /// previously created injectors from this instance. If `clear_snapshot`
     /// is `true` then all items and matched are removed from the [`Snapshot`]
     /// immediately. Otherwise the snapshot will keep the current matches until
-    /// the matcher has run again.
+    /// the matcher has run again. The [selection](Nucleo::selection_mut) is
+    /// restored for items with a [key](Injector::push_with_key) once they
+    /// were injected again.
     ///
     /// # Note
     ///
@@ -371,6 +723,48 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
         }
     }
 
+    /// Rebuilds the item store with only the items that were not
+    /// [removed](Injector::remove) so that the memory of removed items is
+    /// freed. The relative order of the items is preserved but their indices
+    /// change. The matches of the current [`Snapshot`] are updated to the new
+    /// indices immediately (its [`item_count`](Snapshot::item_count) is only
+    /// updated by the next [`tick`](Nucleo::tick)), the returned
+    /// [`Compaction`] maps old to new indices so that any state associated
+    /// with the item indices can be updated as well.
+    ///
+    /// Compaction requires exclusive access to the items, so `None` is
+    /// returned (and nothing is changed) if there are any
+    /// [active injectors](Nucleo::active_injectors). Blocks until the
+    /// matcher has finished running.
+    pub fn compact(&mut self) -> Option<Compaction> {
+        if self.active_injectors() != 0 {
+            return None;
+        }
+        let mut worker = self.worker.lock();
+        let worker_items = Arc::ptr_eq(&worker.items, &self.items);
+        let snapshot_items = Arc::ptr_eq(&self.snapshot.items, &self.items);
+        let placeholder = Arc::new(boxcar::Vec::with_capacity(0, self.items.columns()));
+        let items = std::mem::replace(&mut self.items, placeholder.clone());
+        if worker_items {
+            worker.items = placeholder.clone();
+        }
+        if snapshot_items {
+            self.snapshot.items = placeholder;
+        }
+        let Ok(items) = Arc::try_unwrap(items) else {
+            unreachable!("items are still referenced without active injectors")
+        };
+        let (items, new_indices) = items.compact();
+        self.items = Arc::new(items);
+        if worker_items {
+            worker.compact(self.items.clone(), &new_indices);
+        }
+        if snapshot_items {
+            self.snapshot.compact(self.items.clone(), &new_indices);
+        }
+        Some(Compaction { new_indices })
+    }
+
     /// Update the internal configuration.
     pub fn update_config(&mut self, config: Config) {
         self.worker.lock().update_config(config)
@@ -388,6 +782,32 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
         self.worker.lock().reverse_items(reverse_items)
     }
 
+    /// Set the number of best matches that are sorted. The remaining matches
+    /// are only moved behind the best matches instead of being fully sorted,
+    /// which is considerably faster for large numbers of matches. The limit
+    /// grows on demand when matches beyond the sorted matches are accessed
+    /// with [`Snapshot::matched_items`] or [`Snapshot::get_matched_item`] and
+    /// is reset when the pattern changes. `None` (the default) sorts all
+    /// matches.
+    pub fn sort_limit(&mut self, sort_limit: Option<u32>) {
+        self.worker.lock().sort_limit(sort_limit)
+    }
+
+    /// Set whether each [`Snapshot`] should maintain a lookup table from
+    /// item indices to the position of their match so that
+    /// [`Snapshot::match_rank`] doesn't need to scan the matches. The table
+    /// is rebuilt every time the snapshot changes which takes time linear in
+    /// the number of items. Defaults to false.
+    pub fn track_match_ranks(&mut self, track_ranks: bool) {
+        let snapshot = &mut self.snapshot;
+        snapshot.track_ranks = track_ranks;
+        if track_ranks {
+            snapshot.update_ranks();
+        } else {
+            snapshot.ranks = Vec::new();
+        }
+    }
+
     /// The main way to interact with the matcher, this should be called
     /// regularly (for example each time a frame is rendered). To avoid
     /// excessive redraws this method will wait `timeout` milliseconds for the
@@ -395,6 +815,9 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
     pub fn tick(&mut self, timeout: u64) -> Status {
         self.should_notify.store(false, atomic::Ordering::Relaxed);
         let status = self.pattern.status();
+        if status != pattern::Status::Unchanged {
+            self.snapshot.sort_depth.store(0, Ordering::Relaxed);
+        }
         let canceled = status != pattern::Status::Unchanged || self.state.canceled();
         let mut res = self.tick_inner(timeout, canceled, status);
         if !canceled {
@@ -407,6 +830,83 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
         res
     }
 
+    /// Blocks until all items have been matched against the current pattern
+    /// and returns the final snapshot. Only returns once no injectors are
+    /// active anymore (see [`active_injectors`](Nucleo::active_injectors)),
+    /// so all injectors must eventually be dropped.
+    ///
+    /// If `deadline` is reached before matching finished `None` is returned.
+    /// The latest (incomplete) results are still available with
+    /// [`snapshot`](Nucleo::snapshot) in that case.
+    ///
+    /// This is intended for tests and non-interactive batch jobs, interactive
+    /// applications should use [`tick`](Nucleo::tick) instead.
+    pub fn tick_until_done(&mut self, deadline: Option<Instant>) -> Option<&Snapshot<T>> {
+        loop {
+            // observe these before ticking so that the final tick is
+            // guaranteed to see all items and we don't miss any wakeups
+            let generation = self.notify.generation();
+            let input_done = self.active_injectors() == 0;
+            let timeout = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::from_millis(100),
+            };
+            let status = self.tick(timeout.as_millis().min(100) as u64);
+            if input_done && !status.running {
+                return Some(&self.snapshot);
+            }
+            if matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+                return None;
+            }
+            if !status.running {
+                // all items are matched, wait for new items (or for the
+                // remaining injectors to be dropped)
+                self.notify.wait(generation, deadline);
+            }
+        }
+    }
+
+    /// Polls the matcher for a new snapshot, this is the async version of
+    /// [`tick`](Nucleo::tick) (but never blocks on the worker). Returns
+    /// `Poll::Ready` once the snapshot has changed, otherwise the waker of
+    /// `cx` is woken as soon as new information is available.
+    ///
+    /// See also [`changed`](Nucleo::changed) and
+    /// [`status_stream`](Nucleo::status_stream).
+    #[cfg(feature = "async")]
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Status> {
+        loop {
+            let generation = self.notify.generation();
+            let status = self.tick(0);
+            if status.changed {
+                return Poll::Ready(status);
+            }
+            if self.notify.register(cx.waker(), generation) {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Returns a future that resolves once a new snapshot is available.
+    ///
+    /// The future borrows the matcher mutably, to update the pattern while
+    /// waiting simply drop the future and create a new one afterwards.
+    #[cfg(feature = "async")]
+    pub fn changed(&mut self) -> future::Changed<'_, T> {
+        future::Changed::new(self)
+    }
+
+    /// Returns a stream that yields the [`Status`] of the matcher every time
+    /// the snapshot changes or the matcher starts/stops running in the
+    /// background. The stream never ends.
+    ///
+    /// The stream borrows the matcher mutably, to update the pattern while
+    /// waiting simply drop the stream and create a new one afterwards.
+    #[cfg(feature = "async")]
+    pub fn status_stream(&mut self) -> future::StatusStream<'_, T> {
+        future::StatusStream::new(self)
+    }
+
     fn tick_inner(&mut self, timeout: u64, canceled: bool, status: pattern::Status) -> Status {
         let mut inner = if canceled {
             self.pattern.reset_status();
@@ -425,7 +925,7 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
 
         let changed = inner.running;
 
-        let running = canceled || self.items.count() > inner.item_count();
+        let running = canceled || inner.is_outdated(&self.items) || inner.needs_sort();
         if inner.running {
             inner.running = false;
             if !inner.was_canceled && !self.state.canceled() {
@@ -433,7 +933,7 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
             }
         }
         if running {
-            inner.pattern.clone_from(&self.pattern);
+            inner.set_pattern(&self.pattern);
             self.canceled.store(false, atomic::Ordering::Relaxed);
             if !canceled {
                 self.should_notify.store(true, atomic::Ordering::Release);
@@ -442,8 +942,14 @@ impl<T: Sync + Send + 'static> Nucleo<T> {
             if cleared {
                 inner.items = self.items.clone();
             }
-            self.pool
-                .spawn(move || unsafe { inner.run(status, cleared) })
+            let notify = self.notify.clone();
+            self.pool.spawn(move || {
+                unsafe { inner.run(status, cleared) };
+                // wake up blocked callers only after the lock was released so
+                // that they can immediately observe the results
+                drop(inner);
+                notify.wake();
+            })
         }
         Status { changed, running }
     }
@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::Waker;
+use std::time::Instant;
+
+use parking_lot::{Condvar, Mutex};
+
+/// Wraps the user provided notify callback so that nucleo can also block
+/// until new information is available (see [`Nucleo::tick_until_done`](crate::Nucleo::tick_until_done))
+/// or wake up async tasks.
+///
+/// Waking up is lock-free unless somebody is actually waiting, so this
+/// doesn't affect the wait-freedom of [`Injector::push`](crate::Injector::push).
+pub(crate) struct Notify {
+    callback: Arc<dyn Fn() + Sync + Send>,
+    /// Incremented every time the state of the matcher changes.
+    generation: AtomicU64,
+    /// The number of blocked threads and registered wakers.
+    waiters: AtomicUsize,
+    wakers: Mutex<Vec<Waker>>,
+    condvar: Condvar,
+}
+
+impl Notify {
+    pub(crate) fn new(callback: Arc<dyn Fn() + Sync + Send>) -> Notify {
+        Notify {
+            callback,
+            generation: AtomicU64::new(0),
+            waiters: AtomicUsize::new(0),
+            wakers: Mutex::new(Vec::new()),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Invokes the user callback and wakes up any blocked thread.
+    pub(crate) fn notify(&self) {
+        (self.callback)();
+        self.wake();
+    }
+
+    /// Wakes up any blocked thread without invoking the user callback.
+    pub(crate) fn wake(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) != 0 {
+            // taking the lock ensures the waiter is either already blocked
+            // on the condvar or will observe the new generation
+            let mut wakers = self.wakers.lock();
+            self.condvar.notify_all();
+            self.waiters.fetch_sub(wakers.len(), Ordering::SeqCst);
+            for waker in wakers.drain(..) {
+                waker.wake()
+            }
+        }
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Blocks until the generation changes from `generation` or `deadline`
+    /// is reached.
+    pub(crate) fn wait(&self, generation: u64, deadline: Option<Instant>) {
+        let mut guard = self.wakers.lock();
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        while self.generation.load(Ordering::SeqCst) == generation {
+            match deadline {
+                Some(deadline) => {
+                    if self.condvar.wait_until(&mut guard, deadline).timed_out() {
+                        break;
+                    }
+                }
+                None => self.condvar.wait(&mut guard),
+            }
+        }
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Registers `waker` to be woken once the generation changes from
+    /// `generation`. Returns `false` (without registering the waker) if the
+    /// generation already changed.
+    #[cfg(feature = "async")]
+    pub(crate) fn register(&self, waker: &Waker, generation: u64) -> bool {
+        let mut wakers = self.wakers.lock();
+        if wakers.iter().any(|registered| registered.will_wake(waker)) {
+            return self.generation.load(Ordering::SeqCst) == generation;
+        }
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        if self.generation.load(Ordering::SeqCst) != generation {
+            self.waiters.fetch_sub(1, Ordering::SeqCst);
+            return false;
+        }
+        wakers.push(waker.clone());
+        true
+    }
+}
@@ -3,7 +3,8 @@
 //! This implementation is copied verbatim from `std::slice::sort_unstable` and then parallelized.
 //! The only difference from the original is that calls to `recurse` are executed in parallel using
 //! `rayon_core::join`.
-//! Further modified for nucleo to allow canceling the sort
+//! Further modified for nucleo to allow canceling the sort and to only sort the
+//! smallest elements (`par_partial_sort`)
 
 // Copyright (c) 2010 The Rust Project Developers
 //
@@ -31,7 +32,7 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
-use std::cmp;
+use std::cmp::{self, Ordering};
 use std::mem::{self, MaybeUninit};
 use std::ptr;
 use std::sync::atomic::{self, AtomicBool};
@@ -893,3 +894,61 @@ where
 
     recurse(v, &is_less, None, limit, canceled)
 }
+
+/// Moves the `k` smallest elements of `v` to the front of `v` and sorts them
+/// in parallel. The order of the remaining elements is unspecified.
+///
+/// The `k` smallest elements of evenly sized chunks are selected in parallel
+/// first, so only these candidates need to be considered by the final
+/// (sequential) selection.
+pub(crate) fn par_partial_sort<T, F>(
+    v: &mut [T],
+    k: usize,
+    is_less: F,
+    canceled: &AtomicBool,
+) -> bool
+where
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    use rayon::prelude::*;
+
+    if k >= v.len() {
+        return par_quicksort(v, is_less, canceled);
+    }
+    if k == 0 {
+        return false;
+    }
+    let compare = |a: &T, b: &T| {
+        if is_less(a, b) {
+            Ordering::Less
+        } else if is_less(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    };
+    // chunks must be at least twice as long as the number of candidates so
+    // that moving the candidates to the front below never overlaps
+    let chunk_len = (v.len() / rayon::current_num_threads()).max(2 * k);
+    v.par_chunks_mut(chunk_len).for_each(|chunk| {
+        if chunk.len() > k && !canceled.load(atomic::Ordering::Relaxed) {
+            chunk.select_nth_unstable_by(k, compare);
+        }
+    });
+    if canceled.load(atomic::Ordering::Relaxed) {
+        return true;
+    }
+    let mut candidates = 0;
+    for start in (0..v.len()).step_by(chunk_len) {
+        let len = k.min(v.len() - start);
+        for i in 0..len {
+            v.swap(candidates + i, start + i);
+        }
+        candidates += len;
+    }
+    if candidates > k {
+        v[..candidates].select_nth_unstable_by(k, compare);
+    }
+    par_quicksort(&mut v[..k], is_less, canceled)
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub use nucleo_matcher::pattern::{Atom, AtomKind, CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Matcher, Utf32String};
 
@@ -12,31 +14,117 @@ pub(crate) enum Status {
     Rescore,
 }
 
+/// Configuration and state of the column addressed query syntax
+/// (see [`MultiPattern::reparse_query`]).
+#[derive(Debug)]
+struct QuerySyntax {
+    names: Vec<Option<Box<str>>>,
+    default_columns: Vec<usize>,
+    /// The text each column was last parsed from by `reparse_query`. `None`
+    /// if the column was reparsed directly.
+    texts: Vec<Option<String>>,
+    /// The text `MultiPattern::defaults` was last parsed from.
+    default_text: Option<String>,
+}
+
+/// Where `reparse_query` routes an atom to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Target {
+    Column(usize),
+    /// Every default column.
+    DefaultColumns,
+    /// Any of the (multiple) default columns, see `MultiPattern::defaults`.
+    AnyDefaultColumn,
+}
+
+impl Clone for QuerySyntax {
+    fn clone(&self) -> Self {
+        Self {
+            names: self.names.clone(),
+            default_columns: self.default_columns.clone(),
+            texts: self.texts.clone(),
+            default_text: self.default_text.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.names.clone_from(&source.names);
+        self.default_columns.clone_from(&source.default_columns);
+        self.texts.clone_from(&source.texts);
+        self.default_text.clone_from(&source.default_text);
+    }
+}
+
 #[derive(Debug)]
 pub struct MultiPattern {
     cols: Vec<(Pattern, Status)>,
+    /// The score of each column is multiplied with its weight.
+    weights: Vec<u32>,
+    /// The positive atoms without a column name when `reparse_query` has
+    /// multiple default columns. These only need to match one of the default
+    /// columns.
+    defaults: (Pattern, Status),
+    query: QuerySyntax,
 }
 
 impl Clone for MultiPattern {
     fn clone(&self) -> Self {
         Self {
             cols: self.cols.clone(),
+            weights: self.weights.clone(),
+            defaults: self.defaults.clone(),
+            query: self.query.clone(),
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
-        self.cols.clone_from(&source.cols)
+        self.cols.clone_from(&source.cols);
+        self.weights.clone_from(&source.weights);
+        self.defaults.clone_from(&source.defaults);
+        self.query.clone_from(&source.query);
     }
 }
 
 impl MultiPattern {
     /// Creates a multi pattern with `columns` empty column patterns.
+    /// All columns have a weight of 1.
     pub fn new(columns: usize) -> Self {
+        Self::with_column_weights(&vec![1; columns])
+    }
+
+    /// Creates a multi pattern with one empty column pattern for each
+    /// element of `weights`. The score of each column is multiplied by its
+    /// weight, so that matches in more important columns rank higher. A
+    /// weight of 0 means that a column is only used for filtering and does
+    /// not affect the ranking.
+    pub fn with_column_weights(weights: &[u32]) -> Self {
+        let columns = weights.len();
         Self {
             cols: vec![Default::default(); columns],
+            weights: weights.to_vec(),
+            defaults: Default::default(),
+            query: QuerySyntax {
+                names: vec![None; columns],
+                default_columns: (0..columns.min(1)).collect(),
+                texts: vec![None; columns],
+                default_text: None,
+            },
+        }
+    }
+
+    /// Changes the weight of `column` (see [`with_column_weights`](MultiPattern::with_column_weights)).
+    pub fn set_column_weight(&mut self, column: usize, weight: u32) {
+        if self.weights[column] != weight {
+            self.weights[column] = weight;
+            self.cols[column].1 = Status::Rescore;
         }
     }
 
+    /// Returns the weight of `column`.
+    pub fn column_weight(&self, column: usize) -> u32 {
+        self.weights[column]
+    }
+
     /// Reparses a column. By specifying `append` the caller promises that text passed
     /// to the previous `reparse` invocation is a prefix of `new_text`. This enables
     /// additional optimizations but can lead to missing matches if an incorrect value
@@ -49,52 +137,389 @@ impl MultiPattern {
         normalization: Normalization,
         append: bool,
     ) {
-        let old_status = self.cols[column].1;
-        if append
-            && old_status != Status::Rescore
-            && self.cols[column]
-                .0
-                .atoms
-                .last()
-                .map_or(true, |last| !last.negative)
-        {
-            self.cols[column].1 = Status::Update;
-        } else {
-            self.cols[column].1 = Status::Rescore;
+        reparse_pattern(
+            &mut self.cols[column],
+            new_text,
+            case_matching,
+            normalization,
+            append,
+        );
+        self.query.texts[column] = None;
+    }
+
+    /// Sets the name that addresses `column` in the query syntax of
+    /// [`reparse_query`](MultiPattern::reparse_query). Passing `None` removes
+    /// the name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is empty or contains whitespace or `:`.
+    pub fn set_column_name(&mut self, column: usize, name: Option<&str>) {
+        if let Some(name) = name {
+            assert!(
+                !name.is_empty() && !name.contains(|c: char| c == ':' || c.is_whitespace()),
+                "invalid column name {name:?}"
+            );
+        }
+        self.query.names[column] = name.map(Box::from);
+    }
+
+    /// Returns the name of `column` (see [`set_column_name`](MultiPattern::set_column_name)).
+    pub fn column_name(&self, column: usize) -> Option<&str> {
+        self.query.names[column].as_deref()
+    }
+
+    /// Sets the columns that atoms without a column name are matched against
+    /// by [`reparse_query`](MultiPattern::reparse_query). Defaults to the
+    /// first column. Such an atom matches an item if it matches any of the
+    /// default columns, negated atoms exclude items where any of the default
+    /// columns matches.
+    ///
+    /// The query must be passed to [`reparse_query`](MultiPattern::reparse_query)
+    /// again to take effect.
+    pub fn set_default_columns(&mut self, columns: &[usize]) {
+        assert!(
+            columns.iter().all(|&column| column < self.cols.len()),
+            "default column out of bounds"
+        );
+        if self.query.default_columns != columns {
+            self.query.default_columns.clear();
+            self.query.default_columns.extend_from_slice(columns);
+            self.query.default_text = None;
+        }
+    }
+
+    /// Returns the columns that atoms without a column name are matched
+    /// against.
+    pub fn default_columns(&self) -> &[usize] {
+        &self.query.default_columns
+    }
+
+    /// Parses a query that addresses multiple columns and reparses all
+    /// columns accordingly. Each (whitespace separated) atom can be prefixed
+    /// with the name of a column (see [`set_column_name`](MultiPattern::set_column_name))
+    /// followed by `:` to only match it against that column. For negated
+    /// atoms the name comes after the `!`. All other atoms are matched against
+    /// the [default columns](MultiPattern::set_default_columns): they match
+    /// if any default column matches and contribute the best (weighted) score
+    /// of these columns. Negated atoms without a column name exclude items
+    /// where any default column matches.
+    ///
+    /// With a single default column these atoms are simply added to the
+    /// [pattern of that column](MultiPattern::column_pattern). Otherwise the
+    /// positive atoms are kept in the [`default_pattern`](MultiPattern::default_pattern).
+    ///
+    /// For example with columns named `name`, `path` and `ext` the query
+    /// `name:foo path:src/ !ext:rs bar` matches `foo` against the `name`
+    /// column, `src/` against the `path` column, excludes items where the
+    /// `ext` column contains `rs` and matches `bar` against the default
+    /// columns. A `:` after a column name can be escaped as `\:` to match it
+    /// literally. Alternatives (`foo | bar`, see [`Pattern::parse`]) are only
+    /// supported between atoms that address the same column (and with
+    /// multiple default columns not between negated and other atoms without
+    /// a column name).
+    ///
+    /// Unlike [`reparse`](MultiPattern::reparse) no `append` flag is needed,
+    /// this function detects whether the text of each column was only
+    /// appended to and skips columns whose text didn't change at all.
+    pub fn reparse_query(
+        &mut self,
+        query: &str,
+        case_matching: CaseMatching,
+        normalization: Normalization,
+    ) {
+        let mut texts = vec![String::new(); self.cols.len()];
+        let mut default_text = String::new();
+        // alternatives are only kept between atoms with the same target
+        let mut prev_target = None;
+        let mut alternative = false;
+        for atom in pattern_atoms(query) {
+            if atom == "|" {
+                alternative = prev_target.is_some();
+                continue;
+            }
+            let (negative, raw) = match atom.strip_prefix('!') {
+                Some(raw) => (true, raw),
+                None => (false, atom),
+            };
+            let (column, text) = match self.split_column_name(raw) {
+                Some((column, text)) => (Some(column), Cow::Borrowed(text)),
+                None => (None, self.unescape_column_name(raw)),
+            };
+            let target = match column {
+                Some(column) => Target::Column(column),
+                None if negative || self.query.default_columns.len() < 2 => Target::DefaultColumns,
+                None => Target::AnyDefaultColumn,
+            };
+            let alternative = std::mem::take(&mut alternative) && prev_target == Some(target);
+            prev_target = Some(target);
+            let push = |dst: &mut String| {
+                if !dst.is_empty() {
+                    dst.push_str(if alternative { " | " } else { " " });
+                }
+                if negative {
+                    dst.push('!');
+                }
+                dst.push_str(&text);
+            };
+            match target {
+                Target::Column(column) => push(&mut texts[column]),
+                Target::DefaultColumns => {
+                    for &column in &self.query.default_columns {
+                        push(&mut texts[column])
+                    }
+                }
+                Target::AnyDefaultColumn => push(&mut default_text),
+            }
+        }
+        for (column, text) in texts.into_iter().enumerate() {
+            let old_text = self.query.texts[column].as_deref();
+            if old_text == Some(&*text) {
+                continue;
+            }
+            let append = matches!(old_text, Some(old_text) if text.starts_with(old_text));
+            self.reparse(column, &text, case_matching, normalization, append);
+            self.query.texts[column] = Some(text);
+        }
+        let old_text = self.query.default_text.as_deref();
+        if old_text != Some(&*default_text) {
+            let append = matches!(old_text, Some(old_text) if default_text.starts_with(old_text));
+            reparse_pattern(
+                &mut self.defaults,
+                &default_text,
+                case_matching,
+                normalization,
+                append,
+            );
+            self.query.default_text = Some(default_text);
+        }
+    }
+
+    /// Splits an atom of the form `name:text` into the column called `name`
+    /// and `text`.
+    fn split_column_name<'a>(&self, atom: &'a str) -> Option<(usize, &'a str)> {
+        let (name, text) = atom.split_once(':')?;
+        let column = self
+            .query
+            .names
+            .iter()
+            .position(|column_name| column_name.as_deref() == Some(name))?;
+        Some((column, text))
+    }
+
+    /// Turns an atom of the form `name\:text` into `name:text` if `name` is
+    /// a column name.
+    fn unescape_column_name<'a>(&self, atom: &'a str) -> Cow<'a, str> {
+        if let Some((name, text)) = atom.split_once("\\:") {
+            if self
+                .query
+                .names
+                .iter()
+                .any(|column_name| column_name.as_deref() == Some(name))
+            {
+                return Cow::Owned(format!("{name}:{text}"));
+            }
         }
-        self.cols[column]
-            .0
-            .reparse(new_text, case_matching, normalization);
+        Cow::Borrowed(atom)
     }
 
     pub fn column_pattern(&self, column: usize) -> &Pattern {
         &self.cols[column].0
     }
 
+    /// Returns the atoms without a column name that only need to match one
+    /// of the default columns (see [`reparse_query`](MultiPattern::reparse_query)).
+    /// Empty unless there are multiple default columns.
+    pub fn default_pattern(&self) -> &Pattern {
+        &self.defaults.0
+    }
+
     pub(crate) fn status(&self) -> Status {
         self.cols
             .iter()
+            .chain([&self.defaults])
             .map(|&(_, status)| status)
             .max()
             .unwrap_or(Status::Unchanged)
     }
 
     pub(crate) fn reset_status(&mut self) {
-        for (_, status) in &mut self.cols {
+        for (_, status) in self.cols.iter_mut().chain([&mut self.defaults]) {
             *status = Status::Unchanged
         }
     }
 
+    /// Matches each column pattern against the corresponding column of
+    /// `haystack` and returns the weighted sum of the column scores. Returns
+    /// `None` if any column does not match (or an atom of the
+    /// [`default_pattern`](MultiPattern::default_pattern) matches none of the
+    /// default columns).
     pub fn score(&self, haystack: &[Utf32String], matcher: &mut Matcher) -> Option<u32> {
-        // TODO: wheight columns?
-        let mut score = 0;
-        for ((pattern, _), haystack) in self.cols.iter().zip(haystack) {
-            score += pattern.score(haystack.slice(..), matcher)?
+        let mut score = 0u32;
+        for (((pattern, _), &weight), haystack) in self.cols.iter().zip(&self.weights).zip(haystack)
+        {
+            let column_score = pattern.score(haystack.slice(..), matcher)?;
+            score = score.saturating_add(column_score.saturating_mul(weight));
+        }
+        let default_score = self.default_score(haystack, matcher, None)?;
+        Some(score.saturating_add(default_score))
+    }
+
+    /// Matches each group of alternatives of the default pattern against all
+    /// default columns and returns the sum of the best weighted score of each
+    /// group. The indices of the best match of each group are appended to
+    /// the indices of its column.
+    fn default_score(
+        &self,
+        haystack: &[Utf32String],
+        matcher: &mut Matcher,
+        mut indices: Option<&mut [Vec<u32>]>,
+    ) -> Option<u32> {
+        let mut score = 0u32;
+        for group in alternative_groups(&self.defaults.0.atoms) {
+            let (column, atom, group_score) = self
+                .query
+                .default_columns
+                .iter()
+                .flat_map(|&column| group.iter().map(move |atom| (column, atom)))
+                .filter_map(|(column, atom)| {
+                    let atom_score = atom.score(haystack.get(column)?.slice(..), matcher)?;
+                    Some((
+                        column,
+                        atom,
+                        (atom_score as u32).saturating_mul(self.weights[column]),
+                    ))
+                })
+                .reduce(|best, alt| if alt.2 > best.2 { alt } else { best })?;
+            if let Some(indices) = &mut indices {
+                atom.indices(haystack[column].slice(..), matcher, &mut indices[column]);
+            }
+            score = score.saturating_add(group_score);
         }
         Some(score)
     }
 
+    /// Like [`score`](MultiPattern::score) but additionally computes the
+    /// match indices of each column. The indices of column `i` are appended
+    /// to `indices[i]` (see [`Pattern::indices`] for details).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indices` has fewer elements than there are columns.
+    pub fn indices(
+        &self,
+        haystack: &[Utf32String],
+        matcher: &mut Matcher,
+        indices: &mut [Vec<u32>],
+    ) -> Option<u32> {
+        assert!(
+            indices.len() >= self.cols.len(),
+            "missing indices for column"
+        );
+        let mut score = 0u32;
+        let columns = self.cols.iter().zip(&self.weights).zip(haystack);
+        for ((((pattern, _), &weight), haystack), indices) in columns.zip(&mut *indices) {
+            let column_score = pattern.indices(haystack.slice(..), matcher, indices)?;
+            score = score.saturating_add(column_score.saturating_mul(weight));
+        }
+        let default_score = self.default_score(haystack, matcher, Some(indices))?;
+        Some(score.saturating_add(default_score))
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.cols.iter().all(|(pat, _)| pat.atoms.is_empty())
+        self.cols.iter().all(|(pat, _)| pat.atoms.is_empty()) && self.defaults.0.atoms.is_empty()
+    }
+
+    /// Returns whether `self` and `other` match exactly the same items. Their
+    /// scores may still differ if the column weights differ.
+    pub(crate) fn matches_same_items(&self, other: &MultiPattern) -> bool {
+        self.cols.len() == other.cols.len()
+            && self
+                .cols
+                .iter()
+                .zip(&other.cols)
+                .all(|((pat, _), (other, _))| pat.atoms == other.atoms)
+            && self.defaults.0.atoms == other.defaults.0.atoms
+            && (self.defaults.0.atoms.is_empty()
+                || self.query.default_columns == other.query.default_columns)
+    }
+}
+
+/// Reparses `pattern` from `new_text` and updates its status, see
+/// [`MultiPattern::reparse`].
+fn reparse_pattern(
+    (pattern, status): &mut (Pattern, Status),
+    new_text: &str,
+    case_matching: CaseMatching,
+    normalization: Normalization,
+    append: bool,
+) {
+    let old_atoms = pattern.atoms.len();
+    let old_regex = pattern.atoms.last().map_or(false, is_regex);
+    let mut update = append
+        && *status != Status::Rescore
+        && pattern.atoms.last().map_or(true, |last| !last.negative);
+    pattern.reparse(new_text, case_matching, normalization);
+    // a new alternative (`foo | bar`) matches additional items
+    let atoms = &pattern.atoms;
+    if atoms.len() > old_atoms && atoms[old_atoms..].iter().any(Atom::is_alternative) {
+        update = false;
     }
+    // appending to a regex doesn't narrow its matches and closing a
+    // regex (`/foo` to `/foo/`) turns a fuzzy atom into a regex
+    if old_regex && !atoms.get(old_atoms - 1).map_or(false, is_regex)
+        || atoms.last().map_or(false, is_regex)
+    {
+        update = false;
+    }
+    *status = if update {
+        Status::Update
+    } else {
+        Status::Rescore
+    };
+}
+
+/// Splits `atoms` into groups of [alternatives](Atom::is_alternative).
+fn alternative_groups(atoms: &[Atom]) -> impl Iterator<Item = &[Atom]> {
+    let mut rem = atoms;
+    std::iter::from_fn(move || {
+        if rem.is_empty() {
+            return None;
+        }
+        let len = 1 + rem[1..]
+            .iter()
+            .take_while(|atom| atom.is_alternative())
+            .count();
+        let (group, rest) = rem.split_at(len);
+        rem = rest;
+        Some(group)
+    })
+}
+
+/// Returns whether `atom` is a regex (see `AtomKind::Regex`).
+fn is_regex(atom: &Atom) -> bool {
+    #[cfg(feature = "regex")]
+    let res = atom.kind == AtomKind::Regex;
+    #[cfg(not(feature = "regex"))]
+    let res = {
+        let _ = atom;
+        false
+    };
+    res
+}
+
+/// Splits a pattern into its atoms at (unescaped) whitespace, this mirrors
+/// how [`Pattern::parse`] splits patterns.
+fn pattern_atoms(pattern: &str) -> impl Iterator<Item = &str> + '_ {
+    let mut saw_backslash = false;
+    pattern
+        .split(move |c| {
+            saw_backslash = match c {
+                c if c.is_whitespace() && !saw_backslash => return true,
+                '\\' => true,
+                _ => false,
+            };
+            false
+        })
+        .filter(|atom| !atom.is_empty())
 }
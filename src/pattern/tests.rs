@@ -1,4 +1,5 @@
-use nucleo_matcher::pattern::{CaseMatching, Normalization};
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str, Utf32String};
 
 use crate::pattern::{MultiPattern, Status};
 
@@ -12,3 +13,225 @@ fn append() {
     pat.reparse(0, "!fo", CaseMatching::Smart, Normalization::Smart, true);
     assert_eq!(pat.status(), Status::Rescore);
 }
+
+#[test]
+fn column_weights() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let haystack: Vec<Utf32String> = vec!["foo".into(), "bar".into()];
+    let mut pat = MultiPattern::new(2);
+    pat.reparse(0, "fo", CaseMatching::Smart, Normalization::Smart, false);
+    pat.reparse(1, "ba", CaseMatching::Smart, Normalization::Smart, false);
+    let foo_score = pat
+        .column_pattern(0)
+        .score(haystack[0].slice(..), &mut matcher)
+        .unwrap();
+    let bar_score = pat
+        .column_pattern(1)
+        .score(haystack[1].slice(..), &mut matcher)
+        .unwrap();
+    assert_eq!(
+        pat.score(&haystack, &mut matcher),
+        Some(foo_score + bar_score)
+    );
+
+    pat.reset_status();
+    pat.set_column_weight(1, 3);
+    assert_eq!(pat.status(), Status::Rescore);
+    assert_eq!(pat.column_weight(1), 3);
+    assert_eq!(
+        pat.score(&haystack, &mut matcher),
+        Some(foo_score + 3 * bar_score)
+    );
+
+    // weight 0 columns still filter but don't contribute to the score
+    pat.set_column_weight(1, 0);
+    assert_eq!(pat.score(&haystack, &mut matcher), Some(foo_score));
+    pat.reparse(1, "x", CaseMatching::Smart, Normalization::Smart, false);
+    assert_eq!(pat.score(&haystack, &mut matcher), None);
+}
+
+#[test]
+fn column_indices() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let haystack: Vec<Utf32String> = vec!["foo".into(), "xbar".into()];
+    let mut pat = MultiPattern::with_column_weights(&[1, 2]);
+    pat.reparse(0, "o", CaseMatching::Smart, Normalization::Smart, false);
+    pat.reparse(1, "ba", CaseMatching::Smart, Normalization::Smart, false);
+    let mut indices = vec![Vec::new(); 2];
+    let score = pat.indices(&haystack, &mut matcher, &mut indices);
+    assert_eq!(score, pat.score(&haystack, &mut matcher));
+    assert_eq!(indices, [vec![1], vec![1, 2]]);
+}
+
+fn column_text(pat: &MultiPattern, column: usize) -> Vec<(String, bool)> {
+    pat.column_pattern(column)
+        .atoms
+        .iter()
+        .map(|atom| (atom.needle_text().to_string(), atom.negative))
+        .collect()
+}
+
+#[test]
+fn column_query() {
+    let mut pat = MultiPattern::new(3);
+    pat.set_column_name(0, Some("name"));
+    pat.set_column_name(1, Some("path"));
+    pat.set_column_name(2, Some("ext"));
+    assert_eq!(pat.column_name(1), Some("path"));
+    pat.reparse_query(
+        "name:foo path:src/ !ext:rs bar other:baz",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    assert_eq!(
+        column_text(&pat, 0),
+        [
+            ("foo".to_owned(), false),
+            ("bar".to_owned(), false),
+            ("other:baz".to_owned(), false)
+        ]
+    );
+    assert_eq!(column_text(&pat, 1), [("src/".to_owned(), false)]);
+    assert_eq!(column_text(&pat, 2), [("rs".to_owned(), true)]);
+
+    // escaped column names are matched literally
+    pat.reparse_query("path\\:src", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(column_text(&pat, 0), [("path:src".to_owned(), false)]);
+    assert!(pat.column_pattern(1).atoms.is_empty());
+    assert!(pat.column_pattern(2).atoms.is_empty());
+}
+
+#[test]
+fn column_query_default_columns() {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let mut pat = MultiPattern::with_column_weights(&[2, 1, 0]);
+    pat.set_column_name(2, Some("ext"));
+    pat.set_default_columns(&[0, 1]);
+    assert_eq!(pat.default_columns(), [0, 1]);
+    pat.reparse_query("foo !bar ext:rs", CaseMatching::Smart, Normalization::Smart);
+    // negated atoms are matched against every default column, other atoms
+    // only have to match one of them
+    assert_eq!(column_text(&pat, 0), [("bar".to_owned(), true)]);
+    assert_eq!(column_text(&pat, 1), [("bar".to_owned(), true)]);
+    assert_eq!(column_text(&pat, 2), [("rs".to_owned(), false)]);
+    let defaults: Vec<_> = pat
+        .default_pattern()
+        .atoms
+        .iter()
+        .map(|atom| atom.needle_text().to_string())
+        .collect();
+    assert_eq!(defaults, ["foo"]);
+
+    let item = |name: &str, path: &str| -> Vec<Utf32String> {
+        vec![name.into(), path.into(), "rs".into()]
+    };
+    let foo = Pattern::parse("foo", CaseMatching::Smart, Normalization::Smart)
+        .score(Utf32Str::Ascii(b"foo"), &mut matcher)
+        .unwrap();
+    assert_eq!(pat.score(&item("foo", "src"), &mut matcher), Some(2 * foo));
+    assert_eq!(pat.score(&item("main", "foo"), &mut matcher), Some(foo));
+    assert_eq!(pat.score(&item("main", "src"), &mut matcher), None);
+    assert_eq!(pat.score(&item("foo", "bar"), &mut matcher), None);
+    // only the column with the best weighted score is highlighted
+    let mut indices = vec![Vec::new(); 3];
+    let score = pat.indices(&item("foo", "foo"), &mut matcher, &mut indices);
+    assert_eq!(score, Some(2 * foo));
+    assert_eq!(indices, [vec![0, 1, 2], vec![], vec![0, 1]]);
+
+    pat.reset_status();
+    pat.reparse_query(
+        "fooo !bar ext:rs",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    assert_eq!(pat.status(), Status::Update);
+    assert!(pat
+        .cols
+        .iter()
+        .all(|(_, status)| *status == Status::Unchanged));
+    pat.reset_status();
+    // the same atoms match different items with other default columns
+    pat.set_default_columns(&[1]);
+    pat.reparse_query(
+        "fooo !bar ext:rs",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    assert_eq!(pat.status(), Status::Rescore);
+    assert_eq!(
+        column_text(&pat, 1),
+        [("fooo".to_owned(), false), ("bar".to_owned(), true)]
+    );
+    assert!(pat.default_pattern().atoms.is_empty());
+}
+
+#[test]
+fn column_query_status() {
+    let mut pat = MultiPattern::new(2);
+    pat.set_column_name(1, Some("path"));
+    pat.reparse_query("fo path:sr", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.status(), Status::Rescore);
+    pat.reset_status();
+
+    // only appending to a column allows incremental matching
+    pat.reparse_query("foo path:sr", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.cols[0].1, Status::Update);
+    assert_eq!(pat.cols[1].1, Status::Unchanged);
+    pat.reset_status();
+
+    pat.reparse_query("foo path:s", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.cols[0].1, Status::Unchanged);
+    assert_eq!(pat.cols[1].1, Status::Rescore);
+    pat.reset_status();
+
+    // reparsing a column directly invalidates the stored query text
+    pat.reparse(0, "foo", CaseMatching::Smart, Normalization::Smart, false);
+    pat.reset_status();
+    pat.reparse_query("foo path:s", CaseMatching::Smart, Normalization::Smart);
+    assert_eq!(pat.cols[0].1, Status::Rescore);
+}
+
+#[test]
+fn append_alternative() {
+    let mut pat = MultiPattern::new(1);
+    pat.reparse(0, "foo |", CaseMatching::Smart, Normalization::Smart, false);
+    pat.reset_status();
+    pat.reparse(
+        0,
+        "foo | b",
+        CaseMatching::Smart,
+        Normalization::Smart,
+        true,
+    );
+    assert_eq!(pat.status(), Status::Rescore);
+    pat.reset_status();
+    pat.reparse(
+        0,
+        "foo | ba",
+        CaseMatching::Smart,
+        Normalization::Smart,
+        true,
+    );
+    assert_eq!(pat.status(), Status::Update);
+}
+
+#[test]
+fn column_query_alternatives() {
+    let mut pat = MultiPattern::new(2);
+    pat.set_column_name(1, Some("ext"));
+    pat.reparse_query(
+        "ext:rs | ext:c foo | bar | ext:h",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    let alternatives = |column: usize| -> Vec<bool> {
+        pat.column_pattern(column)
+            .atoms
+            .iter()
+            .map(|atom| atom.is_alternative())
+            .collect()
+    };
+    assert_eq!(alternatives(0), [false, true]);
+    // the alternative between `bar` and `ext:h` is dropped
+    assert_eq!(alternatives(1), [false, true, false]);
+}
@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// The items selected by the user of a [`Nucleo`](crate::Nucleo) instance
+/// (see [`Snapshot::selection`](crate::Snapshot::selection) and
+/// [`Nucleo::selection_mut`](crate::Nucleo::selection_mut)).
+///
+/// Items are identified by their index so the selection is not affected by
+/// the matches being reordered. Removed items are deselected (and the indices
+/// are updated by a [compaction](crate::Nucleo::compact)) once the snapshot
+/// observes the change. When the matcher is [restarted](crate::Nucleo::restart)
+/// the selected items that have a [key](crate::Injector::push_with_key) are
+/// selected again as soon as an item with the same key was injected and
+/// matched, all other items are deselected.
+#[derive(Debug, Default, Clone)]
+pub struct Selection {
+    /// maps the selected item indices to their position in `order`
+    items: HashMap<u32, u64>,
+    /// the selected item indices in the order they were selected
+    order: BTreeMap<u64, u32>,
+    /// the position in `order` and key of items that were selected before
+    /// a restart and were not injected again yet
+    pending_keys: Vec<(u64, u64)>,
+    next: u64,
+}
+
+impl Selection {
+    /// Selects the item at the given index. Returns `false` if the item was
+    /// already selected.
+    pub fn select(&mut self, idx: u32) -> bool {
+        if self.items.contains_key(&idx) {
+            return false;
+        }
+        self.insert(self.next, idx);
+        self.next += 1;
+        true
+    }
+
+    /// Deselects the item at the given index. Returns `false` if the item was
+    /// not selected.
+    pub fn deselect(&mut self, idx: u32) -> bool {
+        let Some(pos) = self.items.remove(&idx) else {
+            return false;
+        };
+        self.order.remove(&pos);
+        true
+    }
+
+    /// Toggles the selection of the item at the given index. Returns whether
+    /// the item is selected now.
+    pub fn toggle(&mut self, idx: u32) -> bool {
+        !self.deselect(idx) && self.select(idx)
+    }
+
+    /// Returns whether the item at the given index is selected.
+    pub fn is_selected(&self, idx: u32) -> bool {
+        self.items.contains_key(&idx)
+    }
+
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether no items are selected.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Deselects all items, including items that would be selected again
+    /// after a restart.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.order.clear();
+        self.pending_keys.clear();
+    }
+
+    /// Returns an iterator over the indices of the selected items in the
+    /// order they were selected.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_ {
+        self.order.values().copied()
+    }
+
+    fn insert(&mut self, pos: u64, idx: u32) {
+        self.items.insert(idx, pos);
+        self.order.insert(pos, idx);
+    }
+
+    /// Only keeps the selected items for which `keep` returns `true`.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(u32) -> bool) {
+        let items = &mut self.items;
+        self.order.retain(|_, &mut idx| {
+            let keep = keep(idx);
+            if !keep {
+                items.remove(&idx);
+            }
+            keep
+        });
+    }
+
+    /// Maps the selected item indices to `new_indices[idx]`, items that are
+    /// mapped to `u32::MAX` (or that are out of bounds) are deselected.
+    pub(crate) fn remap(&mut self, new_indices: &[u32]) {
+        self.items.clear();
+        let items = &mut self.items;
+        self.order.retain(|&pos, idx| {
+            *idx = new_indices.get(*idx as usize).copied().unwrap_or(u32::MAX);
+            if *idx == u32::MAX {
+                return false;
+            }
+            items.insert(*idx, pos);
+            true
+        });
+    }
+
+    /// Deselects all items and remembers the keys of the selected items so
+    /// that they can be selected again by [`Selection::resolve`].
+    pub(crate) fn detach(&mut self, key: impl Fn(u32) -> Option<u64>) {
+        self.pending_keys.extend(
+            self.order
+                .iter()
+                .filter_map(|(&pos, &idx)| Some((pos, key(idx)?))),
+        );
+        self.items.clear();
+        self.order.clear();
+    }
+
+    /// Selects the items of the pending keys that `item_index` resolves.
+    pub(crate) fn resolve(&mut self, item_index: impl Fn(u64) -> Option<u32>) {
+        if self.pending_keys.is_empty() {
+            return;
+        }
+        let mut pending_keys = std::mem::take(&mut self.pending_keys);
+        pending_keys.retain(|&(pos, key)| {
+            let Some(idx) = item_index(key) else {
+                return true;
+            };
+            if !self.items.contains_key(&idx) {
+                self.insert(pos, idx);
+            }
+            false
+        });
+        self.pending_keys = pending_keys;
+    }
+}
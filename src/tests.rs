@@ -1,5 +1,8 @@
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use nucleo_matcher::pattern::{CaseMatching, Normalization};
 use nucleo_matcher::Config;
 
 use crate::Nucleo;
@@ -25,3 +28,766 @@ fn active_injector_count() {
     drop(injector3);
     assert_eq!(nucleo.active_injectors(), 0);
 }
+
+#[test]
+fn tick_until_done() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(2), 1);
+    nucleo
+        .pattern
+        .reparse(0, "1", CaseMatching::Smart, Normalization::Smart, false);
+    let injector = nucleo.injector();
+    let producer = thread::spawn(move || {
+        for i in 0..1000 {
+            injector.push(i, |i, cols| cols[0] = i.to_string().into());
+            if i % 100 == 0 {
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+    });
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.item_count(), 1000);
+    // all numbers that contain a 1
+    assert_eq!(snapshot.matched_item_count(), 271);
+    producer.join().unwrap();
+
+    // matching is also rerun after the pattern changes
+    nucleo
+        .pattern
+        .reparse(0, "'99", CaseMatching::Smart, Normalization::Smart, false);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), 19);
+}
+
+#[test]
+fn tick_until_done_deadline() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    injector.push(0, |_, cols| cols[0] = "foo".into());
+    let start = Instant::now();
+    let deadline = start + Duration::from_millis(50);
+    assert!(nucleo.tick_until_done(Some(deadline)).is_none());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    // items matched before the deadline are still visible
+    assert_eq!(nucleo.snapshot().matched_item_count(), 1);
+    drop(injector);
+    assert!(nucleo.tick_until_done(Some(Instant::now())).is_some());
+}
+
+#[test]
+fn tick_until_done_injector_dropped() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    for i in 0..200 {
+        let injector = nucleo.injector();
+        injector.push(i, |_, cols| cols[0] = "foo".into());
+        let producer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(1));
+            drop(injector)
+        });
+        // the last injector is dropped while `tick_until_done` is waiting
+        // for it, the wakeup must not be missed (or wait for the deadline)
+        let start = Instant::now();
+        let snapshot = nucleo.tick_until_done(Some(start + Duration::from_secs(10)));
+        assert_eq!(snapshot.unwrap().item_count(), i + 1);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        producer.join().unwrap();
+    }
+}
+
+/// Minimal executor so the async tests don't need a runtime.
+#[cfg(feature = "async")]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::pin::pin;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct ThreadWaker(thread::Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(res) => return res,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn changed_future() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(2), 1);
+    let injector = nucleo.injector();
+    let producer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        injector.push(1, |_, cols| cols[0] = "foo".into());
+    });
+    // the first snapshot (without any items) is available immediately, the
+    // item arrives later and wakes up the future again
+    let mut snapshots = 0;
+    while nucleo.snapshot().matched_item_count() == 0 {
+        assert!(block_on(nucleo.changed()).changed);
+        snapshots += 1;
+        assert!(snapshots < 10);
+    }
+    assert_eq!(nucleo.snapshot().matched_item_count(), 1);
+    producer.join().unwrap();
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn status_stream() {
+    use futures_core::Stream;
+    use std::future::poll_fn;
+    use std::pin::Pin;
+
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(2), 1);
+    let injector = nucleo.injector();
+    let producer = thread::spawn(move || {
+        for i in 0..10_000 {
+            injector.push(i, |i, cols| cols[0] = i.to_string().into());
+        }
+    });
+    producer.join().unwrap();
+    nucleo
+        .pattern
+        .reparse(0, "12", CaseMatching::Smart, Normalization::Smart, false);
+    let mut stream = nucleo.status_stream();
+    let mut updates = 0;
+    loop {
+        let status = block_on(poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)))
+            .expect("the stream never ends");
+        updates += 1;
+        if !status.running {
+            break;
+        }
+    }
+    assert!(updates >= 1);
+    drop(stream);
+    assert_eq!(nucleo.snapshot().item_count(), 10_000);
+    assert_eq!(
+        nucleo.snapshot().matched_item_count(),
+        (0..10_000u32)
+            .filter(|i| {
+                let s = i.to_string();
+                s.find('1').is_some_and(|pos| s[pos + 1..].contains('2'))
+            })
+            .count() as u32
+    );
+}
+
+#[test]
+fn column_weights_change_rank() {
+    let mut nucleo: Nucleo<&str> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 2);
+    let injector = nucleo.injector();
+    // (symbol, path): "symbol" matches well in the symbol column and poorly
+    // in the path column, "path" the other way around
+    for (data, symbol, path) in [("symbol", "ab", "a____b"), ("path", "a____b", "ab")] {
+        injector.push(data, |_, cols| {
+            cols[0] = symbol.into();
+            cols[1] = path.into();
+        });
+    }
+    drop(injector);
+    for column in 0..2 {
+        nucleo.pattern.reparse(
+            column,
+            "ab",
+            CaseMatching::Smart,
+            Normalization::Smart,
+            false,
+        );
+    }
+    let mut rank = |symbol_weight, path_weight| -> Vec<&str> {
+        nucleo.pattern.set_column_weight(0, symbol_weight);
+        nucleo.pattern.set_column_weight(1, path_weight);
+        let snapshot = nucleo.tick_until_done(None).unwrap();
+        snapshot.matched_items(..).map(|item| *item.data).collect()
+    };
+    assert_eq!(rank(2, 1), ["symbol", "path"]);
+    assert_eq!(rank(1, 2), ["path", "symbol"]);
+    assert_eq!(rank(3, 1), ["symbol", "path"]);
+}
+
+#[test]
+fn column_query() {
+    let mut nucleo: Nucleo<&str> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 2);
+    let injector = nucleo.injector();
+    for (name, ext) in [("main", "rs"), ("main", "c"), ("lib", "rs")] {
+        injector.push(name, |_, cols| {
+            cols[0] = name.into();
+            cols[1] = ext.into();
+        });
+    }
+    drop(injector);
+    nucleo.pattern.set_column_name(1, Some("ext"));
+    nucleo
+        .pattern
+        .reparse_query("ma ext:rs", CaseMatching::Smart, Normalization::Smart);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), 1);
+    nucleo
+        .pattern
+        .reparse_query("!ext:rs", CaseMatching::Smart, Normalization::Smart);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), 1);
+    assert_eq!(*snapshot.get_matched_item(0).unwrap().data, "main");
+}
+
+#[test]
+fn typos_append() {
+    let mut config = Config::DEFAULT;
+    config.max_typos = 1;
+    let mut nucleo: Nucleo<&str> = Nucleo::new(config, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for item in ["rexi", "foo"] {
+        injector.push(item, |item, cols| cols[0] = (*item).into());
+    }
+    drop(injector);
+    nucleo
+        .pattern
+        .reparse(0, "rec", CaseMatching::Smart, Normalization::Smart, false);
+    assert_eq!(
+        nucleo.tick_until_done(None).unwrap().matched_item_count(),
+        0
+    );
+    // the longer pattern tolerates a typo and therefore matches more items
+    nucleo
+        .pattern
+        .reparse(0, "reci", CaseMatching::Smart, Normalization::Smart, true);
+    assert_eq!(
+        nucleo.tick_until_done(None).unwrap().matched_item_count(),
+        1
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_append() {
+    let mut nucleo: Nucleo<&str> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for item in ["src/foo.rs", "foo.rs", "bar.rs"] {
+        injector.push(item, |item, cols| cols[0] = (*item).into());
+    }
+    drop(injector);
+    nucleo
+        .pattern
+        .reparse(0, "/foo", CaseMatching::Smart, Normalization::Smart, false);
+    assert_eq!(
+        nucleo.tick_until_done(None).unwrap().matched_item_count(),
+        1
+    );
+    // closing the regex turns the fuzzy atom `/foo` into the regex `foo`
+    // which also matches items without a `/`
+    nucleo
+        .pattern
+        .reparse(0, "/foo/", CaseMatching::Smart, Normalization::Smart, true);
+    assert_eq!(
+        nucleo.tick_until_done(None).unwrap().matched_item_count(),
+        2
+    );
+}
+
+#[test]
+fn match_highlights() {
+    let mut nucleo: Nucleo<&str> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 2);
+    let injector = nucleo.injector();
+    for (name, path) in [("foo_bar", "src/foo.rs"), ("baz", "src/baz.rs")] {
+        injector.push(name, |_, cols| {
+            cols[0] = name.into();
+            cols[1] = path.into();
+        });
+    }
+    drop(injector);
+    nucleo.pattern.set_column_name(1, Some("path"));
+    // the overlapping atoms `foo` and `fo` produce duplicate indices
+    nucleo.pattern.reparse_query(
+        "foo fo ba path:src path:rs",
+        CaseMatching::Smart,
+        Normalization::Smart,
+    );
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), 1);
+    let mut matcher = nucleo_matcher::Matcher::new(Config::DEFAULT);
+    let highlights = snapshot.match_highlights(0, &mut matcher).unwrap();
+    assert_eq!(highlights, [vec![0..3, 4..6], vec![0..3, 8..10]]);
+    assert_eq!(snapshot.match_highlights(1, &mut matcher), None);
+
+    // the item is matched with the config of the worker (that tolerates
+    // typos) and not the config of `matcher`
+    let mut config = Config::DEFAULT;
+    config.max_typos = 1;
+    nucleo.update_config(config);
+    nucleo
+        .pattern
+        .reparse_query("fobo_bar", CaseMatching::Smart, Normalization::Smart);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), 1);
+    let highlights = snapshot.match_highlights(0, &mut matcher).unwrap();
+    assert_eq!(highlights[0].len(), 1);
+    assert_eq!(highlights[0][0], 0..7);
+    assert!(highlights[1].is_empty());
+    assert_eq!(matcher.config, Config::DEFAULT);
+}
+
+#[test]
+fn remove_items() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for i in 0..100 {
+        injector.push(i, |i, cols| cols[0] = i.to_string().into());
+    }
+    nucleo
+        .pattern
+        .reparse(0, "1", CaseMatching::Smart, Normalization::Smart, false);
+    // the injector is still alive so `tick_until_done` can't be used here
+    while nucleo.tick(10).running {}
+    assert_eq!(nucleo.snapshot().matched_item_count(), 19);
+
+    assert!(injector.remove(1));
+    assert!(injector.remove(2));
+    assert!(!injector.remove(2));
+    assert!(injector.get(1).is_none());
+    let new = injector.replace(10, 100, |i, cols| cols[0] = i.to_string().into());
+    assert_eq!(new, Some(100));
+    assert_eq!(injector.injected_items(), 101);
+    assert_eq!(injector.removed_items(), 3);
+    drop(injector);
+
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.item_count(), 98);
+    // 1 and 10 were removed, 100 was added
+    assert_eq!(snapshot.matched_item_count(), 18);
+    assert!(snapshot
+        .matched_items(..)
+        .all(|item| ![1, 2, 10].contains(item.data)));
+    assert!(snapshot.get_item(1).is_none());
+
+    // removed items also never show up with an empty pattern
+    nucleo
+        .pattern
+        .reparse(0, "", CaseMatching::Smart, Normalization::Smart, false);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), 98);
+    assert!(snapshot
+        .matched_items(..)
+        .all(|item| ![1, 2, 10].contains(item.data)));
+}
+
+#[test]
+fn compact() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for i in 0..100 {
+        injector.push(i, |i, cols| cols[0] = i.to_string().into());
+    }
+    for i in (0..100).step_by(2) {
+        injector.remove(i);
+    }
+    nucleo
+        .pattern
+        .reparse(0, "1", CaseMatching::Smart, Normalization::Smart, false);
+    while nucleo.tick(10).running {}
+    // compaction needs exclusive access to the items
+    assert_eq!(nucleo.compact(), None);
+    drop(injector);
+
+    let matches: Vec<u32> = nucleo
+        .snapshot()
+        .matched_items(..)
+        .map(|item| *item.data)
+        .collect();
+    let compaction = nucleo.compact().unwrap();
+    assert_eq!(compaction.dropped_items(), 50);
+    assert_eq!(compaction.new_index(2), None);
+    assert_eq!(compaction.new_index(3), Some(1));
+    assert_eq!(compaction.new_index(100), None);
+    assert!(compaction.iter().all(|(old, new)| old == 2 * new + 1));
+    // the snapshot was remapped to the new indices
+    let snapshot = nucleo.snapshot();
+    let remapped: Vec<u32> = snapshot.matched_items(..).map(|item| *item.data).collect();
+    assert_eq!(matches, remapped);
+    for match_ in snapshot.matches() {
+        assert_eq!(
+            *snapshot.get_item(match_.idx).unwrap().data,
+            2 * match_.idx + 1
+        );
+    }
+
+    let injector = nucleo.injector();
+    assert_eq!(injector.injected_items(), 50);
+    assert_eq!(
+        injector.push(101, |i, cols| cols[0] = i.to_string().into()),
+        50
+    );
+    drop(injector);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.item_count(), 51);
+    assert_eq!(snapshot.matched_item_count(), matches.len() as u32 + 1);
+}
+
+#[test]
+fn item_keys() {
+    let mut nucleo: Nucleo<&str> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let files = ["foo.rs", "bar.rs", "baz.rs"];
+    let injector = nucleo.injector();
+    for (key, file) in files.into_iter().enumerate() {
+        injector.push_with_key(key as u64, file, |file, cols| cols[0] = (*file).into());
+    }
+    drop(injector);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.item_index(1), Some(1));
+    assert_eq!(snapshot.item_index(3), None);
+
+    // the keys survive a restart that injects the items in a different order
+    nucleo.restart(true);
+    assert_eq!(nucleo.snapshot().item_index(1), None);
+    let injector = nucleo.injector();
+    for key in [2, 0, 1] {
+        injector.push_with_key(key as u64, files[key], |file, cols| {
+            cols[0] = (*file).into()
+        });
+    }
+    injector.push("unkeyed", |_, cols| cols[0] = "unkeyed".into());
+    while nucleo.tick(10).running {}
+    let snapshot = nucleo.snapshot();
+    assert_eq!(snapshot.item_index(1), Some(2));
+    assert_eq!(*snapshot.get_item(2).unwrap().data, "bar.rs");
+
+    // replaced items keep their key, removed items lose it
+    let replaced = injector.replace(2, "bar.c", |_, cols| cols[0] = "bar.c".into());
+    assert_eq!(replaced, Some(4));
+    assert!(injector.remove(0));
+    drop(injector);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.item_index(1), Some(4));
+    assert_eq!(snapshot.item_index(2), None);
+
+    // compaction updates the indices of the keys
+    let compaction = nucleo.compact().unwrap();
+    assert_eq!(compaction.new_index(4), Some(2));
+    assert_eq!(nucleo.snapshot().item_index(0), Some(0));
+    assert_eq!(nucleo.snapshot().item_index(1), Some(2));
+}
+
+#[test]
+fn item_keys_streaming() {
+    let mut nucleo: Nucleo<u64> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    // the key index is updated in many small increments
+    for batch in 0..100 {
+        for key in batch * 10..(batch + 1) * 10 {
+            injector.push_with_key(key, key, |key, cols| cols[0] = key.to_string().into());
+        }
+        while nucleo.tick(10).running {}
+        let snapshot = nucleo.snapshot();
+        for key in 0..(batch + 1) * 10 {
+            assert_eq!(snapshot.item_index(key), Some(key as u32));
+        }
+    }
+
+    // the last item pushed with a key wins, removing it doesn't restore the
+    // older item until the next compaction
+    injector.push_with_key(7, 7, |_, cols| cols[0] = "7".into());
+    while nucleo.tick(10).running {}
+    assert_eq!(nucleo.snapshot().item_index(7), Some(1000));
+    assert!(injector.remove(1000));
+    drop(injector);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.item_index(7), None);
+    assert_eq!(snapshot.item_index(8), Some(8));
+    nucleo.compact().unwrap();
+    assert_eq!(nucleo.snapshot().item_index(7), Some(7));
+}
+
+#[test]
+fn selection() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for i in 0..20 {
+        injector.push_with_key(i.into(), i, |i, cols| cols[0] = i.to_string().into());
+    }
+    injector.push(20, |_, cols| cols[0] = "20".into());
+    drop(injector);
+    nucleo.tick_until_done(None);
+
+    let selection = nucleo.selection_mut();
+    assert!(selection.select(15));
+    assert!(selection.select(3));
+    assert!(!selection.select(3));
+    assert!(selection.select(20));
+    assert!(selection.toggle(11));
+    assert!(!selection.toggle(11));
+    assert!(selection.select(1));
+    assert!(selection.deselect(1));
+    assert!(!selection.deselect(1));
+    assert_eq!(selection.len(), 3);
+    let selected: Vec<u32> = nucleo
+        .snapshot()
+        .selected_items()
+        .map(|i| *i.data)
+        .collect();
+    assert_eq!(selected, [15, 3, 20]);
+
+    // the selection is not affected by the matches being reordered
+    nucleo
+        .pattern
+        .reparse(0, "1", CaseMatching::Smart, Normalization::Smart, false);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    let ranked: Vec<u32> = snapshot.selected_matches().map(|m| m.idx).collect();
+    assert_eq!(ranked, [15]);
+    assert_eq!(snapshot.selection().iter().collect::<Vec<_>>(), [15, 3, 20]);
+
+    nucleo.select_all_matched();
+    assert_eq!(nucleo.snapshot().selection().len(), 13);
+    nucleo.selection_mut().clear();
+    nucleo.selection_mut().select(15);
+    nucleo.selection_mut().select(20);
+
+    // keyed items are selected again after a restart
+    nucleo.restart(false);
+    assert!(nucleo.snapshot().selection().is_selected(15));
+    let injector = nucleo.injector();
+    for i in (0..20).rev() {
+        injector.push_with_key(i.into(), i, |i, cols| cols[0] = i.to_string().into());
+    }
+    injector.push(20, |_, cols| cols[0] = "20".into());
+    drop(injector);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.selection().iter().collect::<Vec<_>>(), [4]);
+    assert_eq!(*snapshot.selected_items().next().unwrap().data, 15);
+
+    // removed items are deselected and compaction updates the indices
+    nucleo.selection_mut().select(10);
+    let injector = nucleo.injector();
+    assert!(injector.remove(4));
+    drop(injector);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.selection().iter().collect::<Vec<_>>(), [10]);
+    nucleo.compact().unwrap();
+    assert_eq!(
+        nucleo.snapshot().selection().iter().collect::<Vec<_>>(),
+        [9]
+    );
+}
+
+#[test]
+fn selection_out_of_bounds() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for i in 0..100 {
+        injector.push_with_key(i.into(), i, |i, cols| cols[0] = i.to_string().into());
+    }
+    drop(injector);
+    nucleo.tick_until_done(None);
+
+    // indices of items that don't exist are deselected by a compaction
+    nucleo.selection_mut().select(1000);
+    nucleo.selection_mut().select(50);
+    nucleo.compact().unwrap();
+    assert_eq!(
+        nucleo.snapshot().selection().iter().collect::<Vec<_>>(),
+        [50]
+    );
+
+    // also if items were removed
+    nucleo.selection_mut().select(1000);
+    let injector = nucleo.injector();
+    assert!(injector.remove(0));
+    drop(injector);
+    nucleo.tick_until_done(None);
+    nucleo.selection_mut().select(100);
+    nucleo.compact().unwrap();
+    assert_eq!(
+        nucleo.snapshot().selection().iter().collect::<Vec<_>>(),
+        [49]
+    );
+}
+
+#[test]
+fn match_rank() {
+    let mut nucleo: Nucleo<&str> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(1), 1);
+    let injector = nucleo.injector();
+    for item in ["abc", "xyz", "a_b_c"] {
+        injector.push(item, |item, cols| cols[0] = (*item).into());
+    }
+    drop(injector);
+    for track in [false, true] {
+        nucleo.track_match_ranks(track);
+        nucleo
+            .pattern
+            .reparse(0, "", CaseMatching::Smart, Normalization::Smart, false);
+        let snapshot = nucleo.tick_until_done(None).unwrap();
+        assert_eq!(snapshot.match_rank(2), Some(2));
+        // the better match moves to the top, the cursor can follow it
+        nucleo
+            .pattern
+            .reparse(0, "abc", CaseMatching::Smart, Normalization::Smart, false);
+        let snapshot = nucleo.tick_until_done(None).unwrap();
+        assert_eq!(snapshot.match_rank(0), Some(0));
+        assert_eq!(snapshot.match_rank(2), Some(1));
+        assert_eq!(snapshot.match_rank(1), None);
+        assert_eq!(snapshot.match_rank(3), None);
+    }
+}
+
+#[test]
+fn sort_limit() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(4), 1);
+    let injector = nucleo.injector();
+    for i in 0..10_000 {
+        injector.push(i, |i, cols| cols[0] = format!("{i}_{}", i % 7).into());
+    }
+    drop(injector);
+    nucleo
+        .pattern
+        .reparse(0, "1_", CaseMatching::Smart, Normalization::Smart, false);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    let sorted = snapshot.matches().to_vec();
+    assert_eq!(snapshot.sorted_item_count(), snapshot.matched_item_count());
+
+    nucleo.sort_limit(Some(10));
+    nucleo
+        .pattern
+        .reparse(0, "1", CaseMatching::Smart, Normalization::Smart, false);
+    nucleo.tick_until_done(None);
+    nucleo
+        .pattern
+        .reparse(0, "1_", CaseMatching::Smart, Normalization::Smart, false);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matched_item_count(), sorted.len() as u32);
+    assert_eq!(snapshot.sorted_item_count(), 10);
+    assert_eq!(snapshot.matches()[..10], sorted[..10]);
+    let mut matches = snapshot.matches().to_vec();
+    matches.sort_unstable_by_key(|m| m.idx);
+    let mut expected = sorted.clone();
+    expected.sort_unstable_by_key(|m| m.idx);
+    assert_eq!(matches, expected);
+
+    // accessing more matches sorts them on demand
+    assert_eq!(snapshot.matched_items(..100).count(), 100);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.sorted_item_count(), 100);
+    assert_eq!(snapshot.matches()[..100], sorted[..100]);
+
+    nucleo.sort_limit(None);
+    let snapshot = nucleo.tick_until_done(None).unwrap();
+    assert_eq!(snapshot.matches(), sorted);
+}
+
+#[test]
+fn restore_previous_matches() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(2), 1);
+    let injector = nucleo.injector();
+    let push = |range: std::ops::Range<u32>| {
+        for i in range {
+            injector.push(i, |i, cols| cols[0] = i.to_string().into());
+        }
+    };
+    let reparse = |nucleo: &mut Nucleo<u32>, pattern: &str, append: bool| {
+        nucleo.pattern.reparse(
+            0,
+            pattern,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            append,
+        );
+        while nucleo.tick(10).running {}
+        nucleo.snapshot().matches().to_vec()
+    };
+    push(0..1000);
+    reparse(&mut nucleo, "1", false);
+    reparse(&mut nucleo, "12", true);
+    assert_eq!(reparse(&mut nucleo, "123", true).len(), 1);
+    // items that were added or removed while the longer pattern was active
+    push(1000..2000);
+    assert!(injector.remove(1));
+    assert!(injector.remove(1234));
+    reparse(&mut nucleo, "123", false);
+    // removing chars from the end restores the matches of the shorter
+    // pattern, the result must be the same as matching all items again
+    let restored = reparse(&mut nucleo, "1", false);
+    nucleo.update_config(Config::DEFAULT);
+    reparse(&mut nucleo, "", false);
+    let rematched = reparse(&mut nucleo, "1", false);
+    assert_eq!(restored, rematched);
+    assert!(restored.iter().all(|m| m.idx != 1 && m.idx != 1234));
+    assert_eq!(
+        restored.len(),
+        (2..2000)
+            .filter(|i| *i != 1234 && i.to_string().contains('1'))
+            .count()
+    );
+}
+
+#[test]
+fn restore_previous_matches_in_flight() {
+    let mut nucleo: Nucleo<u32> = Nucleo::new(Config::DEFAULT, Arc::new(|| ()), Some(2), 1);
+    let injector = nucleo.injector();
+    let push = |range: std::ops::Range<u32>| {
+        for i in range {
+            injector.push(i, |i, cols| cols[0] = i.to_string().into());
+        }
+    };
+    let reparse = |nucleo: &mut Nucleo<u32>, pattern: &str, append: bool| {
+        nucleo.pattern.reparse(
+            0,
+            pattern,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            append,
+        );
+        while nucleo.tick(10).running {}
+        nucleo.snapshot().matches().to_vec()
+    };
+    push(0..100);
+    // item 100 stays in flight until its columns are filled
+    let (started_tx, started_rx) = mpsc::channel();
+    let (fill_tx, fill_rx) = mpsc::channel::<()>();
+    let in_flight = nucleo.injector();
+    let pusher = thread::spawn(move || {
+        in_flight.push(100, |i, cols| {
+            started_tx.send(()).unwrap();
+            fill_rx.recv().unwrap();
+            cols[0] = i.to_string().into();
+        });
+    });
+    started_rx.recv().unwrap();
+    push(101..200);
+    // the matcher keeps running while an item is in flight, so wait for the
+    // expected matches instead
+    let reparse_in_flight = |nucleo: &mut Nucleo<u32>, pattern: &str, append: bool| {
+        nucleo.pattern.reparse(
+            0,
+            pattern,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            append,
+        );
+        let expected = (0..200)
+            .filter(|i| *i != 100 && i.to_string().contains(&pattern[1..]))
+            .count() as u32;
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while nucleo.snapshot().matched_item_count() != expected {
+            assert!(Instant::now() < deadline);
+            nucleo.tick(10);
+        }
+        // the history is only recorded if the last run wasn't canceled
+        thread::sleep(Duration::from_millis(100));
+    };
+    reparse_in_flight(&mut nucleo, "'1", false);
+    reparse_in_flight(&mut nucleo, "'12", true);
+    fill_tx.send(()).unwrap();
+    pusher.join().unwrap();
+    reparse(&mut nucleo, "'12", false);
+    let restored = reparse(&mut nucleo, "'1", false);
+    nucleo.update_config(Config::DEFAULT);
+    reparse(&mut nucleo, "", false);
+    let rematched = reparse(&mut nucleo, "'1", false);
+    assert_eq!(restored, rematched);
+    assert!(restored.iter().any(|m| m.idx == 100));
+}
@@ -7,7 +7,9 @@ use nucleo_matcher::Config;
 use parking_lot::Mutex;
 use rayon::{prelude::*, ThreadPool};
 
-use crate::par_sort::par_quicksort;
+use crate::keys::KeyIndex;
+use crate::notify::Notify;
+use crate::par_sort::par_partial_sort;
 use crate::pattern::{self, MultiPattern};
 use crate::{boxcar, Match};
 
@@ -24,30 +26,77 @@ impl Matchers {
 unsafe impl Sync for Matchers {}
 unsafe impl Send for Matchers {}
 
+/// The maximum number of match sets kept in [`Worker::history`].
+const MAX_HISTORY: usize = 8;
+/// The maximum number of matches (of all match sets) kept in
+/// [`Worker::history`].
+const MAX_HISTORY_MATCHES: usize = 1 << 20;
+
+/// The matches of a previous pattern, see [`Worker::record_history`].
+struct History {
+    pattern: MultiPattern,
+    matches: Vec<Match>,
+    /// The items before this index that are not in `in_flight` were matched
+    /// against `pattern`, later items were not.
+    last_snapshot: u32,
+    in_flight: Vec<u32>,
+}
+
 pub(crate) struct Worker<T: Sync + Send + 'static> {
     pub(crate) running: bool,
     matchers: Matchers,
+    /// Whether typo tolerant matching is enabled (see [`Config::max_typos`]).
+    typos: bool,
+    /// The config of the matchers, shared with the snapshot.
+    pub(crate) config: Arc<Config>,
     pub(crate) matches: Vec<Match>,
     pub(crate) pattern: MultiPattern,
+    /// The pattern of the last run, see [`Worker::set_pattern`].
+    previous_pattern: MultiPattern,
     pub(crate) sort_results: bool,
     pub(crate) reverse_items: bool,
+    /// Only the best `sort_limit` matches (or `sort_depth` if that's larger)
+    /// are sorted if set.
+    pub(crate) sort_limit: Option<u32>,
+    /// The number of sorted matches requested by the snapshot.
+    pub(crate) sort_depth: Arc<AtomicU32>,
+    /// The number of matches at the start of `matches` that are sorted.
+    pub(crate) sorted: u32,
     pub(crate) canceled: Arc<AtomicBool>,
     pub(crate) should_notify: Arc<AtomicBool>,
     pub(crate) was_canceled: bool,
     pub(crate) last_snapshot: u32,
-    notify: Arc<(dyn Fn() + Sync + Send)>,
+    /// The number of removed items when the worker last ran. Removed items
+    /// are never part of `matches` or `in_flight`.
+    pub(crate) removed: u32,
+    notify: Arc<Notify>,
     pub(crate) items: Arc<boxcar::Vec<T>>,
     in_flight: Vec<u32>,
+    /// Maps the keys of all items that were processed to their index, shared
+    /// with the snapshot.
+    pub(crate) keys: KeyIndex,
+    /// The match sets of the patterns that were narrowed down by appending
+    /// to them, so that they can be restored when the appended text is
+    /// removed again.
+    history: Vec<History>,
 }
 
 impl<T: Sync + Send + 'static> Worker<T> {
     pub(crate) fn item_count(&self) -> u32 {
-        self.last_snapshot - self.in_flight.len() as u32
+        self.last_snapshot - self.in_flight.len() as u32 - self.removed
+    }
+    /// Returns whether `items` changed since the worker last ran.
+    pub(crate) fn is_outdated(&self, items: &boxcar::Vec<T>) -> bool {
+        items.count() > self.last_snapshot - self.in_flight.len() as u32
+            || items.removed() != self.removed
     }
     pub(crate) fn update_config(&mut self, config: Config) {
+        self.typos = config.max_typos != 0;
+        self.history.clear();
         for matcher in self.matchers.0.iter_mut() {
             matcher.get_mut().config = config.clone();
         }
+        self.config = Arc::new(config);
     }
     pub(crate) fn sort_results(&mut self, sort_results: bool) {
         self.sort_results = sort_results;
@@ -55,11 +104,135 @@ impl<T: Sync + Send + 'static> Worker<T> {
     pub(crate) fn reverse_items(&mut self, reverse_items: bool) {
         self.reverse_items = reverse_items;
     }
+    pub(crate) fn sort_limit(&mut self, sort_limit: Option<u32>) {
+        self.sort_limit = sort_limit;
+    }
+    /// Updates the pattern for the next run. The current pattern is kept
+    /// so that the next run can record its matches in the history.
+    pub(crate) fn set_pattern(&mut self, pattern: &MultiPattern) {
+        std::mem::swap(&mut self.pattern, &mut self.previous_pattern);
+        self.pattern.clone_from(pattern);
+    }
+    /// Remembers the matches of the previous pattern if the new pattern only
+    /// narrows them down (`status` is [`pattern::Status::Update`]), so that
+    /// they can be restored if the pattern is changed back later. The oldest
+    /// match sets are dropped to stay below [`MAX_HISTORY_MATCHES`], larger
+    /// match sets are not recorded at all.
+    fn record_history(&mut self, status: pattern::Status) {
+        if status != pattern::Status::Update
+            || self.was_canceled
+            || self.previous_pattern.is_empty()
+            || self.matches.len() > MAX_HISTORY_MATCHES
+        {
+            return;
+        }
+        let mut total: usize = self
+            .history
+            .iter()
+            .map(|history| history.matches.len())
+            .sum();
+        total += self.matches.len();
+        while self.history.len() == MAX_HISTORY || total > MAX_HISTORY_MATCHES {
+            total -= self.history.remove(0).matches.len();
+        }
+        self.history.push(History {
+            pattern: self.previous_pattern.clone(),
+            matches: self.matches.clone(),
+            last_snapshot: self.last_snapshot,
+            in_flight: self.in_flight.clone(),
+        });
+    }
+    /// Returns the index of the first item that wasn't processed yet.
+    fn processed_end(&self) -> u32 {
+        self.in_flight
+            .iter()
+            .copied()
+            .fold(self.last_snapshot, u32::min)
+    }
+    /// Restores the matches of a previous pattern that matches the same
+    /// items as the current pattern. Items that were not processed for the
+    /// previous pattern are added as unscored matches. Returns `false` if
+    /// there is no such pattern.
+    fn restore_history(&mut self) -> bool {
+        let Some(pos) = self
+            .history
+            .iter()
+            .rposition(|history| history.pattern.matches_same_items(&self.pattern))
+        else {
+            return false;
+        };
+        self.history.truncate(pos + 1);
+        let history = self.history.pop().unwrap();
+        self.matches = history.matches;
+        if self.items.removed() != 0 {
+            self.matches
+                .retain(|match_| match_.idx == u32::MAX || !self.items.is_removed(match_.idx));
+        }
+        // items that are still in flight are added once they are processed
+        let unprocessed = history
+            .in_flight
+            .into_iter()
+            .chain(history.last_snapshot..self.last_snapshot)
+            .filter(|&idx| !self.in_flight.contains(&idx) && !self.items.is_removed(idx));
+        self.matches
+            .extend(unprocessed.map(|idx| Match { score: 0, idx }));
+        true
+    }
+    /// Returns whether more matches need to be sorted than are currently
+    /// sorted, because the snapshot requested more sorted matches or the
+    /// sort limit was changed.
+    pub(crate) fn needs_sort(&self) -> bool {
+        self.sorted < self.sort_len().min(self.matches.len()) as u32
+    }
+    /// Returns the number of matches that should be sorted.
+    fn sort_len(&self) -> usize {
+        match self.sort_limit {
+            Some(limit) => limit.max(self.sort_depth.load(atomic::Ordering::Relaxed)) as usize,
+            None => self.matches.len(),
+        }
+    }
+
+    /// Replaces the items with their compacted version (see
+    /// [`boxcar::Vec::compact`]) and maps the matches to the new indices.
+    pub(crate) fn compact(&mut self, items: Arc<boxcar::Vec<T>>, new_indices: &[u32]) {
+        // in-flight items may have been initialized in the meantime, so all
+        // items starting at the first in-flight item are treated as new items
+        let end = self.processed_end();
+        let last_snapshot = new_indices[..end as usize]
+            .iter()
+            .filter(|&&idx| idx != u32::MAX)
+            .count() as u32;
+        // the sorted matches stay sorted since the order of the items is
+        // preserved, only the dropped matches must be accounted for
+        let (mut pos, mut sorted) = (0, 0);
+        self.matches.retain_mut(|match_| {
+            pos += 1;
+            let keep = match_.idx == u32::MAX || new_indices[match_.idx as usize] < last_snapshot;
+            if keep && match_.idx != u32::MAX {
+                match_.idx = new_indices[match_.idx as usize];
+            }
+            if keep && pos <= self.sorted {
+                sorted += 1;
+            }
+            keep
+        });
+        self.sorted = sorted;
+        if !self.keys.is_empty() {
+            self.keys.compact(new_indices, last_snapshot);
+        }
+        self.last_snapshot = last_snapshot;
+        self.removed = 0;
+        self.in_flight.clear();
+        self.history.clear();
+        self.items = items;
+        // the snapshot must pick up the new item count
+        self.running = true;
+    }
 
     pub(crate) fn new(
         worker_threads: Option<usize>,
         config: Config,
-        notify: Arc<(dyn Fn() + Sync + Send)>,
+        notify: Arc<Notify>,
         cols: u32,
     ) -> (ThreadPool, Self) {
         let worker_threads = worker_threads
@@ -75,18 +248,27 @@ impl<T: Sync + Send + 'static> Worker<T> {
         let worker = Worker {
             running: false,
             matchers: Matchers(matchers),
+            typos: config.max_typos != 0,
+            config: Arc::new(config),
             last_snapshot: 0,
+            removed: 0,
             matches: Vec::new(),
             // just a placeholder
             pattern: MultiPattern::new(cols as usize),
+            previous_pattern: MultiPattern::new(cols as usize),
             sort_results: true,
             reverse_items: false,
+            sort_limit: None,
+            sort_depth: Arc::new(AtomicU32::new(0)),
+            sorted: 0,
             canceled: Arc::new(AtomicBool::new(false)),
             should_notify: Arc::new(AtomicBool::new(false)),
             was_canceled: false,
             notify,
             items: Arc::new(boxcar::Vec::with_capacity(2 * 1024, cols)),
             in_flight: Vec::with_capacity(64),
+            keys: KeyIndex::default(),
+            history: Vec::new(),
         };
         (pool, worker)
     }
@@ -97,7 +279,7 @@ impl<T: Sync + Send + 'static> Worker<T> {
         self.matches.reserve(self.in_flight.len());
         self.in_flight.retain(|&idx| {
             let Some(item) = self.items.get(idx) else {
-                return true;
+                return !self.items.is_removed(idx);
             };
             if let Some(score) = pattern.score(item.matcher_columns, matchers.get()) {
                 self.matches.push(Match { score, idx });
@@ -110,7 +292,9 @@ impl<T: Sync + Send + 'static> Worker<T> {
             let in_flight = Mutex::new(&mut self.in_flight);
             let items = new_snapshot.map(|(idx, item)| {
                 let Some(item) = item else {
-                    in_flight.lock().push(idx);
+                    if !self.items.is_removed(idx) {
+                        in_flight.lock().push(idx);
+                    }
                     unmatched.fetch_add(1, atomic::Ordering::Relaxed);
                     return Match {
                         score: 0,
@@ -146,13 +330,31 @@ impl<T: Sync + Send + 'static> Worker<T> {
         });
     }
 
+    /// Removes the matches and in-flight items of items that were marked as
+    /// removed. Their keys stay in the key index until the next compaction.
+    fn remove_removed_matches(&mut self) {
+        self.matches
+            .retain(|match_| match_.idx == u32::MAX || !self.items.is_removed(match_.idx));
+        self.in_flight.retain(|&idx| !self.items.is_removed(idx));
+    }
+
+    /// Adds the keys of `indices` to the key index, items that are not
+    /// initialized yet are skipped.
+    fn index_keys(&mut self, indices: impl Iterator<Item = u32>) {
+        let items = &self.items;
+        self.keys
+            .extend(indices.filter_map(|idx| Some((items.key(idx)?, idx))));
+    }
+
     unsafe fn process_new_items_trivial(&mut self) {
         let new_snapshot = self.items.snapshot(self.last_snapshot);
         if new_snapshot.end() != self.last_snapshot {
             let end = new_snapshot.end();
             let items = new_snapshot.filter_map(|(idx, item)| {
                 if item.is_none() {
-                    self.in_flight.push(idx);
+                    if !self.items.is_removed(idx) {
+                        self.in_flight.push(idx);
+                    }
                     return None;
                 };
                 Some(Match { score: 0, idx })
@@ -162,17 +364,48 @@ impl<T: Sync + Send + 'static> Worker<T> {
         }
     }
 
-    pub(crate) unsafe fn run(&mut self, pattern_status: pattern::Status, cleared: bool) {
+    pub(crate) unsafe fn run(&mut self, mut pattern_status: pattern::Status, cleared: bool) {
+        if !cleared {
+            self.record_history(pattern_status);
+        }
+        // the number of tolerated typos grows with the length of the pattern
+        // so appending to the pattern can produce new matches
+        if self.typos && pattern_status == pattern::Status::Update {
+            pattern_status = pattern::Status::Rescore;
+        }
         self.running = true;
         self.was_canceled = false;
 
         if cleared {
             self.last_snapshot = 0;
+            self.removed = 0;
             self.in_flight.clear();
             self.matches.clear();
+            self.history.clear();
+            self.keys = KeyIndex::default();
         }
 
-        // TODO: be smarter around reusing past results for rescoring
+        // this must be loaded before the new items are snapshotted, all items
+        // that were removed so far are then part of the snapshot
+        let removed = self.items.removed();
+        if removed != self.removed {
+            self.removed = removed;
+            self.remove_removed_matches();
+        }
+
+        // items that are still in flight after this run are indexed once
+        // they were processed
+        let in_flight = self.in_flight.clone();
+        let last_snapshot = self.last_snapshot;
+        self.run_inner(pattern_status);
+        self.index_keys(
+            in_flight
+                .into_iter()
+                .chain(last_snapshot..self.last_snapshot),
+        );
+    }
+
+    unsafe fn run_inner(&mut self, mut pattern_status: pattern::Status) {
         if self.pattern.is_empty() {
             self.reset_matches();
             self.process_new_items_trivial();
@@ -180,13 +413,18 @@ impl<T: Sync + Send + 'static> Worker<T> {
             if canceled {
                 self.was_canceled = true;
             } else if self.should_notify.load(atomic::Ordering::Relaxed) {
-                (self.notify)();
+                self.notify.notify();
             }
             return;
         }
 
         if pattern_status == pattern::Status::Rescore {
-            self.reset_matches();
+            if self.restore_history() {
+                // only the restored matches need to be rescored
+                pattern_status = pattern::Status::Update;
+            } else {
+                self.reset_matches();
+            }
         }
 
         let mut unmatched = AtomicU32::new(0);
@@ -203,9 +441,13 @@ impl<T: Sync + Send + 'static> Worker<T> {
                         unmatched.fetch_add(1, atomic::Ordering::Relaxed);
                         return;
                     }
-                    // safety: in-flight items are never added to the matches
-                    let item = self.items.get_unchecked(match_.idx);
-                    if let Some(score) = pattern.score(item.matcher_columns, matchers.get()) {
+                    // in-flight items are never added to the matches so this
+                    // only fails for items that were removed in the meantime
+                    let score = self
+                        .items
+                        .get(match_.idx)
+                        .and_then(|item| pattern.score(item.matcher_columns, matchers.get()));
+                    if let Some(score) = score {
                         match_.score = score;
                     } else {
                         unmatched.fetch_add(1, atomic::Ordering::Relaxed);
@@ -217,6 +459,11 @@ impl<T: Sync + Send + 'static> Worker<T> {
             self.process_new_items(&unmatched);
         }
 
+        if self.sort_limit.is_some() {
+            // a partial sort doesn't move the unmatched items to the end
+            self.matches.retain(|match_| match_.idx != u32::MAX);
+            *unmatched.get_mut() = 0;
+        }
         let canceled = self.sort_matches();
         if canceled {
             self.was_canceled = true;
@@ -224,15 +471,18 @@ impl<T: Sync + Send + 'static> Worker<T> {
             self.matches
                 .truncate(self.matches.len() - take(unmatched.get_mut()) as usize);
             if self.should_notify.load(atomic::Ordering::Relaxed) {
-                (self.notify)();
+                self.notify.notify();
             }
         }
     }
 
     unsafe fn sort_matches(&mut self) -> bool {
+        let sort_len = self.sort_len();
+        self.sorted = sort_len.min(self.matches.len()) as u32;
         if self.sort_results {
-            par_quicksort(
+            par_partial_sort(
                 &mut self.matches,
+                sort_len,
                 |match1, match2| {
                     if match1.score != match2.score {
                         return match1.score > match2.score;
@@ -271,8 +521,9 @@ impl<T: Sync + Send + 'static> Worker<T> {
                 &self.canceled,
             )
         } else {
-            par_quicksort(
+            par_partial_sort(
                 &mut self.matches,
+                sort_len,
                 |match1, match2| {
                     if match1.idx == u32::MAX {
                         return false;
@@ -297,5 +548,8 @@ impl<T: Sync + Send + 'static> Worker<T> {
             .extend((0..self.last_snapshot).map(|idx| Match { score: 0, idx }));
         // there are usually only very few in flight items (one for each writer)
         self.remove_in_flight_matches();
+        if self.items.removed() != 0 {
+            self.remove_removed_matches();
+        }
     }
 }